@@ -0,0 +1,65 @@
+#!/usr/bin/env node
+
+/**
+ * 生成 Rust 端使用的路由表 src-tauri/routes.json
+ * 工具和分类来自 src/data，静态页面在下方维护
+ * 修改工具列表后运行: node scripts/generate-routes.mjs
+ */
+
+import fs from 'fs'
+import path from 'path'
+import { fileURLToPath } from 'url'
+import { tools } from '../src/data/tools.js'
+import { categories } from '../src/data/categories.js'
+
+const __dirname = path.dirname(fileURLToPath(import.meta.url))
+const outputPath = path.join(__dirname, '../src-tauri/routes.json')
+
+// 菜单、托盘和深层链接可以跳转的静态页面
+const pages = [
+  { id: 'home', name: '首页', path: '/' },
+  { id: 'all', name: '全部工具', path: '/all/' },
+  { id: 'explore', name: '发现', path: '/explore/' },
+  { id: 'favorites', name: '我的收藏', path: '/favorites/' },
+  { id: 'recent', name: '最近使用', path: '/recent/' },
+  { id: 'tags', name: '标签', path: '/tags/' },
+  { id: 'about', name: '关于', path: '/about/' },
+  { id: 'feedback', name: '意见反馈', path: '/feedback/' },
+  { id: 'updates', name: '版本更新', path: '/feedback/' },
+  { id: 'faq', name: '常见问题', path: '/faq/' },
+  { id: 'download', name: '下载', path: '/download/' },
+  { id: 'sitemap', name: '网站地图', path: '/sitemap/' },
+  { id: 'privacy', name: '隐私政策', path: '/privacy/' },
+  { id: 'terms', name: '服务条款', path: '/terms/' },
+  { id: 'cookie', name: 'Cookie 政策', path: '/cookie/' },
+  { id: 'notification-settings', name: '通知设置', path: '/settings/notifications/' }
+]
+
+// 工具 ID 去重，保留第一次出现的定义
+const seen = new Set()
+const toolRoutes = []
+for (const tool of tools) {
+  if (seen.has(tool.id)) continue
+  seen.add(tool.id)
+  toolRoutes.push({ id: tool.id, name: tool.name, category: tool.category })
+}
+
+const categoryRoutes = categories.map(category => ({ id: category.id, name: category.name }))
+
+// 每条路由一行，便于审阅 diff
+const format = items => items.map(item => '    ' + JSON.stringify(item)).join(',\n')
+const content = `{
+  "pages": [
+${format(pages)}
+  ],
+  "categories": [
+${format(categoryRoutes)}
+  ],
+  "tools": [
+${format(toolRoutes)}
+  ]
+}
+`
+
+fs.writeFileSync(outputPath, content)
+console.log(`✅ 路由表已生成: ${toolRoutes.length} 个工具, ${categoryRoutes.length} 个分类, ${pages.length} 个页面`)
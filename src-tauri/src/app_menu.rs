@@ -0,0 +1,230 @@
+// 应用菜单：由声明式定义生成，标签来自消息目录（跟随 language 设置），工具菜单由分类和收藏生成
+use crate::i18n;
+use tauri::menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::AppHandle;
+
+/// 菜单中打开页面的菜单项 ID 前缀
+pub const OPEN_PAGE_PREFIX: &str = "open_page:";
+
+/// 菜单中打开分类的菜单项 ID 前缀
+pub const OPEN_CATEGORY_PREFIX: &str = "open_category:";
+
+/// 系统提供的菜单项
+#[derive(Debug, Clone, Copy)]
+enum Predefined {
+    Undo,
+    Redo,
+    Cut,
+    Copy,
+    Paste,
+    SelectAll,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    Services,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    Hide,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    HideOthers,
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    ShowAll,
+}
+
+/// 菜单定义节点；label 为消息目录中 `menu.` 之后的键
+enum MenuNode {
+    Item { id: &'static str, label: &'static str, accelerator: Option<&'static str> },
+    Predefined { kind: Predefined, label: &'static str },
+    Separator,
+    Submenu { label: &'static str, children: &'static [MenuNode] },
+    /// 收藏和分类生成的工具菜单
+    Tools,
+}
+
+#[cfg(target_os = "macos")]
+const APP_MENU: MenuNode = MenuNode::Submenu {
+    label: "app",
+    children: &[
+        MenuNode::Item { id: "show_about", label: "about", accelerator: None },
+        MenuNode::Item { id: "check_updates", label: "check_updates", accelerator: None },
+        MenuNode::Separator,
+        MenuNode::Predefined { kind: Predefined::Services, label: "services" },
+        MenuNode::Separator,
+        MenuNode::Predefined { kind: Predefined::Hide, label: "hide" },
+        MenuNode::Predefined { kind: Predefined::HideOthers, label: "hide_others" },
+        MenuNode::Predefined { kind: Predefined::ShowAll, label: "show_all" },
+        MenuNode::Separator,
+        MenuNode::Item { id: "quit_app", label: "quit", accelerator: Some("CmdOrCtrl+Q") },
+    ],
+};
+
+#[cfg(not(target_os = "macos"))]
+const APP_MENU: MenuNode = MenuNode::Submenu {
+    label: "home",
+    children: &[
+        MenuNode::Item { id: "go_home", label: "go_home", accelerator: Some("CmdOrCtrl+Shift+H") },
+        MenuNode::Separator,
+        MenuNode::Item { id: "quit_app", label: "quit", accelerator: Some("CmdOrCtrl+Q") },
+    ],
+};
+
+/// 完整的菜单定义
+const MENU: &[MenuNode] = &[
+    APP_MENU,
+    MenuNode::Submenu {
+        label: "edit",
+        children: &[
+            MenuNode::Predefined { kind: Predefined::Undo, label: "undo" },
+            MenuNode::Predefined { kind: Predefined::Redo, label: "redo" },
+            MenuNode::Separator,
+            MenuNode::Predefined { kind: Predefined::Cut, label: "cut" },
+            MenuNode::Predefined { kind: Predefined::Copy, label: "copy" },
+            MenuNode::Predefined { kind: Predefined::Paste, label: "paste" },
+            MenuNode::Predefined { kind: Predefined::SelectAll, label: "select_all" },
+        ],
+    },
+    MenuNode::Submenu {
+        label: "view",
+        children: &[
+            #[cfg(target_os = "macos")]
+            MenuNode::Item { id: "go_home", label: "go_home", accelerator: Some("CmdOrCtrl+Shift+H") },
+            MenuNode::Item { id: "refresh_page", label: "refresh", accelerator: Some("CmdOrCtrl+R") },
+            MenuNode::Separator,
+            MenuNode::Item { id: "toggle_fullscreen", label: "fullscreen", accelerator: Some(FULLSCREEN_ACCELERATOR) },
+        ],
+    },
+    MenuNode::Tools,
+    MenuNode::Submenu {
+        label: "window",
+        children: &[
+            MenuNode::Item { id: "minimize_window", label: "minimize", accelerator: Some("CmdOrCtrl+M") },
+            MenuNode::Item { id: "toggle_maximize", label: "zoom", accelerator: None },
+            MenuNode::Separator,
+            MenuNode::Item { id: "toggle_mini_mode", label: "mini_mode", accelerator: None },
+            MenuNode::Item { id: "toggle_mini_click_through", label: "mini_click_through", accelerator: None },
+            MenuNode::Separator,
+            MenuNode::Item { id: "close_window", label: "close_window", accelerator: Some("CmdOrCtrl+W") },
+        ],
+    },
+    MenuNode::Submenu {
+        label: "help",
+        children: &[
+            MenuNode::Item { id: "check_updates", label: "check_updates", accelerator: None },
+            MenuNode::Item { id: "show_about", label: "about", accelerator: None },
+        ],
+    },
+];
+
+#[cfg(target_os = "macos")]
+const FULLSCREEN_ACCELERATOR: &str = "Ctrl+Cmd+F";
+
+#[cfg(not(target_os = "macos"))]
+const FULLSCREEN_ACCELERATOR: &str = "F11";
+
+/// 菜单标签，对应消息目录中的 `menu.<键>`
+fn label(key: &str) -> String {
+    i18n::t(&format!("menu.{}", key))
+}
+
+/// 按定义构建菜单项
+fn build_node(app_handle: &AppHandle, node: &MenuNode) -> tauri::Result<Box<dyn IsMenuItem<tauri::Wry>>> {
+    let item: Box<dyn IsMenuItem<tauri::Wry>> = match node {
+        MenuNode::Item { id, label: key, accelerator } => {
+            Box::new(MenuItem::with_id(app_handle, *id, label(key), true, *accelerator)?)
+        }
+        MenuNode::Predefined { kind, label: key } => {
+            let text = label(key);
+            let text = Some(text.as_str());
+            Box::new(match kind {
+                Predefined::Undo => PredefinedMenuItem::undo(app_handle, text)?,
+                Predefined::Redo => PredefinedMenuItem::redo(app_handle, text)?,
+                Predefined::Cut => PredefinedMenuItem::cut(app_handle, text)?,
+                Predefined::Copy => PredefinedMenuItem::copy(app_handle, text)?,
+                Predefined::Paste => PredefinedMenuItem::paste(app_handle, text)?,
+                Predefined::SelectAll => PredefinedMenuItem::select_all(app_handle, text)?,
+                Predefined::Services => PredefinedMenuItem::services(app_handle, text)?,
+                Predefined::Hide => PredefinedMenuItem::hide(app_handle, text)?,
+                Predefined::HideOthers => PredefinedMenuItem::hide_others(app_handle, text)?,
+                Predefined::ShowAll => PredefinedMenuItem::show_all(app_handle, text)?,
+            })
+        }
+        MenuNode::Separator => Box::new(PredefinedMenuItem::separator(app_handle)?),
+        MenuNode::Submenu { label: key, children } => {
+            let submenu = Submenu::new(app_handle, label(key), true)?;
+            for child in children.iter() {
+                submenu.append(build_node(app_handle, child)?.as_ref())?;
+            }
+            Box::new(submenu)
+        }
+        MenuNode::Tools => Box::new(build_tools_menu(app_handle)?),
+    };
+    Ok(item)
+}
+
+/// 工具菜单：收藏的工具、全部工具，以及每个分类下的工具
+fn build_tools_menu(app_handle: &AppHandle) -> tauri::Result<Submenu<tauri::Wry>> {
+    let tools_menu = Submenu::new(app_handle, label("tools"), true)?;
+
+    let favorites_menu = Submenu::new(app_handle, label("favorites"), true)?;
+    let favorites = crate::tray::favorite_tools(app_handle);
+    if favorites.is_empty() {
+        favorites_menu.append(&MenuItem::new(app_handle, label("no_favorites"), false, None::<&str>)?)?;
+    }
+    for (id, name) in favorites {
+        favorites_menu.append(&MenuItem::with_id(app_handle, format!("{}{}", crate::tray::OPEN_TOOL_PREFIX, id), name, true, None::<&str>)?)?;
+    }
+    tools_menu.append(&favorites_menu)?;
+    tools_menu.append(&MenuItem::with_id(
+        app_handle,
+        format!("{}all", OPEN_PAGE_PREFIX),
+        label("all_tools"),
+        true,
+        Some("CmdOrCtrl+Shift+T"),
+    )?)?;
+    tools_menu.append(&PredefinedMenuItem::separator(app_handle)?)?;
+
+    let table = crate::routes::table();
+    for category in &table.categories {
+        let category_menu = Submenu::new(app_handle, &category.name, true)?;
+        category_menu.append(&MenuItem::with_id(
+            app_handle,
+            format!("{}{}", OPEN_CATEGORY_PREFIX, category.id),
+            label("category_page"),
+            true,
+            None::<&str>,
+        )?)?;
+        category_menu.append(&PredefinedMenuItem::separator(app_handle)?)?;
+        for tool in table.tools.iter().filter(|tool| tool.category == category.id) {
+            category_menu.append(&MenuItem::with_id(
+                app_handle,
+                format!("{}{}", crate::tray::OPEN_TOOL_PREFIX, tool.id),
+                &tool.name,
+                true,
+                None::<&str>,
+            )?)?;
+        }
+        tools_menu.append(&category_menu)?;
+    }
+
+    Ok(tools_menu)
+}
+
+/// 构建完整的应用菜单
+pub fn build(app_handle: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app_handle)?;
+    for node in MENU {
+        menu.append(build_node(app_handle, node)?.as_ref())?;
+    }
+    Ok(menu)
+}
+
+/// 创建并设置应用菜单
+pub fn create(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    log::info!("🔧 创建系统菜单...");
+    app_handle.set_menu(build(app_handle)?)?;
+    Ok(())
+}
+
+/// 重建应用菜单（收藏或语言变化后调用）
+pub fn refresh(app_handle: &AppHandle) {
+    if let Err(e) = create(app_handle) {
+        log::warn!("⚠️ 更新系统菜单失败: {}", e);
+    }
+}
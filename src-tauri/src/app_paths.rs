@@ -0,0 +1,87 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+
+/// 可执行文件旁的便携模式标记文件
+const PORTABLE_MARKER: &str = "utilx.portable";
+
+/// 开启便携模式的环境变量（值为 1/true，或者数据目录路径）
+const PORTABLE_ENV: &str = "UTILX_PORTABLE";
+
+/// 便携模式下默认的数据目录名（位于可执行文件旁）
+const PORTABLE_DATA_DIR: &str = "utilx-data";
+
+static PORTABLE_ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// 检测便携模式，返回便携数据根目录
+fn detect_portable_root() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+
+    if let Ok(value) = std::env::var(PORTABLE_ENV) {
+        match value.trim() {
+            "" | "0" | "false" => {}
+            "1" | "true" => return Some(exe_dir.join(PORTABLE_DATA_DIR)),
+            path => {
+                let path = PathBuf::from(path);
+                return Some(if path.is_absolute() { path } else { exe_dir.join(path) });
+            }
+        }
+    }
+
+    exe_dir.join(PORTABLE_MARKER).exists().then(|| exe_dir.join(PORTABLE_DATA_DIR))
+}
+
+/// 便携模式数据根目录，非便携模式返回 None
+pub fn portable_root() -> Option<&'static PathBuf> {
+    PORTABLE_ROOT.get_or_init(detect_portable_root).as_ref()
+}
+
+/// 是否运行在便携模式
+pub fn is_portable() -> bool {
+    portable_root().is_some()
+}
+
+/// 配置目录（便携模式下位于可执行文件旁）
+pub fn config_dir(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    match portable_root() {
+        Some(root) => Ok(root.join("config")),
+        None => Ok(app_handle.path().app_config_dir()?),
+    }
+}
+
+/// 数据目录（调试信息等）
+pub fn data_dir(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    match portable_root() {
+        Some(root) => Ok(root.join("data")),
+        None => Ok(app_handle.path().app_data_dir()?),
+    }
+}
+
+/// 缓存目录
+pub fn cache_dir(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(config_dir(app_handle)?.join("cache"))
+}
+
+/// 日志目录
+pub fn log_dir(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(config_dir(app_handle)?.join("logs"))
+}
+
+/// 更新缓存目录
+pub fn updates_dir(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(config_dir(app_handle)?.join("updates"))
+}
+
+/// 配置备份目录
+pub fn backups_dir(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(config_dir(app_handle)?.join("backups"))
+}
+
+/// 窗口状态文件名；便携模式下返回绝对路径，让窗口状态插件也写到便携目录
+pub fn window_state_filename() -> String {
+    let file_name = tauri_plugin_window_state::DEFAULT_FILENAME;
+    match portable_root() {
+        Some(root) => root.join("config").join(file_name).to_string_lossy().to_string(),
+        None => file_name.to_string(),
+    }
+}
@@ -0,0 +1,331 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+/// 开机自启动时附加的命令行参数
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// 本次启动是否由开机自启动触发
+pub fn launched_at_login() -> bool {
+    std::env::args().skip(1).any(|arg| arg == AUTOSTART_ARG)
+}
+
+/// 自启动状态
+#[derive(Debug, Clone, Serialize)]
+pub struct AutostartStatus {
+    /// 用户设置中的 auto_start
+    pub enabled_in_settings: bool,
+    /// 系统中是否实际存在自启动项
+    pub registered: bool,
+    /// 自启动项位置（文件路径或注册表键）
+    pub location: Option<String>,
+}
+
+/// 用于启动应用的可执行文件路径（AppImage 运行时使用 AppImage 本身的路径）
+fn launch_executable() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(PathBuf::from(appimage));
+    }
+    Ok(std::env::current_exe()?)
+}
+
+/// 读取当前生效的 auto_start 设置
+fn auto_start_setting(app_handle: &AppHandle) -> bool {
+    let profile = crate::config_profiles::active_profile(app_handle);
+    crate::config_profiles::read_effective(app_handle, profile.as_deref(), "user-settings.json")
+        .ok()
+        .flatten()
+        .and_then(|settings| settings.get("auto_start").and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
+/// 使系统自启动项与 auto_start 设置保持一致；应用移动位置后会重写指向旧路径的自启动项
+pub fn reconcile(app_handle: &AppHandle) -> Result<AutostartStatus, Box<dyn std::error::Error>> {
+    let enabled = auto_start_setting(app_handle);
+    let registered = platform::is_registered(app_handle)?;
+
+    if enabled {
+        let executable = launch_executable()?;
+        if !platform::is_current(app_handle, &executable)? {
+            platform::register(app_handle, &executable)?;
+            if registered {
+                log::info!("🔄 已更新开机自启动项: {}", executable.display());
+            } else {
+                log::info!("✅ 已注册开机自启动");
+            }
+        }
+    } else if registered {
+        platform::unregister(app_handle)?;
+        log::info!("🗑️ 已移除开机自启动");
+    }
+
+    status(app_handle)
+}
+
+/// 获取自启动状态
+pub fn status(app_handle: &AppHandle) -> Result<AutostartStatus, Box<dyn std::error::Error>> {
+    Ok(AutostartStatus {
+        enabled_in_settings: auto_start_setting(app_handle),
+        registered: platform::is_registered(app_handle)?,
+        location: platform::location(app_handle).ok(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::*;
+    use std::fs;
+
+    /// XDG 自启动目录中的 .desktop 文件
+    fn desktop_entry_path(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .map(Ok)
+            .unwrap_or_else(|| app_handle.path().config_dir())?;
+        Ok(config_home.join("autostart").join(format!("{}.desktop", app_handle.config().identifier)))
+    }
+
+    pub fn location(app_handle: &AppHandle) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(desktop_entry_path(app_handle)?.to_string_lossy().to_string())
+    }
+
+    pub fn is_registered(app_handle: &AppHandle) -> Result<bool, Box<dyn std::error::Error>> {
+        Ok(desktop_entry_path(app_handle)?.exists())
+    }
+
+    /// 把参数加引号写入 Exec 字段
+    ///
+    /// 按 Desktop Entry 规范，引号内的 `"`、`` ` ``、`$`、`\` 需要反斜杠转义；Exec 作为字符串值还要再转义一次反斜杠，
+    /// `%` 写成 `%%` 以免被当作字段代码
+    pub(super) fn quote_exec_arg(arg: &str) -> String {
+        let mut quoted = String::with_capacity(arg.len() + 2);
+        for c in arg.chars() {
+            if matches!(c, '"' | '`' | '$' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(c);
+        }
+        format!("\"{}\"", quoted.replace('\\', "\\\\").replace('%', "%%"))
+    }
+
+    fn desktop_entry(app_handle: &AppHandle, executable: &std::path::Path) -> String {
+        let name = app_handle.config().product_name.clone().unwrap_or_else(|| "utilx".to_string());
+        format!(
+            "[Desktop Entry]\nType=Application\nName={}\nExec={} {}\nTerminal=false\nX-GNOME-Autostart-enabled=true\n",
+            name,
+            quote_exec_arg(&executable.to_string_lossy()),
+            AUTOSTART_ARG
+        )
+    }
+
+    /// 自启动项是否存在且指向当前的可执行文件
+    pub fn is_current(app_handle: &AppHandle, executable: &std::path::Path) -> Result<bool, Box<dyn std::error::Error>> {
+        let existing = fs::read_to_string(desktop_entry_path(app_handle)?).ok();
+        Ok(existing.as_deref() == Some(desktop_entry(app_handle, executable).as_str()))
+    }
+
+    pub fn register(app_handle: &AppHandle, executable: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+        let path = desktop_entry_path(app_handle)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, desktop_entry(app_handle, executable))?;
+        Ok(())
+    }
+
+    pub fn unregister(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        let path = desktop_entry_path(app_handle)?;
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::*;
+    use std::fs;
+
+    /// ~/Library/LaunchAgents 中的 plist
+    fn launch_agent_path(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(app_handle.path().home_dir()?
+            .join("Library/LaunchAgents")
+            .join(format!("{}.plist", app_handle.config().identifier)))
+    }
+
+    pub fn location(app_handle: &AppHandle) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(launch_agent_path(app_handle)?.to_string_lossy().to_string())
+    }
+
+    pub fn is_registered(app_handle: &AppHandle) -> Result<bool, Box<dyn std::error::Error>> {
+        Ok(launch_agent_path(app_handle)?.exists())
+    }
+
+    fn launch_agent(app_handle: &AppHandle, executable: &std::path::Path) -> String {
+        let escape = |s: &str| s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{}</string>
+        <string>{}</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+</dict>
+</plist>
+"#,
+            escape(&app_handle.config().identifier),
+            escape(&executable.to_string_lossy()),
+            AUTOSTART_ARG
+        )
+    }
+
+    /// 自启动项是否存在且指向当前的可执行文件
+    pub fn is_current(app_handle: &AppHandle, executable: &std::path::Path) -> Result<bool, Box<dyn std::error::Error>> {
+        let existing = fs::read_to_string(launch_agent_path(app_handle)?).ok();
+        Ok(existing.as_deref() == Some(launch_agent(app_handle, executable).as_str()))
+    }
+
+    pub fn register(app_handle: &AppHandle, executable: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+        let path = launch_agent_path(app_handle)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, launch_agent(app_handle, executable))?;
+        Ok(())
+    }
+
+    pub fn unregister(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        let path = launch_agent_path(app_handle)?;
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::*;
+    use std::os::windows::process::CommandExt;
+    use std::process::Command;
+
+    const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+
+    /// 不为子进程创建控制台窗口，避免启动和切换设置时闪过命令行窗口
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    fn reg() -> Command {
+        let mut command = Command::new("reg");
+        command.creation_flags(CREATE_NO_WINDOW);
+        command
+    }
+
+    fn value_name(app_handle: &AppHandle) -> String {
+        app_handle.config().identifier.clone()
+    }
+
+    pub fn location(app_handle: &AppHandle) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(format!(r"{}\{}", RUN_KEY, value_name(app_handle)))
+    }
+
+    pub fn is_registered(app_handle: &AppHandle) -> Result<bool, Box<dyn std::error::Error>> {
+        let status = reg()
+            .args(["query", RUN_KEY, "/v", &value_name(app_handle)])
+            .output()?
+            .status;
+        Ok(status.success())
+    }
+
+    fn run_command(executable: &std::path::Path) -> String {
+        format!("\"{}\" {}", executable.display(), AUTOSTART_ARG)
+    }
+
+    /// 自启动项是否存在且指向当前的可执行文件
+    pub fn is_current(app_handle: &AppHandle, executable: &std::path::Path) -> Result<bool, Box<dyn std::error::Error>> {
+        let output = reg()
+            .args(["query", RUN_KEY, "/v", &value_name(app_handle)])
+            .output()?;
+        if !output.status.success() {
+            return Ok(false);
+        }
+        // 输出形如 `    <名称>    REG_SZ    <命令>`
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let existing = stdout
+            .lines()
+            .find_map(|line| line.split_once("REG_SZ").map(|(_, command)| command.trim().to_string()));
+        Ok(existing.as_deref() == Some(run_command(executable).as_str()))
+    }
+
+    pub fn register(app_handle: &AppHandle, executable: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+        let command = run_command(executable);
+        let status = reg()
+            .args(["add", RUN_KEY, "/v", &value_name(app_handle), "/t", "REG_SZ", "/d", &command, "/f"])
+            .output()?
+            .status;
+        if !status.success() {
+            return Err(crate::i18n::t("autostart.registry_write_failed").into());
+        }
+        Ok(())
+    }
+
+    pub fn unregister(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        let status = reg()
+            .args(["delete", RUN_KEY, "/v", &value_name(app_handle), "/f"])
+            .output()?
+            .status;
+        if !status.success() {
+            return Err(crate::i18n::t("autostart.registry_delete_failed").into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod platform {
+    use super::*;
+
+    pub fn location(_app_handle: &AppHandle) -> Result<String, Box<dyn std::error::Error>> {
+        Err(crate::i18n::t("autostart.unsupported").into())
+    }
+
+    pub fn is_registered(_app_handle: &AppHandle) -> Result<bool, Box<dyn std::error::Error>> {
+        Ok(false)
+    }
+
+    pub fn is_current(_app_handle: &AppHandle, _executable: &std::path::Path) -> Result<bool, Box<dyn std::error::Error>> {
+        Ok(false)
+    }
+
+    pub fn register(_app_handle: &AppHandle, _executable: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+        Err(crate::i18n::t("autostart.unsupported").into())
+    }
+
+    pub fn unregister(_app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn quote_exec_arg_escapes_reserved_characters() {
+        use super::platform::quote_exec_arg;
+
+        assert_eq!(quote_exec_arg("/opt/utilx/utilx"), r#""/opt/utilx/utilx""#);
+        assert_eq!(quote_exec_arg("/opt/my app/utilx"), r#""/opt/my app/utilx""#);
+        assert_eq!(quote_exec_arg(r#"/opt/a"b/utilx"#), r#""/opt/a\\"b/utilx""#);
+        assert_eq!(quote_exec_arg("/opt/$HOME/`x`"), r#""/opt/\\$HOME/\\`x\\`""#);
+        assert_eq!(quote_exec_arg(r"/opt/a\b"), r#""/opt/a\\\\b""#);
+        assert_eq!(quote_exec_arg("/opt/100%/utilx"), r#""/opt/100%%/utilx""#);
+    }
+}
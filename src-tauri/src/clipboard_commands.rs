@@ -0,0 +1,103 @@
+use crate::clipboard_history::{ClipboardEntry, ClipboardHistoryConfig, ClipboardHistoryState, EntryKind};
+use crate::i18n;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use tauri::{AppHandle, State, command};
+
+/// 单次返回的默认记录数
+const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// 分页获取剪贴板历史（置顶的在前）
+#[command]
+pub async fn get_clipboard_history(
+    state: State<'_, ClipboardHistoryState>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<Vec<ClipboardEntry>, String> {
+    Ok(state.store().list(offset.unwrap_or(0), limit.unwrap_or(DEFAULT_PAGE_SIZE)))
+}
+
+/// 搜索剪贴板历史
+#[command]
+pub async fn search_clipboard_history(
+    state: State<'_, ClipboardHistoryState>,
+    query: String,
+    kind: Option<EntryKind>,
+    limit: Option<usize>,
+) -> Result<Vec<ClipboardEntry>, String> {
+    Ok(state.store().search(&query, kind, limit.unwrap_or(DEFAULT_PAGE_SIZE)))
+}
+
+/// 把历史记录重新复制到剪贴板
+#[command]
+pub async fn copy_clipboard_entry(state: State<'_, ClipboardHistoryState>, id: String) -> Result<ClipboardEntry, String> {
+    state.copy_entry(&id)
+        .map_err(|e| i18n::tf("clipboard.copy_failed", &[("error", &e)]))
+}
+
+/// 置顶或取消置顶
+#[command]
+pub async fn pin_clipboard_entry(state: State<'_, ClipboardHistoryState>, id: String, pinned: bool) -> Result<(), String> {
+    let max_entries = state.config().max_entries;
+    state.store().set_pinned(&id, pinned, max_entries)
+        .map_err(|e| i18n::tf("clipboard.update_failed", &[("error", &e)]))
+}
+
+/// 删除一条记录
+#[command]
+pub async fn delete_clipboard_entry(state: State<'_, ClipboardHistoryState>, id: String) -> Result<(), String> {
+    state.store().delete(&id)
+        .map_err(|e| i18n::tf("clipboard.delete_failed", &[("error", &e)]))
+}
+
+/// 清空剪贴板历史（默认保留置顶的记录）
+#[command]
+pub async fn clear_clipboard_history(state: State<'_, ClipboardHistoryState>, keep_pinned: Option<bool>) -> Result<(), String> {
+    state.store().clear(keep_pinned.unwrap_or(true))
+        .map_err(|e| i18n::tf("clipboard.clear_failed", &[("error", &e)]))
+}
+
+/// 获取图片记录（PNG data URL）
+#[command]
+pub async fn get_clipboard_image(state: State<'_, ClipboardHistoryState>, id: String) -> Result<String, String> {
+    let png = state.store().image_png(&id)
+        .map_err(|e| i18n::tf("clipboard.image_failed", &[("error", &e)]))?;
+    Ok(format!("data:image/png;base64,{}", BASE64.encode(png)))
+}
+
+/// 获取剪贴板历史配置
+#[command]
+pub async fn get_clipboard_history_config(state: State<'_, ClipboardHistoryState>) -> Result<ClipboardHistoryConfig, String> {
+    Ok(state.config())
+}
+
+/// 保存剪贴板历史配置并立即生效
+#[command]
+pub async fn save_clipboard_history_config(
+    app_handle: AppHandle,
+    state: State<'_, ClipboardHistoryState>,
+    config: ClipboardHistoryConfig,
+) -> Result<String, String> {
+    config.save(&app_handle)
+        .map_err(|e| i18n::tf("clipboard.save_config_failed", &[("error", &e)]))?;
+    state.set_config(config);
+    state.sync_last_seen();
+    Ok(i18n::t("clipboard.config_saved"))
+}
+
+/// 暂停或恢复记录；恢复时不会补录暂停期间复制的内容
+#[command]
+pub async fn set_clipboard_history_paused(
+    app_handle: AppHandle,
+    state: State<'_, ClipboardHistoryState>,
+    paused: bool,
+) -> Result<(), String> {
+    let mut config = state.config();
+    config.paused = paused;
+    config.save(&app_handle)
+        .map_err(|e| i18n::tf("clipboard.save_config_failed", &[("error", &e)]))?;
+    state.set_config(config);
+    state.sync_last_seen();
+
+    log::info!("📋 剪贴板历史已{}", if paused { "暂停" } else { "恢复" });
+    Ok(())
+}
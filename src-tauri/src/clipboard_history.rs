@@ -0,0 +1,936 @@
+// 剪贴板历史：后台监听系统剪贴板，把文本和图片记录到配置目录下有上限、可搜索的历史中
+use crate::i18n;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// 剪贴板历史配置文件名（位于配置目录）
+const CONFIG_FILE: &str = "clipboard-history.json";
+
+/// 历史数据目录（位于配置目录）
+const HISTORY_DIR: &str = "clipboard-history";
+
+/// 历史索引文件名
+const HISTORY_FILE: &str = "history.json";
+
+/// 图片的最大尺寸（RGBA 字节数），超过的图片不记录
+const MAX_IMAGE_BYTES: usize = 32 * 1024 * 1024;
+
+/// 检查剪贴板图片的最短间隔：读取图片需要复制完整的像素数据，比文本慢得多
+const IMAGE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 判断图片是否变化时每行抽样的像素数
+const IMAGE_ROW_SAMPLES: usize = 64;
+
+/// 设置为 `mock` 时使用内存中的剪贴板，便于在没有图形环境时测试（仅调试构建）
+const BACKEND_ENV: &str = "UTILX_CLIPBOARD_BACKEND";
+
+/// 新增或更新记录时发送给前端的事件
+pub const CHANGED_EVENT: &str = "clipboard-history-changed";
+
+/// 剪贴板内容
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardContent {
+    Text(String),
+    Image { width: u32, height: u32, rgba: Vec<u8> },
+}
+
+impl ClipboardContent {
+    /// 内容指纹，用于去重和判断剪贴板是否变化
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        match self {
+            ClipboardContent::Text(text) => {
+                hasher.update(b"text:");
+                hasher.update(text.as_bytes());
+            }
+            ClipboardContent::Image { width, height, rgba } => {
+                hasher.update(format!("image:{}x{}:", width, height).as_bytes());
+                hasher.update(rgba);
+            }
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// 判断剪贴板是否变化的键：文本使用完整指纹，图片只使用尺寸和抽样像素，避免每次检查都哈希整张图片
+    ///
+    /// 图片每一行都抽样，且每行的起始列依次错开，相邻的行合起来覆盖所有列
+    pub fn change_key(&self) -> String {
+        let ClipboardContent::Image { width, height, rgba } = self else {
+            return self.fingerprint();
+        };
+        let row_bytes = (*width as usize * 4).max(4);
+        let step = (*width as usize / IMAGE_ROW_SAMPLES).max(1);
+        let mut hasher = Sha256::new();
+        for (index, row) in rgba.chunks(row_bytes).enumerate() {
+            for pixel in row.chunks_exact(4).skip(index % step).step_by(step) {
+                hasher.update(pixel);
+            }
+        }
+        format!("image:{}x{}:{:x}", width, height, hasher.finalize())
+    }
+}
+
+/// 图片的 RGBA 数据是否超过记录上限
+fn image_too_large(width: u32, height: u32) -> bool {
+    width as usize * height as usize * 4 > MAX_IMAGE_BYTES
+}
+
+/// 剪贴板读写接口，监听逻辑不依赖具体的系统剪贴板
+pub trait ClipboardBackend: Send {
+    /// 读取当前内容；剪贴板为空或格式不支持时返回 None
+    fn read(&mut self, include_images: bool) -> Result<Option<ClipboardContent>, Box<dyn std::error::Error>>;
+    /// 写入内容
+    fn write(&mut self, content: &ClipboardContent) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+/// 通过剪贴板插件访问系统剪贴板（在无头 X 服务器上同样可用）
+pub struct SystemClipboard {
+    app_handle: AppHandle,
+}
+
+impl ClipboardBackend for SystemClipboard {
+    fn read(&mut self, include_images: bool) -> Result<Option<ClipboardContent>, Box<dyn std::error::Error>> {
+        let clipboard = self.app_handle.clipboard();
+        if let Ok(text) = clipboard.read_text() {
+            if !text.is_empty() {
+                return Ok(Some(ClipboardContent::Text(text)));
+            }
+        }
+
+        if include_images {
+            if let Ok(image) = clipboard.read_image() {
+                // 过大的图片不会被记录，不再复制像素数据
+                if image_too_large(image.width(), image.height()) {
+                    return Ok(None);
+                }
+                return Ok(Some(ClipboardContent::Image {
+                    width: image.width(),
+                    height: image.height(),
+                    rgba: image.rgba().to_vec(),
+                }));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn write(&mut self, content: &ClipboardContent) -> Result<(), Box<dyn std::error::Error>> {
+        let clipboard = self.app_handle.clipboard();
+        match content {
+            ClipboardContent::Text(text) => clipboard.write_text(text.as_str())?,
+            ClipboardContent::Image { width, height, rgba } => {
+                clipboard.write_image(&tauri::image::Image::new(rgba, *width, *height))?
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 内存中的剪贴板，克隆后共享同一份内容
+#[derive(Clone, Default)]
+pub struct MockClipboard {
+    content: Arc<Mutex<Option<ClipboardContent>>>,
+}
+
+impl MockClipboard {
+    /// 模拟其他程序复制了内容
+    pub fn set(&self, content: ClipboardContent) {
+        *self.content.lock().unwrap() = Some(content);
+    }
+}
+
+impl ClipboardBackend for MockClipboard {
+    fn read(&mut self, include_images: bool) -> Result<Option<ClipboardContent>, Box<dyn std::error::Error>> {
+        let content = self.content.lock().unwrap().clone();
+        Ok(content.filter(|c| include_images || matches!(c, ClipboardContent::Text(_))))
+    }
+
+    fn write(&mut self, content: &ClipboardContent) -> Result<(), Box<dyn std::error::Error>> {
+        self.set(content.clone());
+        Ok(())
+    }
+}
+
+/// 剪贴板历史配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClipboardHistoryConfig {
+    /// 是否记录剪贴板历史
+    pub enabled: bool,
+    /// 暂停记录（例如输入敏感内容期间）
+    pub paused: bool,
+    /// 最多保留的记录数（不含置顶）
+    pub max_entries: usize,
+    /// 单条文本的最大长度（字节）
+    pub max_text_bytes: usize,
+    /// 是否记录图片
+    pub capture_images: bool,
+    /// 检查剪贴板的间隔（毫秒）
+    pub poll_interval_ms: u64,
+    /// 跳过看起来像密码的文本
+    pub skip_passwords: bool,
+    /// 跳过与保险库中密钥相同的文本（锁定时使用解锁时记下的哈希判断，本次运行还未解锁过时不记录文本）
+    pub skip_vault_secrets: bool,
+    /// 匹配任一正则表达式的文本不记录
+    pub exclude_patterns: Vec<String>,
+}
+
+impl Default for ClipboardHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            paused: false,
+            max_entries: 200,
+            max_text_bytes: 256 * 1024,
+            capture_images: true,
+            poll_interval_ms: 500,
+            skip_passwords: true,
+            skip_vault_secrets: true,
+            exclude_patterns: Vec::new(),
+        }
+    }
+}
+
+impl ClipboardHistoryConfig {
+    /// 从配置目录加载
+    pub fn load(app_handle: &AppHandle) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_path = crate::app_paths::config_dir(app_handle)?.join(CONFIG_FILE);
+        if config_path.exists() {
+            let content = fs::read_to_string(&config_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// 保存到配置目录
+    pub fn save(&self, app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        self.validate()?;
+        let config_dir = crate::app_paths::config_dir(app_handle)?;
+        fs::create_dir_all(&config_dir)?;
+        fs::write(config_dir.join(CONFIG_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 检查数值范围和排除规则
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.max_entries == 0 || self.max_entries > 10_000 {
+            return Err(i18n::t("clipboard.invalid_max_entries").into());
+        }
+        if self.poll_interval_ms < 100 {
+            return Err(i18n::t("clipboard.invalid_poll_interval").into());
+        }
+        ExclusionRules::from_config(self)?;
+        Ok(())
+    }
+}
+
+/// 排除规则
+pub struct ExclusionRules {
+    skip_passwords: bool,
+    patterns: Vec<regex::Regex>,
+}
+
+impl ExclusionRules {
+    pub fn from_config(config: &ClipboardHistoryConfig) -> Result<Self, Box<dyn std::error::Error>> {
+        let patterns = config
+            .exclude_patterns
+            .iter()
+            .map(|p| regex::Regex::new(p).map_err(|e| i18n::tf("clipboard.invalid_pattern", &[("pattern", &p), ("error", &e)])))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { skip_passwords: config.skip_passwords, patterns })
+    }
+
+    /// 返回不记录的原因
+    pub fn exclusion_reason(&self, text: &str) -> Option<&'static str> {
+        if self.skip_passwords && looks_like_password(text) {
+            return Some("疑似密码");
+        }
+        if self.patterns.iter().any(|p| p.is_match(text)) {
+            return Some("匹配排除规则");
+        }
+        None
+    }
+}
+
+/// 单行、无空白、长度适中且混合了至少三类字符的文本视为密码
+pub fn looks_like_password(text: &str) -> bool {
+    let len = text.chars().count();
+    if !(8..=64).contains(&len) || text.chars().any(char::is_whitespace) {
+        return false;
+    }
+    // 链接、路径和邮箱地址即使字符混杂也不是密码
+    if text.contains("://") || text.starts_with('/') || looks_like_email(text) {
+        return false;
+    }
+
+    let classes = [
+        text.chars().any(|c| c.is_ascii_lowercase()),
+        text.chars().any(|c| c.is_ascii_uppercase()),
+        text.chars().any(|c| c.is_ascii_digit()),
+        text.chars().any(|c| !c.is_alphanumeric()),
+    ];
+    classes.iter().filter(|&&present| present).count() >= 3
+}
+
+fn looks_like_email(text: &str) -> bool {
+    let Some((local, domain)) = text.split_once('@') else {
+        return false;
+    };
+    let top_level = domain.rsplit('.').next().unwrap_or_default();
+    !local.is_empty()
+        && local.chars().all(|c| c.is_alphanumeric() || matches!(c, '.' | '_' | '+' | '-'))
+        && domain.contains('.')
+        && domain.chars().all(|c| c.is_alphanumeric() || matches!(c, '.' | '-'))
+        && top_level.len() >= 2
+        && top_level.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// 记录类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    Text,
+    Image,
+}
+
+/// 图片信息（图片以 PNG 格式保存在历史目录中）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageInfo {
+    pub file: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// 一条剪贴板记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardEntry {
+    pub id: String,
+    pub kind: EntryKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<ImageInfo>,
+    pub hash: String,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub copy_count: u32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub last_used_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryFile {
+    entries: Vec<ClipboardEntry>,
+}
+
+/// 历史存储，记录按最近使用时间从新到旧排列
+pub struct HistoryStore {
+    dir: PathBuf,
+    entries: Vec<ClipboardEntry>,
+}
+
+impl HistoryStore {
+    /// 从历史目录加载
+    pub fn load(dir: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let history_path = dir.join(HISTORY_FILE);
+        let file: HistoryFile = if history_path.exists() {
+            serde_json::from_str(&fs::read_to_string(&history_path)?)?
+        } else {
+            HistoryFile::default()
+        };
+        Ok(Self { dir: dir.to_path_buf(), entries: file.entries })
+    }
+
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        fs::create_dir_all(&self.dir)?;
+        let file = HistoryFile { entries: self.entries.clone() };
+        fs::write(self.dir.join(HISTORY_FILE), serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    fn image_path(&self, file: &str) -> PathBuf {
+        self.dir.join("images").join(file)
+    }
+
+    /// 记录新内容；已有相同内容时移到最前。返回新增或更新的记录
+    pub fn record(&mut self, content: &ClipboardContent, max_entries: usize) -> Result<ClipboardEntry, Box<dyn std::error::Error>> {
+        let hash = content.fingerprint();
+        let now = chrono::Utc::now();
+
+        let entry = match self.entries.iter().position(|e| e.hash == hash) {
+            Some(index) => {
+                let mut entry = self.entries.remove(index);
+                entry.last_used_at = now;
+                entry
+            }
+            None => {
+                let id = new_id();
+                let (kind, text, image) = match content {
+                    ClipboardContent::Text(text) => (EntryKind::Text, Some(text.clone()), None),
+                    ClipboardContent::Image { width, height, rgba } => {
+                        let file = format!("{}.png", id);
+                        write_png(&self.image_path(&file), *width, *height, rgba)?;
+                        (EntryKind::Image, None, Some(ImageInfo { file, width: *width, height: *height }))
+                    }
+                };
+                ClipboardEntry { id, kind, text, image, hash, pinned: false, copy_count: 0, created_at: now, last_used_at: now }
+            }
+        };
+
+        self.entries.insert(0, entry.clone());
+        self.evict(max_entries);
+        self.save()?;
+        Ok(entry)
+    }
+
+    /// 超出上限时删除最旧的未置顶记录
+    fn evict(&mut self, max_entries: usize) {
+        while self.entries.iter().filter(|e| !e.pinned).count() > max_entries {
+            let Some(index) = self.entries.iter().rposition(|e| !e.pinned) else {
+                break;
+            };
+            let removed = self.entries.remove(index);
+            self.remove_image(&removed);
+        }
+    }
+
+    fn remove_image(&self, entry: &ClipboardEntry) {
+        if let Some(image) = &entry.image {
+            let _ = fs::remove_file(self.image_path(&image.file));
+        }
+    }
+
+    /// 分页列出记录，置顶的在前
+    pub fn list(&self, offset: usize, limit: usize) -> Vec<ClipboardEntry> {
+        self.sorted().skip(offset).take(limit).cloned().collect()
+    }
+
+    /// 按文本搜索（不区分大小写），可按类型过滤
+    pub fn search(&self, query: &str, kind: Option<EntryKind>, limit: usize) -> Vec<ClipboardEntry> {
+        let query = query.to_lowercase();
+        self.sorted()
+            .filter(|e| kind.is_none_or(|kind| e.kind == kind))
+            .filter(|e| query.is_empty() || e.text.as_deref().is_some_and(|t| t.to_lowercase().contains(&query)))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    fn sorted(&self) -> impl Iterator<Item = &ClipboardEntry> {
+        self.entries.iter().filter(|e| e.pinned).chain(self.entries.iter().filter(|e| !e.pinned))
+    }
+
+    fn find(&self, id: &str) -> Result<usize, Box<dyn std::error::Error>> {
+        self.entries
+            .iter()
+            .position(|e| e.id == id)
+            .ok_or_else(|| i18n::tf("clipboard.entry_not_found", &[("id", &id)]).into())
+    }
+
+    /// 读取记录的完整内容
+    pub fn content(&self, id: &str) -> Result<ClipboardContent, Box<dyn std::error::Error>> {
+        let entry = &self.entries[self.find(id)?];
+        match (&entry.text, &entry.image) {
+            (Some(text), _) => Ok(ClipboardContent::Text(text.clone())),
+            (None, Some(image)) => {
+                let (width, height, rgba) = read_png(&self.image_path(&image.file))?;
+                Ok(ClipboardContent::Image { width, height, rgba })
+            }
+            (None, None) => Err(i18n::t("clipboard.entry_empty").into()),
+        }
+    }
+
+    /// 读取图片记录的 PNG 文件内容
+    pub fn image_png(&self, id: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let entry = &self.entries[self.find(id)?];
+        let image = entry.image.as_ref().ok_or_else(|| i18n::t("clipboard.not_image"))?;
+        Ok(fs::read(self.image_path(&image.file))?)
+    }
+
+    /// 记录被重新复制：移到最前并增加次数
+    pub fn mark_copied(&mut self, id: &str) -> Result<ClipboardEntry, Box<dyn std::error::Error>> {
+        let mut entry = self.entries.remove(self.find(id)?);
+        entry.copy_count += 1;
+        entry.last_used_at = chrono::Utc::now();
+        self.entries.insert(0, entry.clone());
+        self.save()?;
+        Ok(entry)
+    }
+
+    /// 置顶或取消置顶（置顶的记录不会因超出上限被删除）
+    pub fn set_pinned(&mut self, id: &str, pinned: bool, max_entries: usize) -> Result<(), Box<dyn std::error::Error>> {
+        let index = self.find(id)?;
+        self.entries[index].pinned = pinned;
+        self.evict(max_entries);
+        self.save()
+    }
+
+    /// 删除一条记录
+    pub fn delete(&mut self, id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let removed = self.entries.remove(self.find(id)?);
+        self.remove_image(&removed);
+        self.save()
+    }
+
+    /// 清空历史，可保留置顶的记录
+    pub fn clear(&mut self, keep_pinned: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let (kept, removed): (Vec<_>, Vec<_>) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition(|e| keep_pinned && e.pinned);
+        for entry in &removed {
+            self.remove_image(entry);
+        }
+        self.entries = kept;
+        self.save()
+    }
+}
+
+fn new_id() -> String {
+    let mut bytes = [0u8; 8];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn write_png(path: &Path, width: u32, height: u32, rgba: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(rgba)?;
+    Ok(())
+}
+
+fn read_png(path: &Path) -> Result<(u32, u32, Vec<u8>), Box<dyn std::error::Error>> {
+    let mut decoder = png::Decoder::new(File::open(path)?);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::ALPHA);
+    let mut reader = decoder.read_info()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer)?;
+    buffer.truncate(info.buffer_size());
+    Ok((info.width, info.height, buffer))
+}
+
+/// 剪贴板历史状态（在 setup 中创建并由 Tauri 管理）
+pub struct ClipboardHistoryState {
+    config: Mutex<ClipboardHistoryConfig>,
+    store: Mutex<HistoryStore>,
+    backend: Mutex<Box<dyn ClipboardBackend>>,
+    /// 最近一次看到的剪贴板内容的变化键（见 `ClipboardContent::change_key`）
+    last_seen: Mutex<Option<String>>,
+    /// 最近一次检查图片的时间
+    last_image_poll: Mutex<Option<Instant>>,
+}
+
+impl ClipboardHistoryState {
+    pub fn new(config: ClipboardHistoryConfig, store: HistoryStore, backend: Box<dyn ClipboardBackend>) -> Self {
+        Self {
+            config: Mutex::new(config),
+            store: Mutex::new(store),
+            backend: Mutex::new(backend),
+            last_seen: Mutex::new(None),
+            last_image_poll: Mutex::new(None),
+        }
+    }
+
+    pub fn config(&self) -> ClipboardHistoryConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    pub fn set_config(&self, config: ClipboardHistoryConfig) {
+        *self.config.lock().unwrap() = config;
+    }
+
+    pub fn store(&self) -> std::sync::MutexGuard<'_, HistoryStore> {
+        self.store.lock().unwrap()
+    }
+
+    /// 记下当前剪贴板内容但不记录（启动或恢复记录时调用，避免收录之前复制的内容）
+    pub fn sync_last_seen(&self) {
+        let include_images = self.config().capture_images;
+        let current = self.backend.lock().unwrap().read(include_images).ok().flatten();
+        *self.last_seen.lock().unwrap() = current.map(|c| c.change_key());
+    }
+
+    /// 检查剪贴板一次，有新内容且未被排除时写入历史
+    pub fn capture(&self, is_secret: impl Fn(&str) -> bool) -> Result<Option<ClipboardEntry>, Box<dyn std::error::Error>> {
+        let config = self.config();
+        if !config.enabled || config.paused {
+            return Ok(None);
+        }
+
+        let include_images = config.capture_images && self.image_poll_due();
+        let Some(content) = self.backend.lock().unwrap().read(include_images)? else {
+            return Ok(None);
+        };
+        let change_key = content.change_key();
+        {
+            let mut last_seen = self.last_seen.lock().unwrap();
+            if last_seen.as_deref() == Some(change_key.as_str()) {
+                return Ok(None);
+            }
+            *last_seen = Some(change_key);
+        }
+
+        // 日志中不输出剪贴板内容
+        match &content {
+            ClipboardContent::Text(text) => {
+                if text.len() > config.max_text_bytes {
+                    log::debug!("🔍 剪贴板文本过长，跳过 ({} 字节)", text.len());
+                    return Ok(None);
+                }
+                if let Some(reason) = ExclusionRules::from_config(&config)?.exclusion_reason(text) {
+                    log::debug!("🔍 剪贴板内容未记录: {}", reason);
+                    return Ok(None);
+                }
+                if config.skip_vault_secrets && is_secret(text) {
+                    log::debug!("🔍 剪贴板内容未记录: 可能是保险库中的密钥");
+                    return Ok(None);
+                }
+            }
+            ClipboardContent::Image { width, height, .. } => {
+                if image_too_large(*width, *height) {
+                    log::debug!("🔍 剪贴板图片过大，跳过 ({}×{})", width, height);
+                    return Ok(None);
+                }
+            }
+        }
+
+        let entry = self.store().record(&content, config.max_entries)?;
+        Ok(Some(entry))
+    }
+
+    /// 距离上次检查图片是否已超过 `IMAGE_POLL_INTERVAL`，是则记下本次检查时间
+    fn image_poll_due(&self) -> bool {
+        let mut last_image_poll = self.last_image_poll.lock().unwrap();
+        let due = last_image_poll.is_none_or(|last| last.elapsed() >= IMAGE_POLL_INTERVAL);
+        if due {
+            *last_image_poll = Some(Instant::now());
+        }
+        due
+    }
+
+    /// 把历史记录重新复制到剪贴板
+    pub fn copy_entry(&self, id: &str) -> Result<ClipboardEntry, Box<dyn std::error::Error>> {
+        let mut store = self.store();
+        let content = store.content(id)?;
+        self.backend.lock().unwrap().write(&content)?;
+        // 自己写入的内容不再作为新记录
+        *self.last_seen.lock().unwrap() = Some(content.change_key());
+        store.mark_copied(id)
+    }
+}
+
+/// 历史数据目录
+pub fn history_dir(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(crate::app_paths::config_dir(app_handle)?.join(HISTORY_DIR))
+}
+
+/// 根据环境变量选择剪贴板实现
+fn create_backend(app_handle: &AppHandle) -> Box<dyn ClipboardBackend> {
+    match std::env::var(BACKEND_ENV).as_deref() {
+        Ok("mock") if cfg!(debug_assertions) => {
+            log::info!("📋 剪贴板历史使用内存剪贴板");
+            Box::new(MockClipboard::default())
+        }
+        _ => Box::new(SystemClipboard { app_handle: app_handle.clone() }),
+    }
+}
+
+/// 加载历史并开始监听剪贴板
+pub fn start(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let config = ClipboardHistoryConfig::load(app_handle).unwrap_or_else(|e| {
+        log::warn!("⚠️ 剪贴板历史配置无效，使用默认配置: {}", e);
+        ClipboardHistoryConfig::default()
+    });
+    let store = HistoryStore::load(&history_dir(app_handle)?)?;
+    let state = ClipboardHistoryState::new(config, store, create_backend(app_handle));
+    state.sync_last_seen();
+    app_handle.manage(state);
+
+    let app_handle = app_handle.clone();
+    std::thread::spawn(move || loop {
+        let state = app_handle.state::<ClipboardHistoryState>();
+        let interval = state.config().poll_interval_ms;
+
+        let is_secret = |text: &str| {
+            let vault = app_handle.state::<crate::secrets_vault::SecretsVaultState>();
+            crate::secrets_vault::contains_value(&app_handle, &vault, text)
+        };
+        match state.capture(is_secret) {
+            Ok(Some(entry)) => {
+                let _ = app_handle.emit(CHANGED_EVENT, &entry);
+            }
+            Ok(None) => {}
+            Err(e) => log::warn!("⚠️ 记录剪贴板历史失败: {}", e),
+        }
+
+        std::thread::sleep(Duration::from_millis(interval));
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试用的临时历史目录，结束时删除
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("utilx-clipboard-test-{}", new_id()));
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn enabled_config() -> ClipboardHistoryConfig {
+        ClipboardHistoryConfig { enabled: true, ..ClipboardHistoryConfig::default() }
+    }
+
+    fn new_state(dir: &TempDir, config: ClipboardHistoryConfig) -> (ClipboardHistoryState, MockClipboard) {
+        let clipboard = MockClipboard::default();
+        let store = HistoryStore::load(&dir.0).unwrap();
+        (ClipboardHistoryState::new(config, store, Box::new(clipboard.clone())), clipboard)
+    }
+
+    fn text(value: &str) -> ClipboardContent {
+        ClipboardContent::Text(value.to_string())
+    }
+
+    fn image(width: u32, height: u32, seed: u8) -> ClipboardContent {
+        let rgba = (0..width * height * 4).map(|i| (i as u8).wrapping_add(seed)).collect();
+        ClipboardContent::Image { width, height, rgba }
+    }
+
+    #[test]
+    fn capture_skips_unchanged_content() {
+        let dir = TempDir::new();
+        let (state, clipboard) = new_state(&dir, enabled_config());
+
+        clipboard.set(text("hello"));
+        assert!(state.capture(|_| false).unwrap().is_some());
+        assert!(state.capture(|_| false).unwrap().is_none());
+
+        clipboard.set(text("world"));
+        assert!(state.capture(|_| false).unwrap().is_some());
+        assert_eq!(state.store().list(0, 10).len(), 2);
+    }
+
+    #[test]
+    fn capture_ignores_content_present_at_start() {
+        let dir = TempDir::new();
+        let (state, clipboard) = new_state(&dir, enabled_config());
+
+        clipboard.set(text("copied before start"));
+        state.sync_last_seen();
+        assert!(state.capture(|_| false).unwrap().is_none());
+    }
+
+    #[test]
+    fn capture_respects_disabled_and_paused() {
+        let dir = TempDir::new();
+        let (state, clipboard) = new_state(&dir, ClipboardHistoryConfig::default());
+        clipboard.set(text("hello"));
+        assert!(state.capture(|_| false).unwrap().is_none());
+
+        state.set_config(ClipboardHistoryConfig { paused: true, ..enabled_config() });
+        assert!(state.capture(|_| false).unwrap().is_none());
+        assert!(state.store().list(0, 10).is_empty());
+    }
+
+    #[test]
+    fn capture_skips_excluded_and_secret_text() {
+        let dir = TempDir::new();
+        let config = ClipboardHistoryConfig { exclude_patterns: vec!["^token-".to_string()], ..enabled_config() };
+        let (state, clipboard) = new_state(&dir, config);
+
+        clipboard.set(text("Tr0ub4dor&3x"));
+        assert!(state.capture(|_| false).unwrap().is_none());
+        clipboard.set(text("token-abc"));
+        assert!(state.capture(|_| false).unwrap().is_none());
+        clipboard.set(text("vault value"));
+        assert!(state.capture(|t| t == "vault value").unwrap().is_none());
+        assert!(state.store().list(0, 10).is_empty());
+    }
+
+    #[test]
+    fn capture_skips_oversized_images() {
+        let dir = TempDir::new();
+        let (state, clipboard) = new_state(&dir, enabled_config());
+
+        clipboard.set(ClipboardContent::Image { width: 4096, height: 4096, rgba: Vec::new() });
+        assert!(state.capture(|_| false).unwrap().is_none());
+    }
+
+    #[test]
+    fn password_detection() {
+        assert!(looks_like_password("Tr0ub4dor&3x"));
+        assert!(looks_like_password("abcDEF123"));
+        assert!(!looks_like_password("short1A"));
+        assert!(!looks_like_password("alllowercaseletters"));
+        assert!(!looks_like_password("two words 1A"));
+        assert!(!looks_like_password("https://Example.com/a1"));
+        assert!(!looks_like_password("/usr/Local/bin1"));
+        assert!(!looks_like_password("John.Doe1@example.com"));
+    }
+
+    #[test]
+    fn exclusion_rules() {
+        let config = ClipboardHistoryConfig { exclude_patterns: vec![r"^\d{6}$".to_string()], ..enabled_config() };
+        let rules = ExclusionRules::from_config(&config).unwrap();
+        assert!(rules.exclusion_reason("123456").is_some());
+        assert!(rules.exclusion_reason("Tr0ub4dor&3x").is_some());
+        assert!(rules.exclusion_reason("plain text").is_none());
+
+        let rules = ExclusionRules::from_config(&ClipboardHistoryConfig { skip_passwords: false, ..config }).unwrap();
+        assert!(rules.exclusion_reason("Tr0ub4dor&3x").is_none());
+
+        let invalid = ClipboardHistoryConfig { exclude_patterns: vec!["(".to_string()], ..enabled_config() };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn evict_keeps_pinned_entries() {
+        let dir = TempDir::new();
+        let mut store = HistoryStore::load(&dir.0).unwrap();
+        let first = store.record(&text("first"), 2).unwrap();
+        store.set_pinned(&first.id, true, 2).unwrap();
+        for value in ["second", "third", "fourth"] {
+            store.record(&text(value), 2).unwrap();
+        }
+
+        let texts: Vec<_> = store.list(0, 10).into_iter().filter_map(|e| e.text).collect();
+        assert_eq!(texts, ["first", "fourth", "third"]);
+    }
+
+    #[test]
+    fn record_moves_duplicate_to_front() {
+        let dir = TempDir::new();
+        let mut store = HistoryStore::load(&dir.0).unwrap();
+        let first = store.record(&text("a"), 10).unwrap();
+        store.record(&text("b"), 10).unwrap();
+        let again = store.record(&text("a"), 10).unwrap();
+
+        assert_eq!(again.id, first.id);
+        let ids: Vec<_> = store.list(0, 10).into_iter().map(|e| e.id).collect();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(ids[0], first.id);
+    }
+
+    #[test]
+    fn pin_delete_and_clear() {
+        let dir = TempDir::new();
+        let mut store = HistoryStore::load(&dir.0).unwrap();
+        let a = store.record(&text("a"), 10).unwrap();
+        let b = store.record(&text("b"), 10).unwrap();
+        let c = store.record(&text("c"), 10).unwrap();
+
+        store.set_pinned(&a.id, true, 10).unwrap();
+        assert_eq!(store.list(0, 10)[0].id, a.id);
+
+        store.delete(&b.id).unwrap();
+        assert!(store.content(&b.id).is_err());
+        assert!(store.delete(&b.id).is_err());
+
+        // 修改已保存到磁盘
+        let reloaded = HistoryStore::load(&dir.0).unwrap();
+        let ids: Vec<_> = reloaded.list(0, 10).into_iter().map(|e| (e.id, e.pinned)).collect();
+        assert_eq!(ids, [(a.id.clone(), true), (c.id, false)]);
+
+        store.clear(true).unwrap();
+        let ids: Vec<_> = store.list(0, 10).into_iter().map(|e| e.id).collect();
+        assert_eq!(ids, [a.id]);
+
+        store.clear(false).unwrap();
+        assert!(store.list(0, 10).is_empty());
+    }
+
+    #[test]
+    fn image_round_trip() {
+        let dir = TempDir::new();
+        let mut store = HistoryStore::load(&dir.0).unwrap();
+        let original = image(3, 2, 7);
+        let entry = store.record(&original, 10).unwrap();
+
+        assert_eq!(entry.kind, EntryKind::Image);
+        assert_eq!(store.content(&entry.id).unwrap(), original);
+        assert!(store.image_png(&entry.id).unwrap().starts_with(b"\x89PNG"));
+
+        let image_path = store.image_path(&entry.image.unwrap().file);
+        assert!(image_path.exists());
+        store.delete(&entry.id).unwrap();
+        assert!(!image_path.exists());
+    }
+
+    #[test]
+    fn copy_entry_is_not_captured_again() {
+        let dir = TempDir::new();
+        let (state, clipboard) = new_state(&dir, enabled_config());
+
+        clipboard.set(image(2, 2, 1));
+        let entry = state.capture(|_| false).unwrap().unwrap();
+        clipboard.set(text("other"));
+        state.capture(|_| false).unwrap();
+
+        let copied = state.copy_entry(&entry.id).unwrap();
+        assert_eq!(copied.copy_count, 1);
+        assert_eq!(clipboard.content.lock().unwrap().clone(), Some(image(2, 2, 1)));
+        assert!(state.capture(|_| false).unwrap().is_none());
+    }
+
+    #[test]
+    fn change_key_distinguishes_images() {
+        assert_eq!(image(4, 4, 1).change_key(), image(4, 4, 1).change_key());
+        assert_ne!(image(4, 4, 1).change_key(), image(4, 4, 2).change_key());
+        assert_ne!(image(4, 4, 1).change_key(), image(2, 8, 1).change_key());
+    }
+
+    #[test]
+    fn change_key_notices_changes_in_any_row() {
+        let (width, height) = (1024u32, 1024u32);
+        let blank = ClipboardContent::Image { width, height, rgba: vec![0; (width * height * 4) as usize] };
+        let step = width as usize / IMAGE_ROW_SAMPLES;
+
+        // 每一行都参与抽样：整行变化（例如截图中多出一行文字）一定会被发现
+        for row in [0usize, 1, 511, 1023] {
+            let ClipboardContent::Image { rgba, .. } = &blank else { unreachable!() };
+            let mut rgba = rgba.clone();
+            let start = row * width as usize * 4;
+            rgba[start..start + width as usize * 4].fill(255);
+            let changed = ClipboardContent::Image { width, height, rgba };
+            assert_ne!(blank.change_key(), changed.change_key(), "row {}", row);
+        }
+
+        // 每一列都在某一行中被抽样
+        for column in 0..step {
+            let mut rgba = vec![0; (width * height * 4) as usize];
+            for row in 0..height as usize {
+                let offset = (row * width as usize + column) * 4;
+                rgba[offset] = 255;
+            }
+            let changed = ClipboardContent::Image { width, height, rgba };
+            assert_ne!(blank.change_key(), changed.change_key(), "column {}", column);
+        }
+    }
+}
@@ -1,20 +1,39 @@
-use tauri::{AppHandle, command, Manager};
+use crate::i18n;
+use tauri::{AppHandle, command};
 
 /// 导入配置
+///
+/// `strategy` 为 `replace`（默认）或 `merge`
 #[command]
-pub async fn import_config(app_handle: AppHandle, import_path: String) -> Result<String, String> {
-    match crate::config_init::import_config(&app_handle, &import_path) {
-        Ok(_) => Ok("配置导入成功".to_string()),
-        Err(e) => Err(format!("导入配置失败: {}", e)),
+pub async fn import_config(
+    app_handle: AppHandle,
+    import_path: String,
+    strategy: Option<crate::config_init::ImportStrategy>,
+) -> Result<String, String> {
+    let strategy = strategy.unwrap_or_default();
+    match crate::config_init::import_config(&app_handle, &import_path, strategy) {
+        Ok(_) => {
+            // 导入可能替换了保险库文件，缓存的会话密钥不再对应
+            crate::secrets_vault::lock(&app_handle, "config-replaced");
+            crate::config_init::apply_settings(&app_handle);
+            Ok(i18n::t("config.imported"))
+        }
+        Err(e) => Err(i18n::tf("config.import_failed", &[("error", &e)])),
     }
 }
 
 /// 导出配置
+///
+/// 默认不包含保险库，`include_secrets` 为 true 时附带加密后的保险库
 #[command]
-pub async fn export_config(app_handle: AppHandle, export_path: String) -> Result<String, String> {
-    match crate::config_init::export_config(&app_handle, &export_path) {
-        Ok(_) => Ok("配置导出成功".to_string()),
-        Err(e) => Err(format!("导出配置失败: {}", e)),
+pub async fn export_config(
+    app_handle: AppHandle,
+    export_path: String,
+    include_secrets: Option<bool>,
+) -> Result<String, String> {
+    match crate::config_init::export_config(&app_handle, &export_path, include_secrets.unwrap_or(false)) {
+        Ok(_) => Ok(i18n::t("config.exported")),
+        Err(e) => Err(i18n::tf("config.export_failed", &[("error", &e)])),
     }
 }
 
@@ -22,17 +41,22 @@ pub async fn export_config(app_handle: AppHandle, export_path: String) -> Result
 #[command]
 pub async fn reset_config(app_handle: AppHandle) -> Result<String, String> {
     match crate::config_init::reset_all_configs(&app_handle) {
-        Ok(_) => Ok("配置已重置为默认值".to_string()),
-        Err(e) => Err(format!("重置配置失败: {}", e)),
+        Ok(_) => {
+            crate::config_init::apply_settings(&app_handle);
+            Ok(i18n::t("config.reset"))
+        }
+        Err(e) => Err(i18n::tf("config.reset_failed", &[("error", &e)])),
     }
 }
 
 /// 备份配置
+///
+/// 默认不包含保险库，`include_secrets` 为 true 时一并备份加密后的保险库
 #[command]
-pub async fn backup_config(app_handle: AppHandle) -> Result<String, String> {
-    match crate::config_init::backup_config(&app_handle) {
-        Ok(_) => Ok("配置备份成功".to_string()),
-        Err(e) => Err(format!("备份配置失败: {}", e)),
+pub async fn backup_config(app_handle: AppHandle, include_secrets: Option<bool>) -> Result<String, String> {
+    match crate::config_init::backup_config(&app_handle, include_secrets.unwrap_or(false)) {
+        Ok(_) => Ok(i18n::t("config.backed_up")),
+        Err(e) => Err(i18n::tf("config.backup_failed", &[("error", &e)])),
     }
 }
 
@@ -41,7 +65,7 @@ pub async fn backup_config(app_handle: AppHandle) -> Result<String, String> {
 pub async fn list_config_backups(app_handle: AppHandle) -> Result<Vec<String>, String> {
     match crate::config_init::list_config_backups(&app_handle) {
         Ok(backups) => Ok(backups),
-        Err(e) => Err(format!("获取备份列表失败: {}", e)),
+        Err(e) => Err(i18n::tf("config.list_backups_failed", &[("error", &e)])),
     }
 }
 
@@ -56,23 +80,24 @@ pub async fn restore_config_backup(app_handle: AppHandle, backup_name: String) -
     };
 
     match crate::config_init::restore_config_backup(&app_handle, actual_backup_name) {
-        Ok(_) => Ok("配置备份恢复成功".to_string()),
-        Err(e) => Err(format!("恢复备份失败: {}", e)),
+        Ok(_) => {
+            crate::secrets_vault::lock(&app_handle, "config-replaced");
+            crate::config_init::apply_settings(&app_handle);
+            Ok(i18n::t("config.restored"))
+        }
+        Err(e) => Err(i18n::tf("config.restore_failed", &[("error", &e)])),
     }
 }
 
 /// 获取用户设置
 #[command]
 pub async fn get_user_settings(app_handle: AppHandle) -> Result<serde_json::Value, String> {
-    let config_dir = app_handle.path().app_config_dir()
-        .map_err(|e| format!("获取配置目录失败: {}", e))?;
-    let settings_path = config_dir.join("user-settings.json");
-
-    if settings_path.exists() {
-        let content = std::fs::read_to_string(&settings_path)
-            .map_err(|e| format!("读取设置文件失败: {}", e))?;
-        serde_json::from_str(&content)
-            .map_err(|e| format!("解析设置文件失败: {}", e))
+    let profile = crate::config_profiles::active_profile(&app_handle);
+    let effective = crate::config_profiles::read_effective(&app_handle, profile.as_deref(), "user-settings.json")
+        .map_err(|e| i18n::tf("settings.read_failed", &[("error", &e)]))?;
+
+    if let Some(value) = effective {
+        Ok(value)
     } else {
         // 返回默认设置
         Ok(serde_json::json!({
@@ -88,7 +113,8 @@ pub async fn get_user_settings(app_handle: AppHandle) -> Result<serde_json::Valu
             "tools": {
                 "remember_last_used": true,
                 "favorites": []
-            }
+            },
+            "shortcuts": crate::global_shortcuts::default_bindings()
         }))
     }
 }
@@ -96,31 +122,51 @@ pub async fn get_user_settings(app_handle: AppHandle) -> Result<serde_json::Valu
 /// 保存用户设置
 #[command]
 pub async fn save_user_settings(app_handle: AppHandle, settings: serde_json::Value) -> Result<String, String> {
-    let config_dir = app_handle.path().app_config_dir()
-        .map_err(|e| format!("获取配置目录失败: {}", e))?;
-    let settings_path = config_dir.join("user-settings.json");
+    crate::config_profiles::write_effective(&app_handle, "user-settings.json", &settings)
+        .map_err(|e| i18n::tf("settings.write_failed", &[("error", &e)]))?;
+
+    crate::config_init::apply_settings(&app_handle);
+    Ok(i18n::t("settings.saved"))
+}
+
+/// 记录最近使用的工具（用于托盘菜单）
+#[command]
+pub async fn record_recent_tool(app_handle: AppHandle, tool_id: String, name: Option<String>) -> Result<(), String> {
+    crate::tray::record_recent_tool(&app_handle, &tool_id, name.as_deref())
+        .map_err(|e| i18n::tf("tools.record_recent_failed", &[("error", &e)]))
+}
 
-    let content = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("序列化设置失败: {}", e))?;
+/// 收藏的工具
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct FavoriteTool {
+    pub id: String,
+    pub name: Option<String>,
+}
 
-    std::fs::write(&settings_path, content)
-        .map_err(|e| format!("写入设置文件失败: {}", e))?;
+/// 保存收藏的工具（用于托盘菜单和应用菜单）
+#[command]
+pub async fn save_favorite_tools(app_handle: AppHandle, tools: Vec<FavoriteTool>) -> Result<(), String> {
+    let tools: Vec<(String, Option<String>)> = tools.into_iter().map(|t| (t.id, t.name)).collect();
+    crate::tray::set_favorite_tools(&app_handle, &tools)
+        .map_err(|e| i18n::tf("tools.save_favorites_failed", &[("error", &e)]))
+}
 
-    Ok("用户设置已保存".to_string())
+/// 获取开机自启动状态（设置值以及系统中是否实际存在自启动项）
+#[command]
+pub async fn get_autostart_status(app_handle: AppHandle) -> Result<crate::autostart::AutostartStatus, String> {
+    crate::autostart::status(&app_handle)
+        .map_err(|e| i18n::tf("autostart.status_failed", &[("error", &e)]))
 }
 
 /// 获取工具配置
 #[command]
 pub async fn get_tools_config(app_handle: AppHandle) -> Result<serde_json::Value, String> {
-    let config_dir = app_handle.path().app_config_dir()
-        .map_err(|e| format!("获取配置目录失败: {}", e))?;
-    let config_path = config_dir.join("tools-config.json");
-
-    if config_path.exists() {
-        let content = std::fs::read_to_string(&config_path)
-            .map_err(|e| format!("读取工具配置失败: {}", e))?;
-        serde_json::from_str(&content)
-            .map_err(|e| format!("解析工具配置失败: {}", e))
+    let profile = crate::config_profiles::active_profile(&app_handle);
+    let effective = crate::config_profiles::read_effective(&app_handle, profile.as_deref(), "tools-config.json")
+        .map_err(|e| i18n::tf("tools.read_config_failed", &[("error", &e)]))?;
+
+    if let Some(value) = effective {
+        Ok(value)
     } else {
         // 返回默认配置
         Ok(serde_json::json!({
@@ -144,42 +190,39 @@ pub async fn get_tools_config(app_handle: AppHandle) -> Result<serde_json::Value
 /// 保存工具配置
 #[command]
 pub async fn save_tools_config(app_handle: AppHandle, config: serde_json::Value) -> Result<String, String> {
-    let config_dir = app_handle.path().app_config_dir()
-        .map_err(|e| format!("获取配置目录失败: {}", e))?;
-    let config_path = config_dir.join("tools-config.json");
-
-    let content = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("序列化工具配置失败: {}", e))?;
-
-    std::fs::write(&config_path, content)
-        .map_err(|e| format!("写入工具配置失败: {}", e))?;
+    crate::config_profiles::write_effective(&app_handle, "tools-config.json", &config)
+        .map_err(|e| i18n::tf("tools.write_config_failed", &[("error", &e)]))?;
 
-    Ok("工具配置已保存".to_string())
+    Ok(i18n::t("tools.config_saved"))
 }
 
 /// 清理缓存
 #[command]
 pub async fn clear_cache(app_handle: AppHandle) -> Result<String, String> {
-    let config_dir = app_handle.path().app_config_dir()
-        .map_err(|e| format!("获取配置目录失败: {}", e))?;
-    let cache_dir = config_dir.join("cache");
+    let cache_dir = crate::app_paths::cache_dir(&app_handle)
+        .map_err(|e| i18n::tf("dirs.cache_failed", &[("error", &e)]))?;
 
     if cache_dir.exists() {
         std::fs::remove_dir_all(&cache_dir)
-            .map_err(|e| format!("删除缓存目录失败: {}", e))?;
+            .map_err(|e| i18n::tf("dirs.remove_cache_failed", &[("error", &e)]))?;
 
         // 重新创建缓存目录
         std::fs::create_dir_all(&cache_dir)
-            .map_err(|e| format!("创建缓存目录失败: {}", e))?;
+            .map_err(|e| i18n::tf("dirs.create_cache_failed", &[("error", &e)]))?;
     }
 
-    Ok("缓存已清理".to_string())
+    Ok(i18n::t("dirs.cache_cleared"))
 }
 
 /// 获取应用信息
 #[command]
 pub async fn get_app_info() -> Result<serde_json::Value, String> {
-    Ok(serde_json::json!({
+    Ok(app_info())
+}
+
+/// 应用及构建信息（崩溃报告也会使用）
+pub fn app_info() -> serde_json::Value {
+    serde_json::json!({
         "version": option_env!("CARGO_PKG_VERSION").unwrap_or("unknown"),
         "name": option_env!("CARGO_PKG_NAME").unwrap_or("utilx"),
         "description": option_env!("CARGO_PKG_DESCRIPTION").unwrap_or("开发者效率工具箱"),
@@ -188,28 +231,34 @@ pub async fn get_app_info() -> Result<serde_json::Value, String> {
         "build_time": option_env!("VERGEN_BUILD_TIMESTAMP").unwrap_or("unknown"),
         "git_commit": option_env!("VERGEN_GIT_SHA").unwrap_or("unknown"),
         "git_commit_timestamp": option_env!("VERGEN_GIT_COMMIT_TIMESTAMP").unwrap_or("unknown"),
-        "rust_version": option_env!("VERGEN_RUSTC_SEMVER").unwrap_or("unknown")
-    }))
+        "rust_version": option_env!("VERGEN_RUSTC_SEMVER").unwrap_or("unknown"),
+        "portable": crate::app_paths::is_portable()
+    })
 }
 
-/// 获取配置目录路径
+/// 获取配置目录路径（便携模式下为可执行文件旁的目录）
 #[command]
 pub async fn get_config_dir(app_handle: AppHandle) -> Result<String, String> {
-    let config_dir = app_handle.path().app_config_dir()
-        .map_err(|e| format!("获取配置目录失败: {}", e))?;
+    let config_dir = crate::app_paths::config_dir(&app_handle)
+        .map_err(|e| i18n::tf("dirs.config_failed", &[("error", &e)]))?;
+
+    if !config_dir.exists() {
+        std::fs::create_dir_all(&config_dir)
+            .map_err(|e| i18n::tf("dirs.create_config_failed", &[("error", &e)]))?;
+    }
+
     Ok(config_dir.to_string_lossy().to_string())
 }
 
 /// 获取缓存目录路径
 #[command]
 pub async fn get_cache_dir(app_handle: AppHandle) -> Result<String, String> {
-    let config_dir = app_handle.path().app_config_dir()
-        .map_err(|e| format!("获取配置目录失败: {}", e))?;
-    let cache_dir = config_dir.join("cache");
+    let cache_dir = crate::app_paths::cache_dir(&app_handle)
+        .map_err(|e| i18n::tf("dirs.cache_failed", &[("error", &e)]))?;
 
     if !cache_dir.exists() {
         std::fs::create_dir_all(&cache_dir)
-            .map_err(|e| format!("创建缓存目录失败: {}", e))?;
+            .map_err(|e| i18n::tf("dirs.create_cache_failed", &[("error", &e)]))?;
     }
 
     Ok(cache_dir.to_string_lossy().to_string())
@@ -218,13 +267,12 @@ pub async fn get_cache_dir(app_handle: AppHandle) -> Result<String, String> {
 /// 获取日志目录路径
 #[command]
 pub async fn get_log_dir(app_handle: AppHandle) -> Result<String, String> {
-    let config_dir = app_handle.path().app_config_dir()
-        .map_err(|e| format!("获取配置目录失败: {}", e))?;
-    let log_dir = config_dir.join("logs");
+    let log_dir = crate::app_paths::log_dir(&app_handle)
+        .map_err(|e| i18n::tf("dirs.log_failed", &[("error", &e)]))?;
 
     if !log_dir.exists() {
         std::fs::create_dir_all(&log_dir)
-            .map_err(|e| format!("创建日志目录失败: {}", e))?;
+            .map_err(|e| i18n::tf("dirs.create_log_failed", &[("error", &e)]))?;
     }
 
     Ok(log_dir.to_string_lossy().to_string())
@@ -233,32 +281,37 @@ pub async fn get_log_dir(app_handle: AppHandle) -> Result<String, String> {
 /// 打开配置目录
 #[command]
 pub async fn open_config_dir(app_handle: AppHandle) -> Result<String, String> {
-    let config_dir = app_handle.path().app_config_dir()
-        .map_err(|e| format!("获取配置目录失败: {}", e))?;
+    let config_dir = crate::app_paths::config_dir(&app_handle)
+        .map_err(|e| i18n::tf("dirs.config_failed", &[("error", &e)]))?;
+
+    open_in_file_manager(&config_dir)
+        .map_err(|e| i18n::tf("dirs.open_config_failed", &[("error", &e)]))?;
+
+    Ok(i18n::t("dirs.config_opened"))
+}
 
+/// 在系统文件管理器中打开目录
+pub fn open_in_file_manager(path: &std::path::Path) -> std::io::Result<()> {
     #[cfg(target_os = "macos")]
     {
         std::process::Command::new("open")
-            .arg(&config_dir)
-            .spawn()
-            .map_err(|e| format!("打开配置目录失败: {}", e))?;
+            .arg(path)
+            .spawn()?;
     }
 
     #[cfg(target_os = "windows")]
     {
         std::process::Command::new("explorer")
-            .arg(&config_dir)
-            .spawn()
-            .map_err(|e| format!("打开配置目录失败: {}", e))?;
+            .arg(path)
+            .spawn()?;
     }
 
     #[cfg(target_os = "linux")]
     {
         std::process::Command::new("xdg-open")
-            .arg(&config_dir)
-            .spawn()
-            .map_err(|e| format!("打开配置目录失败: {}", e))?;
+            .arg(path)
+            .spawn()?;
     }
 
-    Ok("配置目录已打开".to_string())
+    Ok(())
 }
\ No newline at end of file
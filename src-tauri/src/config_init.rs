@@ -1,15 +1,19 @@
 use crate::update_config::UpdateConfig;
-use tauri::{AppHandle, Manager};
+use crate::i18n;
+use tauri::AppHandle;
 use std::fs;
 
 /// 初始化应用配置
 pub fn initialize_app_config(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔧 初始化应用配置...");
+    log::info!("🔧 初始化应用配置...");
 
     // 确保配置目录存在
-    let config_dir = app_handle.path().app_config_dir()?;
+    let config_dir = crate::app_paths::config_dir(app_handle)?;
     fs::create_dir_all(&config_dir)?;
-    println!("✅ 配置目录已创建: {}", config_dir.display());
+    log::info!("✅ 配置目录已创建: {}", config_dir.display());
+    if let Some(portable_root) = crate::app_paths::portable_root() {
+        log::info!("💼 便携模式：所有数据保存在 {}", portable_root.display());
+    }
 
     // 初始化更新配置
     initialize_update_config(app_handle)?;
@@ -17,24 +21,24 @@ pub fn initialize_app_config(app_handle: &AppHandle) -> Result<(), Box<dyn std::
     // 初始化其他配置文件
     initialize_other_configs(app_handle)?;
 
-    println!("🎉 应用配置初始化完成");
+    log::info!("🎉 应用配置初始化完成");
     Ok(())
 }
 
 /// 初始化更新配置
 fn initialize_update_config(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    let config_path = app_handle.path().app_config_dir()?.join("update-config.json");
+    let config_path = crate::app_paths::config_dir(app_handle)?.join("update-config.json");
 
     if !config_path.exists() {
-        println!("📝 创建默认更新配置...");
+        log::info!("📝 创建默认更新配置...");
 
         // 从内置的默认配置创建配置文件
         let default_config = UpdateConfig::default();
         default_config.save(app_handle)?;
 
-        println!("✅ 默认更新配置已创建");
+        log::info!("✅ 默认更新配置已创建");
     } else {
-        println!("✅ 更新配置已存在");
+        log::info!("✅ 更新配置已存在");
     }
 
     Ok(())
@@ -42,7 +46,7 @@ fn initialize_update_config(app_handle: &AppHandle) -> Result<(), Box<dyn std::e
 
 /// 初始化其他配置文件
 fn initialize_other_configs(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    let config_dir = app_handle.path().app_config_dir()?;
+    let config_dir = crate::app_paths::config_dir(app_handle)?;
 
     // 创建用户设置配置
     let user_settings_path = config_dir.join("user-settings.json");
@@ -60,11 +64,12 @@ fn initialize_other_configs(app_handle: &AppHandle) -> Result<(), Box<dyn std::e
             "tools": {
                 "remember_last_used": true,
                 "favorites": []
-            }
+            },
+            "shortcuts": crate::global_shortcuts::default_bindings()
         });
 
         fs::write(&user_settings_path, serde_json::to_string_pretty(&default_settings)?)?;
-        println!("✅ 用户设置配置已创建");
+        log::info!("✅ 用户设置配置已创建");
     }
 
     // 创建工具配置
@@ -87,49 +92,54 @@ fn initialize_other_configs(app_handle: &AppHandle) -> Result<(), Box<dyn std::e
         });
 
         fs::write(&tools_config_path, serde_json::to_string_pretty(&default_tools_config)?)?;
-        println!("✅ 工具配置已创建");
+        log::info!("✅ 工具配置已创建");
     }
 
     // 创建缓存目录
-    let cache_dir = config_dir.join("cache");
+    let cache_dir = crate::app_paths::cache_dir(app_handle)?;
     if !cache_dir.exists() {
         fs::create_dir_all(&cache_dir)?;
-        println!("✅ 缓存目录已创建");
+        log::info!("✅ 缓存目录已创建");
     }
 
     // 创建日志目录
-    let log_dir = config_dir.join("logs");
+    let log_dir = crate::app_paths::log_dir(app_handle)?;
     if !log_dir.exists() {
         fs::create_dir_all(&log_dir)?;
-        println!("✅ 日志目录已创建");
+        log::info!("✅ 日志目录已创建");
     }
 
     // 创建更新缓存目录
-    let update_cache_dir = config_dir.join("updates");
+    let update_cache_dir = crate::app_paths::updates_dir(app_handle)?;
     if !update_cache_dir.exists() {
         fs::create_dir_all(&update_cache_dir)?;
-        println!("✅ 更新缓存目录已创建");
+        log::info!("✅ 更新缓存目录已创建");
     }
 
     Ok(())
 }
 
 /// 创建配置文件的备份
-pub fn backup_config(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    let config_dir = app_handle.path().app_config_dir()?;
-    let backup_dir = config_dir.join("backups");
+///
+/// 保险库默认不参与备份，只有 `include_secrets` 为 true 时才会一并备份（仍为加密形式）
+pub fn backup_config(app_handle: &AppHandle, include_secrets: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let config_dir = crate::app_paths::config_dir(app_handle)?;
+    let backup_dir = crate::app_paths::backups_dir(app_handle)?;
     fs::create_dir_all(&backup_dir)?;
 
     let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
     let backup_name = format!("config_backup_{}", timestamp);
 
     // 需要备份的配置文件列表
-    let config_files = vec![
+    let mut config_files = vec![
         "update-config.json",
         "user-settings.json",
         "tools-config.json",
         "update-history.json"
     ];
+    if include_secrets {
+        config_files.push(crate::secrets_vault::VAULT_FILE_NAME);
+    }
 
     for file in config_files {
         let source_path = config_dir.join(file);
@@ -139,14 +149,27 @@ pub fn backup_config(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::E
                 fs::create_dir_all(parent)?;
             }
             fs::copy(&source_path, &backup_path)?;
-            println!("✅ 配置文件已备份: {}", file);
+            log::info!("✅ 配置文件已备份: {}", file);
+        }
+    }
+
+    // 当前配置方案的覆盖一并备份（目录名记录备份时的方案），导入或重置后可以完整恢复
+    if let Some(profile) = crate::config_profiles::active_profile(app_handle) {
+        let profile_backup_dir = backup_dir.join(&backup_name).join("profiles").join(&profile);
+        fs::create_dir_all(&profile_backup_dir)?;
+        for file in crate::config_profiles::PROFILE_FILES {
+            let source_path = crate::config_profiles::overlay_path(app_handle, &profile, file)?;
+            if source_path.exists() {
+                fs::copy(&source_path, profile_backup_dir.join(file))?;
+                log::info!("✅ 配置方案 {} 的覆盖已备份: {}", profile, file);
+            }
         }
     }
 
     // 清理旧备份（保留最近10个）
     cleanup_old_backups(&backup_dir, 10)?;
 
-    println!("✅ 配置备份完成: {}", backup_name);
+    log::info!("✅ 配置备份完成: {}", backup_name);
     Ok(())
 }
 
@@ -179,7 +202,7 @@ fn cleanup_old_backups(backup_dir: &std::path::Path, keep_count: usize) -> Resul
     // 删除超过保留数量的备份
     for backup in backups.iter().skip(keep_count) {
         fs::remove_dir_all(backup)?;
-        println!("🗑️ 已删除旧备份: {}", backup.file_name().unwrap().to_str().unwrap());
+        log::info!("🗑️ 已删除旧备份: {}", backup.file_name().unwrap().to_str().unwrap());
     }
 
     Ok(())
@@ -187,19 +210,20 @@ fn cleanup_old_backups(backup_dir: &std::path::Path, keep_count: usize) -> Resul
 
 /// 恢复配置备份
 pub fn restore_config_backup(app_handle: &AppHandle, backup_name: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let config_dir = app_handle.path().app_config_dir()?;
-    let backup_dir = config_dir.join("backups").join(backup_name);
+    let config_dir = crate::app_paths::config_dir(app_handle)?;
+    let backup_dir = crate::app_paths::backups_dir(app_handle)?.join(backup_name);
 
     if !backup_dir.exists() {
-        return Err(format!("备份不存在: {}", backup_name).into());
+        return Err(i18n::tf("config.backup_not_found", &[("name", &backup_name)]).into());
     }
 
-    // 需要恢复的配置文件列表
+    // 需要恢复的配置文件列表（保险库只在备份时选择包含才会存在）
     let config_files = vec![
         "update-config.json",
         "user-settings.json",
         "tools-config.json",
-        "update-history.json"
+        "update-history.json",
+        crate::secrets_vault::VAULT_FILE_NAME
     ];
 
     for file in config_files {
@@ -207,18 +231,37 @@ pub fn restore_config_backup(app_handle: &AppHandle, backup_name: &str) -> Resul
         if backup_path.exists() {
             let target_path = config_dir.join(file);
             fs::copy(&backup_path, &target_path)?;
-            println!("✅ 配置文件已恢复: {}", file);
+            log::info!("✅ 配置文件已恢复: {}", file);
         }
     }
 
-    println!("✅ 配置备份已恢复: {}", backup_name);
+    // 恢复备份时所用配置方案的覆盖；备份中没有的覆盖文件说明当时没有覆盖
+    if let Ok(entries) = fs::read_dir(backup_dir.join("profiles")) {
+        for entry in entries.flatten().filter(|entry| entry.path().is_dir()) {
+            let profile = entry.file_name().to_string_lossy().to_string();
+            for file in crate::config_profiles::PROFILE_FILES {
+                let target_path = crate::config_profiles::overlay_path(app_handle, &profile, file)?;
+                let backup_path = entry.path().join(file);
+                if backup_path.exists() {
+                    if let Some(parent) = target_path.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    fs::copy(&backup_path, &target_path)?;
+                } else if target_path.exists() {
+                    fs::remove_file(&target_path)?;
+                }
+            }
+            log::info!("✅ 配置方案 {} 的覆盖已恢复", profile);
+        }
+    }
+
+    log::info!("✅ 配置备份已恢复: {}", backup_name);
     Ok(())
 }
 
 /// 列出所有备份
 pub fn list_config_backups(app_handle: &AppHandle) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-    let config_dir = app_handle.path().app_config_dir()?;
-    let backup_dir = config_dir.join("backups");
+    let backup_dir = crate::app_paths::backups_dir(app_handle)?;
 
     if !backup_dir.exists() {
         return Ok(Vec::new());
@@ -255,12 +298,12 @@ pub fn list_config_backups(app_handle: &AppHandle) -> Result<Vec<String>, Box<dy
 
 /// 重置所有配置到默认值
 pub fn reset_all_configs(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    println!("⚠️ 重置所有配置到默认值...");
+    log::warn!("⚠️ 重置所有配置到默认值...");
 
     // 先备份当前配置
-    backup_config(app_handle)?;
+    backup_config(app_handle, false)?;
 
-    let config_dir = app_handle.path().app_config_dir()?;
+    let config_dir = crate::app_paths::config_dir(app_handle)?;
 
     // 删除现有配置文件
     let config_files = vec![
@@ -273,7 +316,18 @@ pub fn reset_all_configs(app_handle: &AppHandle) -> Result<(), Box<dyn std::erro
         let file_path = config_dir.join(file);
         if file_path.exists() {
             fs::remove_file(&file_path)?;
-            println!("🗑️ 已删除配置文件: {}", file);
+            log::info!("🗑️ 已删除配置文件: {}", file);
+        }
+    }
+
+    // 当前配置方案的覆盖也要删除，否则会继续覆盖默认值（已随上面的备份保存）
+    if let Some(profile) = crate::config_profiles::active_profile(app_handle) {
+        for file in crate::config_profiles::PROFILE_FILES {
+            let overlay_path = crate::config_profiles::overlay_path(app_handle, &profile, file)?;
+            if overlay_path.exists() {
+                fs::remove_file(&overlay_path)?;
+                log::info!("🗑️ 已删除配置方案 {} 的覆盖: {}", profile, file);
+            }
         }
     }
 
@@ -281,92 +335,291 @@ pub fn reset_all_configs(app_handle: &AppHandle) -> Result<(), Box<dyn std::erro
     initialize_update_config(app_handle)?;
     initialize_other_configs(app_handle)?;
 
-    println!("✅ 所有配置已重置为默认值");
+    log::info!("✅ 所有配置已重置为默认值");
     Ok(())
 }
 
+/// 当前配置导出格式版本
+///
+/// 1.1: 不再导出 `update_config.last_check_time`（属于本机状态，不应跨设备迁移）
+pub const EXPORT_VERSION: &str = "1.1";
+
+/// 导入策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportStrategy {
+    /// 用导入的内容整体替换现有配置文件
+    #[default]
+    Replace,
+    /// 深度合并：对象逐键合并，数组取并集（例如 `tools.favorites`）
+    Merge,
+}
+
+/// 导出文件与其对应的配置文件
+pub const EXPORT_SECTIONS: [(&str, &str); 3] = [
+    ("update_config", "update-config.json"),
+    ("user_settings", "user-settings.json"),
+    ("tools_config", "tools-config.json"),
+];
+
 /// 导出配置
-pub fn export_config(app_handle: &AppHandle, export_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let config_dir = app_handle.path().app_config_dir()?;
+///
+/// 导出的是当前配置方案下的生效配置；保险库默认不导出，
+/// 只有 `include_secrets` 为 true 时才会附带加密后的保险库
+pub fn export_config(app_handle: &AppHandle, export_path: &str, include_secrets: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let config_dir = crate::app_paths::config_dir(app_handle)?;
     let export_file = std::path::Path::new(export_path);
 
     // 创建配置归档
     use std::fs::File;
     use std::io::Write;
 
-    let mut export_data = serde_json::Map::new();
+    let active_profile = crate::config_profiles::active_profile(app_handle);
+    let mut export_data = build_export_data(app_handle, active_profile.as_deref())?;
 
-    // 导出更新配置
-    let update_config_path = config_dir.join("update-config.json");
-    if update_config_path.exists() {
-        let content = fs::read_to_string(&update_config_path)?;
-        if let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) {
-            export_data.insert("update_config".to_string(), config);
+    if include_secrets {
+        let vault_path = config_dir.join(crate::secrets_vault::VAULT_FILE_NAME);
+        if vault_path.exists() {
+            let vault: serde_json::Value = serde_json::from_str(&fs::read_to_string(&vault_path)?)?;
+            export_data.insert("secrets_vault".to_string(), vault);
+            log::info!("🔐 已附带加密保险库");
         }
     }
 
-    // 导出用户设置
-    let user_settings_path = config_dir.join("user-settings.json");
-    if user_settings_path.exists() {
-        let content = fs::read_to_string(&user_settings_path)?;
-        if let Ok(settings) = serde_json::from_str::<serde_json::Value>(&content) {
-            export_data.insert("user_settings".to_string(), settings);
+    // 写入导出文件
+    let mut file = File::create(export_file)?;
+    file.write_all(serde_json::to_string_pretty(&export_data)?.as_bytes())?;
+
+    log::info!("✅ 配置已导出到: {}", export_path);
+    Ok(())
+}
+
+/// 构建导出数据：指定配置方案下的生效配置和元数据
+pub fn build_export_data(app_handle: &AppHandle, profile: Option<&str>) -> Result<serde_json::Map<String, serde_json::Value>, Box<dyn std::error::Error>> {
+    let mut export_data = serde_json::Map::new();
+
+    for (key, file_name) in EXPORT_SECTIONS {
+        if let Ok(Some(config)) = crate::config_profiles::read_effective(app_handle, profile, file_name) {
+            export_data.insert(key.to_string(), config);
         }
     }
 
-    // 导出工具配置
-    let tools_config_path = config_dir.join("tools-config.json");
-    if tools_config_path.exists() {
-        let content = fs::read_to_string(&tools_config_path)?;
-        if let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) {
-            export_data.insert("tools_config".to_string(), config);
-        }
+    // 本机状态不随配置导出
+    if let Some(update_config) = export_data.get_mut("update_config").and_then(|c| c.as_object_mut()) {
+        update_config.remove("last_check_time");
     }
 
     // 添加元数据
-    export_data.insert("export_version".to_string(), serde_json::Value::String("1.0".to_string()));
+    insert_export_metadata(&mut export_data);
+    Ok(export_data)
+}
+
+/// 写入导出格式版本、导出时间和应用版本；导入方据此调用 `migrate_export_data` 迁移
+pub fn insert_export_metadata(export_data: &mut serde_json::Map<String, serde_json::Value>) {
+    export_data.insert("export_version".to_string(), serde_json::Value::String(EXPORT_VERSION.to_string()));
     export_data.insert("export_time".to_string(), serde_json::Value::String(chrono::Utc::now().to_rfc3339()));
     export_data.insert("app_version".to_string(), serde_json::Value::String(env!("CARGO_PKG_VERSION").to_string()));
+}
 
-    // 写入导出文件
-    let mut file = File::create(export_file)?;
-    file.write_all(serde_json::to_string_pretty(&export_data)?.as_bytes())?;
+/// 配置文件被修改后（保存设置、切换配置方案、导入、重置、恢复备份、同步拉取）让各模块重新读取：开机自启动、后端语言、托盘菜单、应用菜单、主题和全局快捷键
+pub fn apply_settings(app_handle: &AppHandle) {
+    // 使开机自启动项与 auto_start 设置一致
+    if let Err(e) = crate::autostart::reconcile(app_handle) {
+        log::warn!("⚠️ 同步开机自启动失败: {}", e);
+    }
 
-    println!("✅ 配置已导出到: {}", export_path);
-    Ok(())
+    // 收藏、语言或主题可能变化，重建托盘菜单和应用菜单并重新应用主题
+    crate::i18n::reload(app_handle);
+    crate::tray::refresh_menu(app_handle);
+    crate::app_menu::refresh(app_handle);
+    crate::theme_sync::apply(app_handle);
+    crate::global_shortcuts::reload(app_handle);
 }
 
 /// 导入配置
-pub fn import_config(app_handle: &AppHandle, import_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    println!("📥 导入配置: {}", import_path);
-
-    // 先备份当前配置
-    backup_config(app_handle)?;
+pub fn import_config(app_handle: &AppHandle, import_path: &str, strategy: ImportStrategy) -> Result<(), Box<dyn std::error::Error>> {
+    log::info!("📥 导入配置: {} (策略: {:?})", import_path, strategy);
 
     let import_data: serde_json::Value = serde_json::from_str(&fs::read_to_string(import_path)?)?;
-    let config_dir = app_handle.path().app_config_dir()?;
 
-    // 导入更新配置
-    if let Some(update_config) = import_data.get("update_config") {
-        let update_config_path = config_dir.join("update-config.json");
-        fs::write(&update_config_path, serde_json::to_string_pretty(update_config)?)?;
-        println!("✅ 更新配置已导入");
-    }
+    // 先校验并迁移，确认可以导入后再备份和写入
+    let import_data = migrate_export_data(import_data)?;
+
+    let imported_vault = import_data.get("secrets_vault");
+
+    // 先备份当前配置（导入文件带有保险库时一并备份现有保险库）
+    backup_config(app_handle, imported_vault.is_some())?;
 
-    // 导入用户设置
-    if let Some(user_settings) = import_data.get("user_settings") {
-        let user_settings_path = config_dir.join("user-settings.json");
-        fs::write(&user_settings_path, serde_json::to_string_pretty(user_settings)?)?;
-        println!("✅ 用户设置已导入");
+    let config_dir = crate::app_paths::config_dir(app_handle)?;
+
+    // 与导出对称：基于当前配置方案下的生效配置合并，并通过方案写入（激活方案时写入方案覆盖）
+    let profile = crate::config_profiles::active_profile(app_handle);
+    for (key, file_name) in EXPORT_SECTIONS {
+        let Some(imported) = import_data.get(key) else {
+            continue;
+        };
+
+        let content = match strategy {
+            ImportStrategy::Replace => imported.clone(),
+            ImportStrategy::Merge => {
+                let mut current = crate::config_profiles::read_effective(app_handle, profile.as_deref(), file_name)?
+                    .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+                deep_merge(&mut current, imported);
+                current
+            }
+        };
+
+        crate::config_profiles::write_effective(app_handle, file_name, &content)?;
+        log::info!("✅ 已导入: {}", file_name);
     }
 
-    // 导入工具配置
-    if let Some(tools_config) = import_data.get("tools_config") {
-        let tools_config_path = config_dir.join("tools-config.json");
-        fs::write(&tools_config_path, serde_json::to_string_pretty(tools_config)?)?;
-        println!("✅ 工具配置已导入");
+    // 保险库使用各自的主密码加密，无法逐条合并
+    if let Some(vault) = imported_vault {
+        let vault_path = config_dir.join(crate::secrets_vault::VAULT_FILE_NAME);
+        if strategy == ImportStrategy::Merge && vault_path.exists() {
+            log::warn!("⚠️ 本地已有保险库，合并模式下跳过导入的保险库");
+        } else {
+            fs::write(&vault_path, serde_json::to_string_pretty(vault)?)?;
+            log::info!("✅ 已导入加密保险库");
+        }
     }
 
-    println!("🎉 配置导入完成");
+    log::info!("🎉 配置导入完成");
     Ok(())
-}
\ No newline at end of file
+}
+
+/// 校验导出文件的版本，并将旧格式迁移到当前格式
+pub fn migrate_export_data(mut data: serde_json::Value) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let object = data.as_object_mut()
+        .ok_or_else(|| i18n::t("config.invalid_format"))?;
+
+    // 没有版本号的文件来自引入版本号之前，按 1.0 处理
+    let export_version = object.get("export_version")
+        .and_then(|v| v.as_str())
+        .unwrap_or("1.0")
+        .to_string();
+    let (major, minor) = parse_version_pair(&export_version)
+        .ok_or_else(|| i18n::tf("config.unknown_export_version", &[("version", &export_version)]))?;
+    let (current_major, current_minor) = parse_version_pair(EXPORT_VERSION)
+        .expect("EXPORT_VERSION 必须是合法版本号");
+
+    if major > current_major {
+        return Err(i18n::tf("config.export_version_too_new", &[("version", &export_version), ("supported", &EXPORT_VERSION)]).into());
+    }
+
+    if let Some(app_version) = object.get("app_version").and_then(|v| v.as_str()) {
+        let ours = parse_version_pair(env!("CARGO_PKG_VERSION"));
+        if let (Some(theirs), Some(ours)) = (parse_version_pair(app_version), ours) {
+            if theirs.0 > ours.0 {
+                return Err(i18n::tf("config.app_version_incompatible", &[("version", &app_version), ("current", &env!("CARGO_PKG_VERSION"))]).into());
+            }
+            if theirs > ours {
+                log::warn!("⚠️ 配置由较新的应用版本 {} 导出，部分设置可能被忽略", app_version);
+            }
+        }
+    }
+
+    if !EXPORT_SECTIONS.iter().any(|(key, _)| object.contains_key(*key)) {
+        return Err(i18n::t("config.no_sections").into());
+    }
+
+    // 1.0 -> 1.1: 去掉本机状态字段
+    if (major, minor) < (1, 1) {
+        if let Some(update_config) = object.get_mut("update_config").and_then(|c| c.as_object_mut()) {
+            update_config.remove("last_check_time");
+        }
+        log::info!("🔄 已将导出格式 {} 迁移到 1.1", export_version);
+    }
+
+    if (major, minor) > (current_major, current_minor) {
+        log::warn!("⚠️ 导出格式版本 {} 较新，未知字段将按原样导入", export_version);
+    }
+
+    object.insert("export_version".to_string(), serde_json::Value::String(EXPORT_VERSION.to_string()));
+    Ok(data)
+}
+
+/// 解析版本号的主、次版本（例如 "1.2.3" -> (1, 2)）
+fn parse_version_pair(version: &str) -> Option<(u64, u64)> {
+    let mut parts = version.trim().trim_start_matches('v').split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map(|p| p.parse().ok()).unwrap_or(Some(0))?;
+    Some((major, minor))
+}
+
+/// 深度合并 JSON：对象逐键递归合并，数组取并集，其他值以导入值为准
+pub fn deep_merge(target: &mut serde_json::Value, source: &serde_json::Value) {
+    match (target, source) {
+        (serde_json::Value::Object(target_map), serde_json::Value::Object(source_map)) => {
+            for (key, value) in source_map {
+                match target_map.get_mut(key) {
+                    Some(existing) => deep_merge(existing, value),
+                    None => {
+                        target_map.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (serde_json::Value::Array(target_items), serde_json::Value::Array(source_items)) => {
+            for item in source_items {
+                if !target_items.contains(item) {
+                    target_items.push(item.clone());
+                }
+            }
+        }
+        (target, source) => {
+            *target = source.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn deep_merge_merges_objects_recursively() {
+        let mut target = json!({ "a": 1, "nested": { "x": 1, "y": 2 } });
+        deep_merge(&mut target, &json!({ "b": 2, "nested": { "y": 3, "z": 4 } }));
+        assert_eq!(target, json!({ "a": 1, "nested": { "x": 1, "y": 3, "z": 4 }, "b": 2 }));
+    }
+
+    #[test]
+    fn deep_merge_unions_arrays() {
+        let mut target = json!({ "tags": ["a", "b"] });
+        deep_merge(&mut target, &json!({ "tags": ["b", "c"] }));
+        assert_eq!(target, json!({ "tags": ["a", "b", "c"] }));
+    }
+
+    #[test]
+    fn deep_merge_replaces_mismatched_values() {
+        let mut target = json!({ "value": { "x": 1 }, "flag": true });
+        deep_merge(&mut target, &json!({ "value": "text", "flag": false }));
+        assert_eq!(target, json!({ "value": "text", "flag": false }));
+    }
+
+    #[test]
+    fn migrate_export_data_upgrades_unversioned_exports() {
+        let data = json!({ "update_config": { "auto_check_enabled": true, "last_check_time": "2024-01-01T00:00:00Z" } });
+        let migrated = migrate_export_data(data).unwrap();
+        assert_eq!(migrated["export_version"], EXPORT_VERSION);
+        assert_eq!(migrated["update_config"], json!({ "auto_check_enabled": true }));
+    }
+
+    #[test]
+    fn migrate_export_data_keeps_current_exports() {
+        let data = json!({ "export_version": EXPORT_VERSION, "user_settings": { "theme": "dark" } });
+        let migrated = migrate_export_data(data.clone()).unwrap();
+        assert_eq!(migrated, data);
+    }
+
+    #[test]
+    fn migrate_export_data_rejects_invalid_exports() {
+        assert!(migrate_export_data(json!([])).is_err());
+        assert!(migrate_export_data(json!({ "export_version": "abc", "user_settings": {} })).is_err());
+        assert!(migrate_export_data(json!({ "export_version": "99.0", "user_settings": {} })).is_err());
+        assert!(migrate_export_data(json!({ "app_version": "99.0.0", "user_settings": {} })).is_err());
+        assert!(migrate_export_data(json!({ "export_version": EXPORT_VERSION })).is_err());
+    }
+}
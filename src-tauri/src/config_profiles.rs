@@ -0,0 +1,417 @@
+use serde::{Deserialize, Serialize};
+use crate::i18n;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter};
+
+/// 可被配置方案覆盖的配置文件
+pub const PROFILE_FILES: [&str; 3] = [
+    "update-config.json",
+    "user-settings.json",
+    "tools-config.json",
+];
+
+/// 表示不使用任何配置方案（只使用基础配置）
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// 方案覆盖的对象中列出已删除键的字段（`null` 是普通的设置值，不能用来表示删除）
+const DELETED_KEY: &str = "$deleted";
+
+/// 配置方案信息
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileInfo {
+    pub name: String,
+    pub active: bool,
+    pub overridden_files: Vec<String>,
+}
+
+/// 当前激活的配置方案（持久化到 profiles/active-profile.json）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ActiveProfile {
+    active: Option<String>,
+}
+
+/// 配置方案目录
+fn profiles_dir(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(crate::app_paths::config_dir(app_handle)?.join("profiles"))
+}
+
+/// 指定配置方案的目录
+fn profile_dir(app_handle: &AppHandle, name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    validate_profile_name(name)?;
+    Ok(profiles_dir(app_handle)?.join(name))
+}
+
+/// 配置方案中某个配置文件的覆盖文件
+pub fn overlay_path(app_handle: &AppHandle, name: &str, file_name: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(profile_dir(app_handle, name)?.join(file_name))
+}
+
+/// 获取当前激活的配置方案，未激活时返回 None
+pub fn active_profile(app_handle: &AppHandle) -> Option<String> {
+    let path = profiles_dir(app_handle).ok()?.join("active-profile.json");
+    let content = fs::read_to_string(path).ok()?;
+    let active: ActiveProfile = serde_json::from_str(&content).ok()?;
+
+    // 方案目录被手动删除时回退到基础配置
+    let name = active.active?;
+    let exists = profile_dir(app_handle, &name).map(|dir| dir.is_dir()).unwrap_or(false);
+    exists.then_some(name)
+}
+
+fn set_active_profile(app_handle: &AppHandle, name: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = profiles_dir(app_handle)?;
+    fs::create_dir_all(&dir)?;
+
+    let active = ActiveProfile { active: name.map(|n| n.to_string()) };
+    fs::write(dir.join("active-profile.json"), serde_json::to_string_pretty(&active)?)?;
+    Ok(())
+}
+
+/// 列出所有配置方案
+pub fn list_profiles(app_handle: &AppHandle) -> Result<Vec<ProfileInfo>, Box<dyn std::error::Error>> {
+    let active = active_profile(app_handle);
+    let dir = profiles_dir(app_handle)?;
+
+    let mut profiles = vec![ProfileInfo {
+        name: DEFAULT_PROFILE.to_string(),
+        active: active.is_none(),
+        overridden_files: Vec::new(),
+    }];
+
+    if !dir.exists() {
+        return Ok(profiles);
+    }
+
+    let mut names = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names.sort();
+
+    for name in names {
+        let overridden_files = PROFILE_FILES.iter()
+            .filter(|file| dir.join(&name).join(file).exists())
+            .map(|file| file.to_string())
+            .collect();
+        profiles.push(ProfileInfo {
+            active: active.as_deref() == Some(name.as_str()),
+            name,
+            overridden_files,
+        });
+    }
+
+    Ok(profiles)
+}
+
+/// 创建空的配置方案（不覆盖任何配置）
+pub fn create_profile(app_handle: &AppHandle, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = profile_dir(app_handle, name)?;
+    if dir.exists() {
+        return Err(i18n::tf("profile.exists", &[("name", &name)]).into());
+    }
+
+    fs::create_dir_all(&dir)?;
+    log::info!("✅ 配置方案已创建: {}", name);
+    Ok(())
+}
+
+/// 复制配置方案；从 `default` 复制等同于创建空方案
+pub fn clone_profile(app_handle: &AppHandle, source: &str, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let target_dir = profile_dir(app_handle, name)?;
+    if target_dir.exists() {
+        return Err(i18n::tf("profile.exists", &[("name", &name)]).into());
+    }
+
+    if source == DEFAULT_PROFILE {
+        return create_profile(app_handle, name);
+    }
+
+    let source_dir = profile_dir(app_handle, source)?;
+    if !source_dir.is_dir() {
+        return Err(i18n::tf("profile.not_found", &[("name", &source)]).into());
+    }
+
+    fs::create_dir_all(&target_dir)?;
+    for file in PROFILE_FILES {
+        let source_path = source_dir.join(file);
+        if source_path.exists() {
+            fs::copy(&source_path, target_dir.join(file))?;
+        }
+    }
+
+    log::info!("✅ 配置方案已复制: {} -> {}", source, name);
+    Ok(())
+}
+
+/// 删除配置方案；删除当前方案时回退到基础配置
+pub fn delete_profile(app_handle: &AppHandle, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if name == DEFAULT_PROFILE {
+        return Err(i18n::t("profile.cannot_delete_default").into());
+    }
+
+    let dir = profile_dir(app_handle, name)?;
+    if !dir.is_dir() {
+        return Err(i18n::tf("profile.not_found", &[("name", &name)]).into());
+    }
+
+    if active_profile(app_handle).as_deref() == Some(name) {
+        switch_profile(app_handle, DEFAULT_PROFILE)?;
+    }
+
+    fs::remove_dir_all(&dir)?;
+    log::info!("🗑️ 配置方案已删除: {}", name);
+    Ok(())
+}
+
+/// 切换配置方案，并通知前端重新加载配置
+pub fn switch_profile(app_handle: &AppHandle, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if name == DEFAULT_PROFILE {
+        set_active_profile(app_handle, None)?;
+    } else {
+        if !profile_dir(app_handle, name)?.is_dir() {
+            return Err(i18n::tf("profile.not_found", &[("name", &name)]).into());
+        }
+        set_active_profile(app_handle, Some(name))?;
+    }
+
+    log::info!("🔀 已切换到配置方案: {}", name);
+    let _ = app_handle.emit("profile-changed", name);
+    Ok(())
+}
+
+/// 读取指定方案下某个配置文件的生效内容（基础配置 + 方案覆盖）
+///
+/// `profile` 为 None 时只读取基础配置；基础配置和覆盖都不存在时返回 None
+pub fn read_effective(app_handle: &AppHandle, profile: Option<&str>, file_name: &str) -> Result<Option<serde_json::Value>, Box<dyn std::error::Error>> {
+    let base_path = crate::app_paths::config_dir(app_handle)?.join(file_name);
+    let base = if base_path.exists() {
+        Some(serde_json::from_str::<serde_json::Value>(&fs::read_to_string(&base_path)?)?)
+    } else {
+        None
+    };
+
+    let overlay = match profile {
+        Some(name) if PROFILE_FILES.contains(&file_name) => {
+            let overlay_path = profile_dir(app_handle, name)?.join(file_name);
+            if overlay_path.exists() {
+                Some(serde_json::from_str::<serde_json::Value>(&fs::read_to_string(&overlay_path)?)?)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+
+    Ok(match (base, overlay) {
+        (Some(mut base), Some(overlay)) => {
+            apply_overlay(&mut base, &overlay);
+            Some(base)
+        }
+        (base, None) => base,
+        (None, Some(overlay)) => {
+            // 没有基础配置时同样去掉覆盖中的删除标记
+            let mut effective = serde_json::Value::Object(serde_json::Map::new());
+            apply_overlay(&mut effective, &overlay);
+            Some(effective)
+        }
+    })
+}
+
+/// 写入某个配置文件的生效内容
+///
+/// 未激活方案时直接写入基础配置；否则只把与基础配置不同的部分写入当前方案。
+/// 写入会同步的配置后记录内容的修改时间，供同步时比较先后
+pub fn write_effective(app_handle: &AppHandle, file_name: &str, value: &serde_json::Value) -> Result<(), Box<dyn std::error::Error>> {
+    write_effective_file(app_handle, file_name, value)?;
+    if crate::config_init::EXPORT_SECTIONS.iter().any(|(_, name)| *name == file_name) {
+        crate::settings_sync::record_local_changes(app_handle);
+    }
+    Ok(())
+}
+
+fn write_effective_file(app_handle: &AppHandle, file_name: &str, value: &serde_json::Value) -> Result<(), Box<dyn std::error::Error>> {
+    let config_dir = crate::app_paths::config_dir(app_handle)?;
+    fs::create_dir_all(&config_dir)?;
+
+    let profile = active_profile(app_handle).filter(|_| PROFILE_FILES.contains(&file_name));
+    let Some(profile) = profile else {
+        fs::write(config_dir.join(file_name), serde_json::to_string_pretty(value)?)?;
+        return Ok(());
+    };
+
+    let base = read_effective(app_handle, None, file_name)?;
+    let overlay = match &base {
+        Some(base) => diff_overlay(base, value),
+        None => Some(value.clone()),
+    };
+
+    let overlay_path = profile_dir(app_handle, &profile)?.join(file_name);
+    match overlay {
+        Some(overlay) => fs::write(&overlay_path, serde_json::to_string_pretty(&overlay)?)?,
+        None if overlay_path.exists() => fs::remove_file(&overlay_path)?,
+        None => {}
+    }
+    Ok(())
+}
+
+/// 导出配置方案的生效配置（与 `export_config` 的格式相同，可直接导入）
+pub fn export_profile(app_handle: &AppHandle, name: &str, export_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let profile = if name == DEFAULT_PROFILE {
+        None
+    } else {
+        if !profile_dir(app_handle, name)?.is_dir() {
+            return Err(i18n::tf("profile.not_found", &[("name", &name)]).into());
+        }
+        Some(name)
+    };
+
+    let mut export_data = crate::config_init::build_export_data(app_handle, profile)?;
+    export_data.insert("profile".to_string(), serde_json::Value::String(name.to_string()));
+
+    fs::write(export_path, serde_json::to_string_pretty(&export_data)?)?;
+    log::info!("✅ 配置方案 {} 已导出到: {}", name, export_path);
+    Ok(())
+}
+
+/// 将方案覆盖应用到基础配置：对象逐键合并，`$deleted` 中列出的键从基础配置中删除，其他值（包括数组）直接替换
+fn apply_overlay(base: &mut serde_json::Value, overlay: &serde_json::Value) {
+    let serde_json::Value::Object(overlay_map) = overlay else {
+        *base = overlay.clone();
+        return;
+    };
+    // 基础配置中没有或不是对象时按空对象逐键应用，去掉其中的删除标记
+    if !base.is_object() {
+        *base = serde_json::Value::Object(serde_json::Map::new());
+    }
+    let serde_json::Value::Object(base_map) = base else {
+        return;
+    };
+
+    let deleted = overlay_map.get(DELETED_KEY).and_then(|keys| keys.as_array());
+    for key in deleted.into_iter().flatten().filter_map(|key| key.as_str()) {
+        base_map.shift_remove(key);
+    }
+    for (key, value) in overlay_map.iter().filter(|(key, _)| *key != DELETED_KEY) {
+        apply_overlay(base_map.entry(key.clone()).or_insert(serde_json::Value::Null), value);
+    }
+}
+
+/// 计算生效配置相对于基础配置的覆盖部分，完全相同时返回 None
+///
+/// 基础配置中有、生效配置中已删除的键列在 `$deleted` 中
+fn diff_overlay(base: &serde_json::Value, effective: &serde_json::Value) -> Option<serde_json::Value> {
+    match (base, effective) {
+        (serde_json::Value::Object(base_map), serde_json::Value::Object(effective_map)) => {
+            let mut overlay = serde_json::Map::new();
+            for (key, value) in effective_map {
+                let changed = match base_map.get(key) {
+                    Some(base_value) => diff_overlay(base_value, value),
+                    None => Some(value.clone()),
+                };
+                if let Some(changed) = changed {
+                    overlay.insert(key.clone(), changed);
+                }
+            }
+            let deleted: Vec<serde_json::Value> = base_map
+                .keys()
+                .filter(|key| !effective_map.contains_key(*key))
+                .map(|key| serde_json::Value::String(key.clone()))
+                .collect();
+            if !deleted.is_empty() {
+                overlay.insert(DELETED_KEY.to_string(), serde_json::Value::Array(deleted));
+            }
+            (!overlay.is_empty()).then_some(serde_json::Value::Object(overlay))
+        }
+        (base, effective) if base == effective => None,
+        (_, effective) => Some(effective.clone()),
+    }
+}
+
+/// 方案名称用作目录名，只允许字母、数字、空格和 `-_`
+fn validate_profile_name(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let trimmed = name.trim();
+    let valid = !trimmed.is_empty()
+        && trimmed == name
+        && name != DEFAULT_PROFILE
+        && name.chars().count() <= 64
+        && name.chars().all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(i18n::tf("profile.invalid_name", &[("name", &name)]).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_overlay_is_none_for_identical_values() {
+        let base = json!({ "theme": "dark", "nested": { "a": 1 } });
+        assert_eq!(diff_overlay(&base, &base), None);
+    }
+
+    #[test]
+    fn diff_overlay_keeps_only_changed_keys() {
+        let base = json!({ "theme": "dark", "language": "zh-CN", "nested": { "a": 1, "b": 2 } });
+        let effective = json!({ "theme": "light", "language": "zh-CN", "nested": { "a": 1, "b": 3 }, "extra": [1] });
+        assert_eq!(
+            diff_overlay(&base, &effective),
+            Some(json!({ "theme": "light", "nested": { "b": 3 }, "extra": [1] }))
+        );
+    }
+
+    #[test]
+    fn diff_overlay_replaces_arrays_whole() {
+        let base = json!({ "list": [1, 2, 3] });
+        let effective = json!({ "list": [1, 2] });
+        assert_eq!(diff_overlay(&base, &effective), Some(json!({ "list": [1, 2] })));
+    }
+
+    #[test]
+    fn diff_overlay_lists_deleted_keys() {
+        let base = json!({ "keep": 1, "removed": 2, "nested": { "a": 1, "b": 2 } });
+        let effective = json!({ "keep": 1, "nested": { "a": 1 } });
+        assert_eq!(
+            diff_overlay(&base, &effective),
+            Some(json!({ "nested": { "$deleted": ["b"] }, "$deleted": ["removed"] }))
+        );
+    }
+
+    #[test]
+    fn null_values_survive_round_trip() {
+        let base = json!({ "proxy": "http://proxy", "nested": { "a": 1 } });
+        let effective = json!({ "proxy": null, "nested": { "a": null }, "added": null });
+        let overlay = diff_overlay(&base, &effective).unwrap();
+
+        let mut applied = base.clone();
+        apply_overlay(&mut applied, &overlay);
+        assert_eq!(applied, effective);
+    }
+
+    #[test]
+    fn apply_overlay_round_trips_diff_overlay() {
+        let base = json!({ "keep": 1, "removed": 2, "nested": { "a": 1, "b": 2 }, "list": [1] });
+        let effective = json!({ "keep": 1, "nested": { "a": 5 }, "list": [2, 3], "added": { "x": true } });
+        let overlay = diff_overlay(&base, &effective).unwrap();
+
+        let mut applied = base.clone();
+        apply_overlay(&mut applied, &overlay);
+        assert_eq!(applied, effective);
+    }
+
+    #[test]
+    fn apply_overlay_strips_tombstones_from_new_objects() {
+        let mut applied = json!({});
+        apply_overlay(&mut applied, &json!({ "added": { "x": 1, "$deleted": ["gone"] }, "$deleted": ["missing"] }));
+        assert_eq!(applied, json!({ "added": { "x": 1 } }));
+    }
+}
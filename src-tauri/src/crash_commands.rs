@@ -0,0 +1,41 @@
+use crate::crash_report::{self, CrashReport, CrashReportSummary};
+use crate::i18n;
+use tauri::command;
+
+/// 获取未处理的崩溃报告
+#[command]
+pub async fn get_pending_crash_reports() -> Result<Vec<CrashReportSummary>, String> {
+    crash_report::pending_reports()
+        .map_err(|e| i18n::tf("crash.list_failed", &[("error", &e)]))
+}
+
+/// 获取崩溃报告详情
+#[command]
+pub async fn get_crash_report(id: String) -> Result<CrashReport, String> {
+    crash_report::load_report(&id)
+        .map_err(|e| i18n::tf("crash.read_failed", &[("error", &e)]))
+}
+
+/// 导出崩溃报告
+#[command]
+pub async fn export_crash_report(id: String, export_path: String) -> Result<String, String> {
+    crash_report::export_report(&id, &export_path)
+        .map_err(|e| i18n::tf("crash.export_failed", &[("error", &e)]))?;
+    Ok(i18n::tf("crash.exported", &[("path", &export_path)]))
+}
+
+/// 保留崩溃报告，不再提示
+#[command]
+pub async fn keep_crash_report(id: String) -> Result<String, String> {
+    crash_report::mark_handled(&id)
+        .map_err(|e| i18n::tf("crash.keep_failed", &[("error", &e)]))?;
+    Ok(i18n::t("crash.kept"))
+}
+
+/// 删除崩溃报告
+#[command]
+pub async fn delete_crash_report(id: String) -> Result<String, String> {
+    crash_report::delete_report(&id)
+        .map_err(|e| i18n::tf("crash.delete_failed", &[("error", &e)]))?;
+    Ok(i18n::t("crash.deleted"))
+}
@@ -0,0 +1,368 @@
+// 崩溃报告：捕获 panic 并在下次启动时提示用户保存或导出
+use crate::i18n;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter};
+
+/// 崩溃报告目录名（位于应用数据目录下）
+const CRASH_DIR_NAME: &str = "crashes";
+
+/// 已处理的崩溃报告目录名
+const HANDLED_DIR_NAME: &str = "handled";
+
+/// 崩溃报告文件名前缀
+const REPORT_PREFIX: &str = "crash-";
+
+/// 崩溃报告中附带的日志行数
+const LOG_TAIL_LINES: usize = 200;
+
+/// 崩溃报告目录，在 setup 中确定
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// 启动时收集的系统信息，避免在 panic 时执行外部命令
+static OS_INFO: OnceLock<OsInfo> = OnceLock::new();
+
+/// 系统信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OsInfo {
+    pub os: String,
+    pub family: String,
+    pub arch: String,
+    pub version: Option<String>,
+}
+
+impl OsInfo {
+    /// 收集完整的系统信息（可能执行外部命令）
+    pub fn collect() -> Self {
+        Self {
+            version: os_version(),
+            ..Self::collect_static()
+        }
+    }
+
+    /// 不执行外部命令的系统信息
+    fn collect_static() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            family: std::env::consts::FAMILY.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            version: None,
+        }
+    }
+}
+
+/// 崩溃报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    pub id: String,
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    pub message: String,
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: String,
+    pub app: serde_json::Value,
+    pub os: OsInfo,
+    pub log_tail: Vec<String>,
+}
+
+/// 崩溃报告摘要（用于列表展示）
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReportSummary {
+    pub id: String,
+    pub timestamp: chrono::DateTime<chrono::Local>,
+    pub message: String,
+    pub location: Option<String>,
+}
+
+#[cfg(target_os = "linux")]
+fn os_version() -> Option<String> {
+    let content = fs::read_to_string("/etc/os-release").ok()?;
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+        .map(|name| name.trim_matches('"').to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn os_version() -> Option<String> {
+    let output = std::process::Command::new("sw_vers").arg("-productVersion").output().ok()?;
+    Some(format!("macOS {}", String::from_utf8_lossy(&output.stdout).trim()))
+}
+
+#[cfg(target_os = "windows")]
+fn os_version() -> Option<String> {
+    let output = std::process::Command::new("cmd").args(["/C", "ver"]).output().ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn os_version() -> Option<String> {
+    None
+}
+
+/// setup 之前发生崩溃时使用的临时目录
+fn fallback_dir() -> PathBuf {
+    std::env::temp_dir().join("utilx-crashes")
+}
+
+fn crash_dir() -> PathBuf {
+    CRASH_DIR.get().cloned().unwrap_or_else(fallback_dir)
+}
+
+/// 安装 panic hook，应在程序启动时尽早调用
+pub fn install_hook() {
+    let default_hook = std::panic::take_hook();
+    // 应用信息和基本系统信息在安装时收集，panic 时不再访问配置或执行其他初始化
+    let app = crate::config_commands::app_info();
+    let static_os = OsInfo::collect_static();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "未知错误".to_string());
+        let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+
+        let timestamp = chrono::Local::now();
+        let report = CrashReport {
+            id: format!("{}{}-{}", REPORT_PREFIX, timestamp.format("%Y%m%d-%H%M%S"), std::process::id()),
+            timestamp,
+            message,
+            location,
+            thread: std::thread::current().name().map(|name| name.to_string()),
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+            app: app.clone(),
+            os: OS_INFO.get().unwrap_or(&static_os).clone(),
+            log_tail: crate::logging::recent_lines(LOG_TAIL_LINES),
+        };
+
+        let summary = match write_report(&crash_dir(), &report) {
+            Ok(path) => format!("💥 程序崩溃: {}，崩溃报告已保存到: {}", report.message, path.display()),
+            Err(e) => format!("💥 程序崩溃: {}，保存崩溃报告失败: {}", report.message, e),
+        };
+        // 在写日志时 panic（日志锁被当前线程持有）只输出到控制台
+        if crate::logging::is_available() {
+            log::error!("{}", summary);
+            log::logger().flush();
+        } else {
+            eprintln!("{}", summary);
+        }
+
+        default_hook(info);
+    }));
+}
+
+/// 写入崩溃报告，只使用 std::fs，不经过任何需要加锁的模块
+fn write_report(dir: &Path, report: &CrashReport) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.json", report.id));
+    let mut writer = std::io::BufWriter::new(fs::File::create(&path)?);
+    serde_json::to_writer_pretty(&mut writer, report)?;
+    std::io::Write::flush(&mut writer)?;
+    Ok(path)
+}
+
+/// 确定崩溃报告目录，并把启动早期写到临时目录的报告移过来
+pub fn init(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let dir = crate::app_paths::data_dir(app_handle)?.join(CRASH_DIR_NAME);
+    fs::create_dir_all(&dir)?;
+    let _ = OS_INFO.set(OsInfo::collect());
+
+    if let Ok(entries) = fs::read_dir(fallback_dir()) {
+        for entry in entries.flatten() {
+            if is_report_file(&entry.path()) {
+                let _ = fs::rename(entry.path(), dir.join(entry.file_name()));
+            }
+        }
+    }
+
+    let _ = CRASH_DIR.set(dir);
+    Ok(())
+}
+
+fn is_report_file(path: &Path) -> bool {
+    path.is_file()
+        && path.extension().is_some_and(|ext| ext == "json")
+        && path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with(REPORT_PREFIX))
+}
+
+/// 报告 ID 只能是文件名，不能包含路径
+fn report_path(dir: &Path, id: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if !id.starts_with(REPORT_PREFIX) || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(i18n::tf("crash.invalid_id", &[("id", &id)]).into());
+    }
+
+    let path = dir.join(format!("{}.json", id));
+    if !path.exists() {
+        return Err(i18n::tf("crash.not_found", &[("id", &id)]).into());
+    }
+    Ok(path)
+}
+
+/// 列出尚未处理的崩溃报告
+pub fn pending_reports() -> Result<Vec<CrashReportSummary>, Box<dyn std::error::Error>> {
+    pending_reports_in(&crash_dir())
+}
+
+fn pending_reports_in(dir: &Path) -> Result<Vec<CrashReportSummary>, Box<dyn std::error::Error>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut reports: Vec<CrashReportSummary> = fs::read_dir(dir)?
+        .flatten()
+        .filter(|entry| is_report_file(&entry.path()))
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path()).ok()?;
+            let report: CrashReport = serde_json::from_str(&content).ok()?;
+            Some(CrashReportSummary {
+                id: report.id,
+                timestamp: report.timestamp,
+                message: report.message,
+                location: report.location,
+            })
+        })
+        .collect();
+    reports.sort_by_key(|report| std::cmp::Reverse(report.timestamp));
+    Ok(reports)
+}
+
+/// 所有崩溃报告文件（包括已处理的）
+pub fn report_files() -> Vec<PathBuf> {
+    let dir = crash_dir();
+    [dir.clone(), dir.join(HANDLED_DIR_NAME)]
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| is_report_file(path))
+        .collect()
+}
+
+/// 读取完整的崩溃报告
+pub fn load_report(id: &str) -> Result<CrashReport, Box<dyn std::error::Error>> {
+    load_report_in(&crash_dir(), id)
+}
+
+fn load_report_in(dir: &Path, id: &str) -> Result<CrashReport, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(report_path(dir, id)?)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// 导出崩溃报告到指定路径，并标记为已处理
+pub fn export_report(id: &str, export_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fs::copy(report_path(&crash_dir(), id)?, export_path)?;
+    mark_handled(id)?;
+    log::info!("📤 崩溃报告已导出到: {}", export_path);
+    Ok(())
+}
+
+/// 保留崩溃报告但不再提示
+pub fn mark_handled(id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    mark_handled_in(&crash_dir(), id)
+}
+
+fn mark_handled_in(dir: &Path, id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let path = report_path(dir, id)?;
+    let handled_dir = dir.join(HANDLED_DIR_NAME);
+    fs::create_dir_all(&handled_dir)?;
+    fs::rename(&path, handled_dir.join(format!("{}.json", id)))?;
+    Ok(())
+}
+
+/// 删除崩溃报告
+pub fn delete_report(id: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fs::remove_file(report_path(&crash_dir(), id)?)?;
+    Ok(())
+}
+
+/// 有未处理的崩溃报告时发给主窗口的事件
+pub const PENDING_EVENT: &str = "crash-report-pending";
+
+/// 主页面就绪后（前端已注册监听）如有未处理的崩溃报告则通知主窗口
+pub fn notify_pending(app_handle: AppHandle) {
+    tokio::spawn(async move {
+        if !crate::splash::wait_ready(&app_handle, None).await {
+            return;
+        }
+
+        match pending_reports() {
+            Ok(reports) if !reports.is_empty() => {
+                log::warn!("⚠️ 发现 {} 个未处理的崩溃报告", reports.len());
+                let _ = app_handle.emit_to(crate::tool_windows::MAIN_LABEL, PENDING_EVENT, &reports);
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("⚠️ 读取崩溃报告失败: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("utilx-crash-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn report(id: &str, hour: u32, message: &str) -> CrashReport {
+        use chrono::TimeZone;
+        CrashReport {
+            id: id.to_string(),
+            timestamp: chrono::Local.with_ymd_and_hms(2024, 1, 1, hour, 0, 0).single().unwrap(),
+            message: message.to_string(),
+            location: Some("src/main.rs:1:1".to_string()),
+            thread: Some("main".to_string()),
+            backtrace: String::new(),
+            app: serde_json::json!({ "version": "1.0.0" }),
+            os: OsInfo::collect_static(),
+            log_tail: vec!["last line".to_string()],
+        }
+    }
+
+    #[test]
+    fn written_reports_can_be_listed_and_loaded() {
+        let dir = temp_dir("roundtrip");
+        write_report(&dir, &report("crash-20240101-010000-1", 1, "first")).unwrap();
+        write_report(&dir, &report("crash-20240101-020000-2", 2, "second")).unwrap();
+        fs::write(dir.join("notes.json"), "{}").unwrap();
+        fs::write(dir.join("crash-broken.json"), "not json").unwrap();
+
+        let pending = pending_reports_in(&dir).unwrap();
+        let messages: Vec<&str> = pending.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(messages, vec!["second", "first"]);
+
+        let loaded = load_report_in(&dir, "crash-20240101-010000-1").unwrap();
+        assert_eq!(loaded.message, "first");
+        assert_eq!(loaded.app["version"], "1.0.0");
+        assert_eq!(loaded.log_tail, vec!["last line"]);
+
+        mark_handled_in(&dir, "crash-20240101-020000-2").unwrap();
+        assert_eq!(pending_reports_in(&dir).unwrap().len(), 1);
+        assert!(dir.join(HANDLED_DIR_NAME).join("crash-20240101-020000-2.json").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn report_ids_cannot_escape_the_crash_dir() {
+        let dir = temp_dir("ids");
+        write_report(&dir, &report("crash-20240101-010000-1", 1, "first")).unwrap();
+
+        assert!(load_report_in(&dir, "../crash-20240101-010000-1").is_err());
+        assert!(load_report_in(&dir, "crash-../../etc/passwd").is_err());
+        assert!(load_report_in(&dir, "other-20240101").is_err());
+        assert!(load_report_in(&dir, "crash-missing").is_err());
+        assert!(pending_reports_in(&dir.join("missing")).unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,352 @@
+// utilx:// 深层链接：打开指定工具并预填输入
+use crate::i18n;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 自定义 URL 协议
+pub const SCHEME: &str = "utilx";
+
+/// 预填输入的最大长度
+const MAX_INPUT_LEN: usize = 64 * 1024;
+
+/// 预填文件的最大大小
+const MAX_FILE_LEN: u64 = 1024 * 1024;
+
+/// 按字节计算文件哈希的工具，文件不限于文本也不限大小
+const HASH_TOOL: &str = "hash-calculator";
+
+/// 文件内容的哈希（十六进制）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileHashes {
+    pub size: u64,
+    pub md5: String,
+    pub sha1: String,
+    pub sha256: String,
+    pub sha512: String,
+    pub sha3_256: String,
+}
+
+/// 流式读取文件并计算各算法的哈希
+pub fn hash_file(path: &Path) -> Result<FileHashes, Box<dyn std::error::Error>> {
+    use sha2::Digest;
+
+    let mut file = std::fs::File::open(path)?;
+    let mut md5 = md5::Md5::new();
+    let mut sha1 = sha1::Sha1::new();
+    let mut sha256 = sha2::Sha256::new();
+    let mut sha512 = sha2::Sha512::new();
+    let mut sha3_256 = sha3::Sha3_256::new();
+    let mut size = 0u64;
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        let chunk = &buffer[..read];
+        md5.update(chunk);
+        sha1.update(chunk);
+        sha256.update(chunk);
+        sha512.update(chunk);
+        sha3_256.update(chunk);
+        size += read as u64;
+    }
+
+    Ok(FileHashes {
+        size,
+        md5: format!("{:x}", md5.finalize()),
+        sha1: format!("{:x}", sha1.finalize()),
+        sha256: format!("{:x}", sha256.finalize()),
+        sha512: format!("{:x}", sha512.finalize()),
+        sha3_256: format!("{:x}", sha3_256.finalize()),
+    })
+}
+
+/// 解析后的深层链接
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeepLink {
+    /// 工具 ID
+    pub tool: String,
+    /// 预填的文本输入
+    pub input: Option<String>,
+    /// 预填的文件路径
+    pub file: Option<String>,
+    /// 预填文件的文本内容（处理链接时由 `load_file` 读取）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_content: Option<String>,
+    /// 哈希计算器打开的文件的哈希（由 `load_file` 按字节计算）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_hashes: Option<FileHashes>,
+    /// 等待用户确认读取文件时的确认 ID，前端确认后调用 `confirm_deep_link_file`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirm_id: Option<String>,
+}
+
+impl DeepLink {
+    /// 读取 `file` 参数指定的文件：哈希计算器按字节计算哈希，其他工具读取文本用于预填输入
+    pub fn load_file(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if self.tool == HASH_TOOL {
+            self.file_hashes = Some(hash_file(Path::new(file))?);
+            return Ok(());
+        }
+        if std::fs::metadata(file)?.len() > MAX_FILE_LEN {
+            return Err(i18n::tf("deep_link.file_too_large", &[("path", &file)]).into());
+        }
+        let content = std::fs::read(file)?;
+        let content = String::from_utf8(content).map_err(|_| i18n::tf("deep_link.not_text_file", &[("path", &file)]))?;
+        self.file_content = Some(content);
+        Ok(())
+    }
+}
+
+/// 深层链接状态：等待页面读取的链接，以及等待用户确认读取文件的链接
+#[derive(Default)]
+pub struct DeepLinkState {
+    pending: Mutex<Option<DeepLink>>,
+    confirmations: Mutex<HashMap<String, DeepLink>>,
+}
+
+/// 解析并校验深层链接，例如 `utilx://tool/json-formatter?input=...`
+pub fn parse(raw: &str) -> Result<DeepLink, Box<dyn std::error::Error>> {
+    let url = url::Url::parse(raw)?;
+    if url.scheme() != SCHEME {
+        return Err(i18n::tf("deep_link.unsupported_scheme", &[("scheme", &url.scheme())]).into());
+    }
+    if url.host_str() != Some("tool") {
+        return Err(i18n::tf("deep_link.unsupported_type", &[("kind", &url.host_str().unwrap_or_default())]).into());
+    }
+
+    let tool = url
+        .path_segments()
+        .and_then(|mut segments| segments.find(|s| !s.is_empty()))
+        .ok_or_else(|| i18n::t("deep_link.missing_tool"))?
+        .to_string();
+    if crate::routes::find_tool(&tool).is_none() {
+        return Err(i18n::tf("deep_link.unknown_tool", &[("tool", &tool)]).into());
+    }
+
+    let mut link = DeepLink { tool, input: None, file: None, file_content: None, file_hashes: None, confirm_id: None };
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "input" => {
+                if value.len() > MAX_INPUT_LEN {
+                    return Err(i18n::t("deep_link.input_too_long").into());
+                }
+                link.input = Some(value.into_owned());
+            }
+            "file" => {
+                let path = Path::new(value.as_ref());
+                if !path.is_absolute() || !path.is_file() {
+                    return Err(i18n::tf("deep_link.file_not_found", &[("path", &value)]).into());
+                }
+                link.file = Some(value.into_owned());
+            }
+            other => log::warn!("⚠️ 忽略未知的深层链接参数: {}", other),
+        }
+    }
+
+    Ok(link)
+}
+
+/// 处理深层链接：保存待读取的内容，通知前端并跳转到对应工具
+///
+/// 链接来自外部，带 `file` 参数时只发送确认 ID，页面征得用户同意后调用 `confirm_file` 才读取文件
+pub fn handle(app_handle: &AppHandle, raw: &str) {
+    let mut link = match parse(raw) {
+        Ok(link) => link,
+        Err(e) => {
+            log::warn!("⚠️ 无效的深层链接 {}: {}", raw, e);
+            return;
+        }
+    };
+
+    if let Some(file) = &link.file {
+        let id = random_id();
+        link.confirm_id = Some(id.clone());
+        if let Some(state) = app_handle.try_state::<DeepLinkState>() {
+            state.confirmations.lock().unwrap().insert(id, link.clone());
+        }
+        log::info!("❓ 深层链接要读取本地文件，等待确认: {}", file);
+    }
+
+    log::info!("🔗 打开深层链接: 工具={}", link.tool);
+    deliver(app_handle, link);
+}
+
+/// 处理用户对读取文件的确认；允许时读取文件并返回带有文件内容的链接
+pub fn confirm_file(app_handle: &AppHandle, id: &str, allow: bool) -> Result<Option<DeepLink>, Box<dyn std::error::Error>> {
+    let mut link = app_handle
+        .state::<DeepLinkState>()
+        .confirmations
+        .lock()
+        .unwrap()
+        .remove(id)
+        .ok_or_else(|| i18n::t("deep_link.not_pending"))?;
+
+    if !allow {
+        log::info!("🚫 用户拒绝深层链接读取文件: {}", link.file.as_deref().unwrap_or_default());
+        return Ok(None);
+    }
+
+    link.confirm_id = None;
+    link.load_file()?;
+    log::info!("📂 深层链接读取文件: {} (工具={})", link.file.as_deref().unwrap_or_default(), link.tool);
+    Ok(Some(link))
+}
+
+fn random_id() -> String {
+    use chacha20poly1305::aead::rand_core::RngCore;
+    let mut bytes = [0u8; 8];
+    chacha20poly1305::aead::OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// 打开文件时默认使用的工具：JSON 文件用 JSON 格式化，其他文件计算哈希
+fn default_tool_for_file(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("json") => "json-formatter",
+        _ => "hash-calculator",
+    }
+}
+
+/// 用工具打开文件（例如其他实例转发的文件参数），与 `file=` 深层链接相同但不需要确认；未指定工具时按扩展名选择
+pub fn open_file(app_handle: &AppHandle, tool: Option<&str>, path: &Path) {
+    let tool = tool.unwrap_or_else(|| default_tool_for_file(path));
+    if crate::routes::find_tool(tool).is_none() {
+        log::warn!("⚠️ 未知的工具: {}", tool);
+        return;
+    }
+
+    let mut link = DeepLink {
+        tool: tool.to_string(),
+        input: None,
+        file: Some(path.to_string_lossy().to_string()),
+        file_content: None,
+        file_hashes: None,
+        confirm_id: None,
+    };
+    if let Err(e) = link.load_file() {
+        log::warn!("⚠️ 无法打开文件 {}: {}", path.display(), e);
+        return;
+    }
+
+    log::info!("📂 打开文件: {} (工具={})", path.display(), link.tool);
+    deliver(app_handle, link);
+}
+
+/// 保存待页面读取的内容，通知前端并跳转到对应工具
+fn deliver(app_handle: &AppHandle, link: DeepLink) {
+    if let Some(state) = app_handle.try_state::<DeepLinkState>() {
+        if let Ok(mut pending) = state.pending.lock() {
+            *pending = Some(link.clone());
+        }
+    }
+
+    let _ = app_handle.emit("deep-link", &link);
+    if let Err(e) = crate::routes::navigate(app_handle, &crate::routes::Route::Tool(link.tool.clone())) {
+        log::warn!("⚠️ 打开深层链接的工具失败: {}", e);
+    }
+}
+
+/// 页面读取并清除待处理的深层链接；指定工具时只返回该工具的内容
+pub fn take_pending(app_handle: &AppHandle, tool: Option<&str>) -> Option<DeepLink> {
+    let state = app_handle.try_state::<DeepLinkState>()?;
+    let mut pending = state.pending.lock().ok()?;
+    match (pending.as_ref(), tool) {
+        (Some(link), Some(tool)) if link.tool != tool => None,
+        _ => pending.take(),
+    }
+}
+
+/// 是否为本应用的深层链接参数
+pub fn is_deep_link(arg: &str) -> bool {
+    arg.starts_with(&format!("{}://", SCHEME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_known_tool_with_input() {
+        let link = parse("utilx://tool/json-formatter?input=%7B%22a%22%3A1%7D").unwrap();
+        assert_eq!(link.tool, "json-formatter");
+        assert_eq!(link.input.as_deref(), Some(r#"{"a":1}"#));
+        assert_eq!(link.file, None);
+    }
+
+    #[test]
+    fn parse_rejects_other_schemes_and_kinds() {
+        assert!(parse("https://tool/json-formatter").is_err());
+        assert!(parse("utilx://page/home").is_err());
+        assert!(parse("not a url").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_missing_or_unknown_tool() {
+        assert!(parse("utilx://tool/").is_err());
+        assert!(parse("utilx://tool/no-such-tool").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_oversized_input() {
+        let input = "a".repeat(MAX_INPUT_LEN + 1);
+        assert!(parse(&format!("utilx://tool/json-formatter?input={}", input)).is_err());
+    }
+
+    #[test]
+    fn parse_requires_existing_absolute_file() {
+        assert!(parse("utilx://tool/json-formatter?file=relative.json").is_err());
+
+        let path = std::env::temp_dir().join(format!("utilx-deep-link-{}.json", std::process::id()));
+        std::fs::write(&path, "{}").unwrap();
+        let raw = url::Url::parse_with_params("utilx://tool/json-formatter", [("file", path.to_string_lossy())]).unwrap();
+        let link = parse(raw.as_str());
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(link.unwrap().file.as_deref(), Some(path.to_string_lossy().as_ref()));
+    }
+
+    #[test]
+    fn hash_file_hashes_binary_bytes() {
+        let path = std::env::temp_dir().join(format!("utilx-deep-link-{}.bin", std::process::id()));
+        std::fs::write(&path, b"abc").unwrap();
+        let hashes = hash_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        let hashes = hashes.unwrap();
+        assert_eq!(hashes.size, 3);
+        assert_eq!(hashes.md5, "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(hashes.sha1, "a9993e364706816aba3e25717850c26c9cd0d89d");
+        assert_eq!(hashes.sha256, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(hashes.sha3_256, "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532");
+    }
+
+    #[test]
+    fn load_file_hashes_non_utf8_files_for_hash_tool() {
+        let path = std::env::temp_dir().join(format!("utilx-deep-link-{}.dat", std::process::id()));
+        std::fs::write(&path, [0xffu8, 0xfe, 0x00]).unwrap();
+        let mut link = DeepLink {
+            tool: HASH_TOOL.to_string(),
+            input: None,
+            file: Some(path.to_string_lossy().to_string()),
+            file_content: None,
+            file_hashes: None,
+            confirm_id: None,
+        };
+        let result = link.load_file();
+        let mut text_link = DeepLink { tool: "json-formatter".to_string(), file_hashes: None, ..link.clone() };
+        let text_result = text_link.load_file();
+        std::fs::remove_file(&path).unwrap();
+
+        result.unwrap();
+        assert_eq!(link.file_hashes.map(|h| h.size), Some(3));
+        assert_eq!(link.file_content, None);
+        assert!(text_result.is_err());
+    }
+}
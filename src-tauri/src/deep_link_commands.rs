@@ -0,0 +1,23 @@
+use crate::deep_link::{self, DeepLink};
+use crate::i18n;
+use tauri::{AppHandle, command};
+
+/// 读取并清除待处理的深层链接（页面加载后调用）
+#[command]
+pub async fn take_deep_link(app_handle: AppHandle, tool: Option<String>) -> Result<Option<DeepLink>, String> {
+    Ok(deep_link::take_pending(&app_handle, tool.as_deref()))
+}
+
+/// 解析深层链接（用于前端预览或校验）
+#[command]
+pub async fn parse_deep_link(url: String) -> Result<DeepLink, String> {
+    deep_link::parse(&url)
+        .map_err(|e| i18n::tf("deep_link.parse_failed", &[("error", &e)]))
+}
+
+/// 确认或拒绝深层链接读取本地文件，允许时返回带有文件内容的链接
+#[command]
+pub async fn confirm_deep_link_file(app_handle: AppHandle, id: String, allow: bool) -> Result<Option<DeepLink>, String> {
+    deep_link::confirm_file(&app_handle, &id, allow)
+        .map_err(|e| i18n::tf("deep_link.file_read_failed", &[("error", &e)]))
+}
@@ -0,0 +1,330 @@
+// 全局快捷键：在不打开窗口的情况下处理剪贴板或打开工具，绑定保存在 user-settings.json 的 shortcuts 中
+use crate::i18n;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::str::FromStr;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+/// 快捷键执行的操作
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ShortcutAction {
+    /// 计算剪贴板文本的 SHA-256，结果写回剪贴板
+    HashClipboard,
+    /// 格式化剪贴板中的 JSON
+    FormatJsonClipboard,
+    /// 生成 UUID v4 并写入剪贴板
+    GenerateUuid,
+    /// 显示主窗口并打开指定工具
+    OpenTool { tool: String },
+    /// 显示主窗口
+    ShowWindow,
+    /// 打开或关闭迷你模式
+    ToggleMiniMode,
+    /// 切换迷你模式的鼠标穿透
+    ToggleMiniClickThrough,
+}
+
+/// 一条快捷键绑定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutBinding {
+    /// 组合键，例如 `CmdOrCtrl+Alt+H`
+    pub accelerator: String,
+    pub action: ShortcutAction,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// 快捷键冲突或无效的绑定
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortcutConflict {
+    pub accelerator: String,
+    pub reason: String,
+}
+
+/// 注册结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ShortcutStatus {
+    /// 已成功注册的组合键
+    pub registered: Vec<String>,
+    /// 未能注册的绑定
+    pub conflicts: Vec<ShortcutConflict>,
+}
+
+/// 默认绑定
+pub fn default_bindings() -> Vec<ShortcutBinding> {
+    vec![
+        ShortcutBinding {
+            accelerator: "CmdOrCtrl+Alt+H".to_string(),
+            action: ShortcutAction::HashClipboard,
+            enabled: true,
+        },
+        ShortcutBinding {
+            accelerator: "CmdOrCtrl+Alt+J".to_string(),
+            action: ShortcutAction::FormatJsonClipboard,
+            enabled: true,
+        },
+        ShortcutBinding {
+            accelerator: "CmdOrCtrl+Alt+U".to_string(),
+            action: ShortcutAction::GenerateUuid,
+            enabled: true,
+        },
+        ShortcutBinding {
+            accelerator: "CmdOrCtrl+Alt+M".to_string(),
+            action: ShortcutAction::ToggleMiniMode,
+            enabled: true,
+        },
+    ]
+}
+
+/// 读取当前生效的用户设置
+fn load_user_settings(app_handle: &AppHandle) -> serde_json::Value {
+    let profile = crate::config_profiles::active_profile(app_handle);
+    crate::config_profiles::read_effective(app_handle, profile.as_deref(), "user-settings.json")
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| serde_json::json!({}))
+}
+
+/// 读取快捷键绑定；未配置时使用默认绑定
+pub fn load_bindings(app_handle: &AppHandle) -> Vec<ShortcutBinding> {
+    match load_user_settings(app_handle).get("shortcuts") {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or_else(|e| {
+            log::warn!("⚠️ 快捷键设置无效，使用默认绑定: {}", e);
+            default_bindings()
+        }),
+        None => default_bindings(),
+    }
+}
+
+/// 保存快捷键绑定到用户设置
+pub fn save_bindings(app_handle: &AppHandle, bindings: &[ShortcutBinding]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut settings = load_user_settings(app_handle);
+    if !settings.is_object() {
+        settings = serde_json::json!({});
+    }
+    settings["shortcuts"] = serde_json::to_value(bindings)?;
+    crate::config_profiles::write_effective(app_handle, "user-settings.json", &settings)
+}
+
+/// 检查绑定：组合键格式、打开的工具是否存在，以及启用的绑定之间是否重复
+pub fn validate(bindings: &[ShortcutBinding]) -> Vec<ShortcutConflict> {
+    check(bindings).into_iter().map(|(_, conflict)| conflict).collect()
+}
+
+/// 返回有问题的绑定序号及原因
+fn check(bindings: &[ShortcutBinding]) -> Vec<(usize, ShortcutConflict)> {
+    let mut conflicts = Vec::new();
+    let mut seen: HashMap<u32, &str> = HashMap::new();
+
+    for (index, binding) in bindings.iter().enumerate().filter(|(_, b)| b.enabled) {
+        let conflict = |reason: String| (index, ShortcutConflict { accelerator: binding.accelerator.clone(), reason });
+
+        let shortcut = match Shortcut::from_str(&binding.accelerator) {
+            Ok(shortcut) => shortcut,
+            Err(e) => {
+                conflicts.push(conflict(i18n::tf("shortcuts.invalid_accelerator", &[("error", &e)])));
+                continue;
+            }
+        };
+
+        if let ShortcutAction::OpenTool { tool } = &binding.action {
+            if crate::routes::find_tool(tool).is_none() {
+                conflicts.push(conflict(i18n::tf("tools.unknown", &[("tool", tool)])));
+                continue;
+            }
+        }
+
+        // 同一组合键的不同写法（如 Ctrl+Alt+H 与 Alt+Control+H）id 相同
+        if let Some(previous) = seen.insert(shortcut.id(), &binding.accelerator) {
+            conflicts.push(conflict(i18n::tf("shortcuts.duplicate", &[("accelerator", &previous)])));
+        }
+    }
+
+    conflicts
+}
+
+/// 按当前设置重新注册所有快捷键
+pub fn apply(app_handle: &AppHandle) -> Result<ShortcutStatus, Box<dyn std::error::Error>> {
+    let global_shortcut = app_handle.global_shortcut();
+    global_shortcut.unregister_all()?;
+
+    let bindings = load_bindings(app_handle);
+    let (invalid, conflicts): (Vec<usize>, Vec<ShortcutConflict>) = check(&bindings).into_iter().unzip();
+    let mut status = ShortcutStatus { registered: Vec::new(), conflicts };
+
+    for (index, binding) in bindings.iter().enumerate().filter(|(_, b)| b.enabled) {
+        if invalid.contains(&index) {
+            continue;
+        }
+
+        let action = binding.action.clone();
+        let result = global_shortcut.on_shortcut(binding.accelerator.as_str(), move |app, _shortcut, event| {
+            if event.state == ShortcutState::Pressed {
+                run_action(app, &action);
+            }
+        });
+
+        match result {
+            Ok(()) => status.registered.push(binding.accelerator.clone()),
+            Err(e) => {
+                log::warn!("⚠️ 注册快捷键 {} 失败: {}", binding.accelerator, e);
+                status.conflicts.push(ShortcutConflict {
+                    accelerator: binding.accelerator.clone(),
+                    reason: i18n::tf("shortcuts.in_use", &[("error", &e)]),
+                });
+            }
+        }
+    }
+
+    log::info!("⌨️ 已注册 {} 个全局快捷键，{} 个冲突", status.registered.len(), status.conflicts.len());
+    Ok(status)
+}
+
+/// 重新注册快捷键，失败时只记录日志（设置或配置方案变化后调用）
+pub fn reload(app_handle: &AppHandle) {
+    if let Err(e) = apply(app_handle) {
+        log::warn!("⚠️ 注册全局快捷键失败: {}", e);
+    }
+}
+
+/// 执行快捷键操作并通过系统通知告知结果
+fn run_action(app_handle: &AppHandle, action: &ShortcutAction) {
+    log::info!("⌨️ 执行快捷键操作: {:?}", action);
+
+    let (title, body) = match execute(app_handle, action) {
+        Ok(Some(message)) => (i18n::t("app.name"), message),
+        Ok(None) => return,
+        Err(e) => {
+            log::warn!("⚠️ 快捷键操作失败: {}", e);
+            (i18n::t("shortcuts.action_failed_title"), e.to_string())
+        }
+    };
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = crate::send_system_notification(title, body, app_handle).await;
+    });
+}
+
+/// 执行操作，返回需要通知的内容
+fn execute(app_handle: &AppHandle, action: &ShortcutAction) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    match action {
+        ShortcutAction::HashClipboard => {
+            let text = read_clipboard_text(app_handle)?;
+            let hash = format!("{:x}", Sha256::digest(text.as_bytes()));
+            app_handle.clipboard().write_text(hash.clone())?;
+            Ok(Some(i18n::tf("shortcuts.hash_copied", &[("hash", &hash)])))
+        }
+        ShortcutAction::FormatJsonClipboard => {
+            let text = read_clipboard_text(app_handle)?;
+            let value: serde_json::Value = serde_json::from_str(&text)
+                .map_err(|e| i18n::tf("shortcuts.invalid_json", &[("error", &e)]))?;
+            app_handle.clipboard().write_text(serde_json::to_string_pretty(&value)?)?;
+            Ok(Some(i18n::t("shortcuts.json_formatted")))
+        }
+        ShortcutAction::GenerateUuid => {
+            let uuid = uuid_v4();
+            app_handle.clipboard().write_text(uuid.clone())?;
+            Ok(Some(i18n::tf("shortcuts.uuid_copied", &[("uuid", &uuid)])))
+        }
+        ShortcutAction::OpenTool { tool } => {
+            crate::routes::navigate(app_handle, &crate::routes::Route::Tool(tool.clone()))?;
+            Ok(None)
+        }
+        ShortcutAction::ShowWindow => {
+            crate::tray::show_main_window(app_handle);
+            Ok(None)
+        }
+        ShortcutAction::ToggleMiniMode => {
+            crate::mini_mode::toggle(app_handle)?;
+            Ok(None)
+        }
+        ShortcutAction::ToggleMiniClickThrough => {
+            let enabled = crate::mini_mode::toggle_click_through(app_handle)?;
+            Ok(Some(i18n::t(if enabled { "mini.click_through_on" } else { "mini.click_through_off" })))
+        }
+    }
+}
+
+fn read_clipboard_text(app_handle: &AppHandle) -> Result<String, Box<dyn std::error::Error>> {
+    let text = app_handle.clipboard().read_text()?;
+    if text.is_empty() {
+        return Err(i18n::t("shortcuts.clipboard_empty").into());
+    }
+    Ok(text)
+}
+
+/// 生成随机 UUID（版本 4）
+fn uuid_v4() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(accelerator: &str, action: ShortcutAction) -> ShortcutBinding {
+        ShortcutBinding { accelerator: accelerator.to_string(), action, enabled: true }
+    }
+
+    #[test]
+    fn check_accepts_default_bindings() {
+        assert!(check(&default_bindings()).is_empty());
+    }
+
+    #[test]
+    fn check_reports_invalid_accelerators() {
+        let bindings = [binding("Ctrl+Nope+H", ShortcutAction::GenerateUuid)];
+        let conflicts = check(&bindings);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].0, 0);
+        assert_eq!(conflicts[0].1.accelerator, "Ctrl+Nope+H");
+    }
+
+    #[test]
+    fn check_reports_unknown_tools() {
+        let bindings = [
+            binding("Ctrl+Shift+1", ShortcutAction::OpenTool { tool: "json-formatter".to_string() }),
+            binding("Ctrl+Shift+2", ShortcutAction::OpenTool { tool: "no-such-tool".to_string() }),
+        ];
+        let indexes: Vec<usize> = check(&bindings).into_iter().map(|(index, _)| index).collect();
+        assert_eq!(indexes, vec![1]);
+    }
+
+    #[test]
+    fn check_reports_duplicates_written_differently() {
+        let bindings = [
+            binding("Ctrl+Shift+K", ShortcutAction::GenerateUuid),
+            binding("Shift+Ctrl+K", ShortcutAction::ShowWindow),
+        ];
+        let conflicts = check(&bindings);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].0, 1);
+    }
+
+    #[test]
+    fn check_ignores_disabled_bindings() {
+        let mut duplicate = binding("Ctrl+Shift+K", ShortcutAction::ShowWindow);
+        duplicate.enabled = false;
+        let mut invalid = binding("not a shortcut", ShortcutAction::ShowWindow);
+        invalid.enabled = false;
+        let bindings = [binding("Ctrl+Shift+K", ShortcutAction::GenerateUuid), duplicate, invalid];
+        assert!(check(&bindings).is_empty());
+    }
+}
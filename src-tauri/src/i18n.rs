@@ -0,0 +1,140 @@
+// 后端文案的多语言支持：消息目录按消息 ID 组织（locales/*.json），语言由 user-settings.json 的 language 设置决定
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{OnceLock, RwLock};
+use tauri::AppHandle;
+
+/// 支持的语言
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    ZhCn,
+    EnUs,
+}
+
+impl Language {
+    /// 由 language 设置（如 `zh-CN`、`en-US`）得到语言，未知语言使用中文
+    pub fn from_setting(language: Option<&str>) -> Self {
+        match language {
+            Some(lang) if lang.to_ascii_lowercase().starts_with("en") => Language::EnUs,
+            _ => Language::ZhCn,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::ZhCn => "zh-CN",
+            Language::EnUs => "en-US",
+        }
+    }
+}
+
+/// 消息 ID 到文案的映射
+type Catalog = HashMap<String, String>;
+
+/// 当前语言
+static CURRENT: RwLock<Language> = RwLock::new(Language::ZhCn);
+
+fn parse_catalog(language: Language, content: &str) -> Catalog {
+    serde_json::from_str(content).unwrap_or_else(|e| {
+        log::error!("❌ 解析内置消息目录 {} 失败: {}", language.code(), e);
+        Catalog::default()
+    })
+}
+
+/// 内置消息目录
+fn catalog(language: Language) -> &'static Catalog {
+    static ZH_CN: OnceLock<Catalog> = OnceLock::new();
+    static EN_US: OnceLock<Catalog> = OnceLock::new();
+    match language {
+        Language::ZhCn => ZH_CN.get_or_init(|| parse_catalog(language, include_str!("../locales/zh-CN.json"))),
+        Language::EnUs => EN_US.get_or_init(|| parse_catalog(language, include_str!("../locales/en-US.json"))),
+    }
+}
+
+/// 当前语言
+pub fn language() -> Language {
+    *CURRENT.read().unwrap()
+}
+
+/// 按 user-settings.json 的 language 设置切换语言（启动时以及设置或配置方案变化后调用）
+pub fn reload(app_handle: &AppHandle) {
+    let profile = crate::config_profiles::active_profile(app_handle);
+    let settings = crate::config_profiles::read_effective(app_handle, profile.as_deref(), "user-settings.json")
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    let language = Language::from_setting(settings.get("language").and_then(|v| v.as_str()));
+
+    let previous = std::mem::replace(&mut *CURRENT.write().unwrap(), language);
+    if previous != language {
+        log::info!("🌐 后端语言: {}", language.code());
+    }
+}
+
+/// 按语言查找文案；缺少翻译时使用中文，都没有时返回消息 ID
+pub fn text(language: Language, id: &str) -> String {
+    catalog(language)
+        .get(id)
+        .or_else(|| catalog(Language::ZhCn).get(id))
+        .cloned()
+        .unwrap_or_else(|| {
+            log::warn!("⚠️ 缺少消息: {}", id);
+            id.to_string()
+        })
+}
+
+/// 当前语言的文案
+pub fn t(id: &str) -> String {
+    text(language(), id)
+}
+
+/// 当前语言的文案，并把 `{名称}` 替换为参数
+pub fn tf(id: &str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(t(id), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tf_replaces_named_placeholders() {
+        let message = tf("crash.not_found", &[("id", &"crash-1")]);
+        assert!(message.contains("crash-1"));
+        assert!(!message.contains("{id}"));
+    }
+
+    #[test]
+    fn tf_leaves_unknown_placeholders_and_ignores_extra_args() {
+        let template = t("crash.not_found");
+        assert_eq!(tf("crash.not_found", &[]), template);
+        assert_eq!(tf("crash.not_found", &[("other", &1)]), template);
+    }
+
+    #[test]
+    fn missing_messages_fall_back_to_id() {
+        assert_eq!(tf("no.such.message", &[("id", &1)]), "no.such.message");
+    }
+
+    #[test]
+    fn catalogs_have_the_same_messages() {
+        let zh = catalog(Language::ZhCn);
+        let en = catalog(Language::EnUs);
+        assert!(!zh.is_empty());
+        for id in zh.keys() {
+            assert!(en.contains_key(id), "英文消息目录缺少 {}", id);
+        }
+    }
+
+    #[test]
+    fn from_setting_defaults_to_chinese() {
+        assert_eq!(Language::from_setting(Some("en-US")), Language::EnUs);
+        assert_eq!(Language::from_setting(Some("EN")), Language::EnUs);
+        assert_eq!(Language::from_setting(Some("zh-CN")), Language::ZhCn);
+        assert_eq!(Language::from_setting(Some("fr")), Language::ZhCn);
+        assert_eq!(Language::from_setting(None), Language::ZhCn);
+    }
+}
@@ -0,0 +1,25 @@
+use crate::external_links::{self, OpenOutcome};
+use crate::i18n;
+use tauri::{AppHandle, command};
+
+/// 打开外部链接（只允许 http、https 和 mailto，未知主机需要确认）
+#[command]
+pub async fn open_external_link(app_handle: AppHandle, url: String) -> Result<OpenOutcome, String> {
+    external_links::open(&app_handle, &url)
+        .map_err(|e| {
+            log::warn!("⚠️ 拒绝打开外部链接 {}: {}", url, e);
+            i18n::tf("link.open_failed", &[("error", &e)])
+        })
+}
+
+/// 确认或取消打开未知主机的链接，返回实际打开的链接
+#[command]
+pub async fn confirm_external_link(
+    app_handle: AppHandle,
+    id: String,
+    allow: bool,
+    remember: Option<bool>,
+) -> Result<Option<String>, String> {
+    external_links::confirm(&app_handle, &id, allow, remember.unwrap_or(false))
+        .map_err(|e| i18n::tf("link.open_failed", &[("error", &e)]))
+}
@@ -0,0 +1,40 @@
+use crate::i18n;
+use crate::logging::{self, LogConfig};
+use tauri::{AppHandle, command};
+
+/// 获取日志配置
+#[command]
+pub async fn get_log_config() -> Result<LogConfig, String> {
+    Ok(logging::current_config())
+}
+
+/// 保存日志配置并立即生效
+#[command]
+pub async fn save_log_config(app_handle: AppHandle, config: LogConfig) -> Result<String, String> {
+    config.save(&app_handle)
+        .map_err(|e| i18n::tf("log.save_config_failed", &[("error", &e)]))?;
+    logging::apply_config(config);
+    Ok(i18n::t("log.config_saved"))
+}
+
+/// 运行时调整日志级别；指定 target 时只调整该模块
+#[command]
+pub async fn set_log_level(app_handle: AppHandle, level: String, target: Option<String>) -> Result<String, String> {
+    logging::parse_level(&level)
+        .map_err(|e| i18n::tf("log.set_level_failed", &[("error", &e)]))?;
+
+    let mut config = logging::current_config();
+    match &target {
+        Some(target) => {
+            config.targets.insert(target.clone(), level.clone());
+        }
+        None => config.level = level.clone(),
+    }
+
+    config.save(&app_handle)
+        .map_err(|e| i18n::tf("log.save_config_failed", &[("error", &e)]))?;
+    logging::apply_config(config);
+
+    log::info!("📝 日志级别已调整: {} = {}", target.as_deref().unwrap_or("默认"), level);
+    Ok(i18n::tf("log.level_set", &[("level", &level)]))
+}
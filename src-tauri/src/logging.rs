@@ -0,0 +1,546 @@
+// 日志系统：分级、按模块过滤，纯文本或 JSON 输出，按天或按大小轮转
+use crate::i18n;
+use log::{LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+use tauri::AppHandle;
+
+/// 日志配置文件名
+const LOG_CONFIG_FILE: &str = "log-config.json";
+
+/// 当前日志文件名，轮转后重命名为 `utilx-<时间>.log`
+pub const LOG_FILE_NAME: &str = "utilx.log";
+
+/// 轮转后日志文件的前缀
+const ROTATED_PREFIX: &str = "utilx-";
+
+/// 前端日志使用的模块名
+pub const FRONTEND_TARGET: &str = "frontend";
+
+/// 日志输出格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Plain,
+    Json,
+}
+
+/// 日志轮转方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    /// 每天一个文件
+    #[default]
+    Daily,
+    /// 超过 `max_file_size_mb` 时轮转
+    Size,
+}
+
+/// 日志配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogConfig {
+    /// 默认日志级别：off/error/warn/info/debug/trace
+    pub level: String,
+    /// 按模块覆盖日志级别，例如 `{"frontend": "debug", "utilx::updater": "trace"}`
+    pub targets: HashMap<String, String>,
+    pub format: LogFormat,
+    pub rotation: LogRotation,
+    /// 单个日志文件最大大小（MB），按大小轮转时生效
+    pub max_file_size_mb: u64,
+    /// 轮转后的日志保留天数
+    pub retention_days: u32,
+    /// 轮转后的日志最多保留数量
+    pub max_files: usize,
+    /// 是否同时输出到控制台
+    pub console: bool,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            targets: HashMap::new(),
+            format: LogFormat::Plain,
+            rotation: LogRotation::Daily,
+            max_file_size_mb: 10,
+            retention_days: 7,
+            max_files: 20,
+            console: true,
+        }
+    }
+}
+
+impl LogConfig {
+    /// 从文件加载日志配置
+    pub fn load(app_handle: &AppHandle) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_path = crate::app_paths::config_dir(app_handle)?.join(LOG_CONFIG_FILE);
+
+        if config_path.exists() {
+            let content = fs::read_to_string(&config_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(LogConfig::default())
+        }
+    }
+
+    /// 保存日志配置到文件
+    pub fn save(&self, app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        self.validate()?;
+        let config_dir = crate::app_paths::config_dir(app_handle)?;
+        fs::create_dir_all(&config_dir)?;
+        fs::write(config_dir.join(LOG_CONFIG_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 检查日志级别是否有效
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        parse_level(&self.level)?;
+        for level in self.targets.values() {
+            parse_level(level)?;
+        }
+        Ok(())
+    }
+
+    /// 所有模块中最详细的级别，用作全局过滤
+    fn max_level(&self) -> LevelFilter {
+        self.targets
+            .values()
+            .filter_map(|level| parse_level(level).ok())
+            .fold(parse_level(&self.level).unwrap_or(LevelFilter::Info), |a, b| a.max(b))
+    }
+
+    /// 某个模块生效的级别：取最长匹配的模块前缀
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .filter(|(prefix, _)| target == prefix.as_str() || target.starts_with(&format!("{}::", prefix)))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .and_then(|(_, level)| parse_level(level).ok())
+            .unwrap_or_else(|| parse_level(&self.level).unwrap_or(LevelFilter::Info))
+    }
+}
+
+/// 解析日志级别
+pub fn parse_level(level: &str) -> Result<LevelFilter, Box<dyn std::error::Error>> {
+    LevelFilter::from_str(level.trim()).map_err(|_| i18n::tf("log.invalid_level", &[("level", &level)]).into())
+}
+
+/// 已打开的日志文件
+struct FileSink {
+    dir: PathBuf,
+    file: File,
+    size: u64,
+    opened_on: chrono::NaiveDate,
+}
+
+impl FileSink {
+    fn open(dir: &Path) -> std::io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(LOG_FILE_NAME);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let metadata = file.metadata()?;
+        // 使用文件最后修改日期，保证跨天启动时也能正确轮转
+        let opened_on = metadata
+            .modified()
+            .map(|time| chrono::DateTime::<chrono::Local>::from(time).date_naive())
+            .unwrap_or_else(|_| chrono::Local::now().date_naive());
+
+        Ok(Self { dir: dir.to_path_buf(), file, size: metadata.len(), opened_on })
+    }
+
+    fn needs_rotation(&self, config: &LogConfig, incoming: usize) -> bool {
+        match config.rotation {
+            LogRotation::Daily => self.opened_on != chrono::Local::now().date_naive(),
+            LogRotation::Size => {
+                self.size > 0 && self.size + incoming as u64 > config.max_file_size_mb.max(1) * 1024 * 1024
+            }
+        }
+    }
+
+    /// 将当前文件重命名为带时间的文件并重新打开
+    fn rotate(&mut self, config: &LogConfig) -> std::io::Result<()> {
+        let stamp = match config.rotation {
+            LogRotation::Daily => self.opened_on.format("%Y-%m-%d").to_string(),
+            LogRotation::Size => chrono::Local::now().format("%Y-%m-%d-%H%M%S").to_string(),
+        };
+        let mut rotated = self.dir.join(format!("{}{}.log", ROTATED_PREFIX, stamp));
+        let mut index = 1;
+        while rotated.exists() {
+            rotated = self.dir.join(format!("{}{}.{}.log", ROTATED_PREFIX, stamp, index));
+            index += 1;
+        }
+
+        fs::rename(self.dir.join(LOG_FILE_NAME), &rotated)?;
+        *self = FileSink::open(&self.dir)?;
+        cleanup(&self.dir, config);
+        Ok(())
+    }
+}
+
+/// 按保留天数和数量清理轮转后的日志
+fn cleanup(dir: &Path, config: &LogConfig) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut rotated: Vec<(PathBuf, std::time::SystemTime)> = entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with(ROTATED_PREFIX) && name.ends_with(".log")
+        })
+        .filter_map(|entry| Some((entry.path(), entry.metadata().ok()?.modified().ok()?)))
+        .collect();
+    rotated.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+    let max_age = std::time::Duration::from_secs(u64::from(config.retention_days) * 24 * 60 * 60);
+    let now = std::time::SystemTime::now();
+    for (index, (path, modified)) in rotated.iter().enumerate() {
+        let expired = now.duration_since(*modified).map(|age| age > max_age).unwrap_or(false);
+        if index >= config.max_files || expired {
+            if let Err(e) = fs::remove_file(path) {
+                eprintln!("⚠️ 删除过期日志失败 {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// 应用日志器：输出到控制台和日志文件
+struct UtilxLogger {
+    config: Mutex<LogConfig>,
+    sink: Mutex<Option<FileSink>>,
+}
+
+static LOGGER: OnceLock<UtilxLogger> = OnceLock::new();
+
+/// 当前日志文件路径，写入日志文件后设置
+static LOG_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+thread_local! {
+    /// 当前线程是否正在写日志；写日志时 panic 会从 panic hook 再次进入日志器，此时不能再加锁
+    static IN_LOGGER: Cell<bool> = const { Cell::new(false) };
+}
+
+/// 标记当前线程正在写日志，离开作用域（包括 panic 展开）时清除
+struct ReentrancyGuard;
+
+impl ReentrancyGuard {
+    /// 当前线程已在日志器中时返回 None
+    fn enter() -> Option<Self> {
+        IN_LOGGER.with(|flag| (!flag.replace(true)).then(|| ReentrancyGuard))
+    }
+}
+
+impl Drop for ReentrancyGuard {
+    fn drop(&mut self) {
+        IN_LOGGER.with(|flag| flag.set(false));
+    }
+}
+
+impl UtilxLogger {
+    fn format_record(config: &LogConfig, record: &Record) -> String {
+        let timestamp = chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, false);
+        match config.format {
+            LogFormat::Plain => format!(
+                "{} {:<5} [{}] {}\n",
+                timestamp,
+                record.level(),
+                record.target(),
+                record.args()
+            ),
+            LogFormat::Json => {
+                let line = serde_json::json!({
+                    "timestamp": timestamp,
+                    "level": record.level().as_str(),
+                    "target": record.target(),
+                    "message": record.args().to_string(),
+                });
+                format!("{}\n", line)
+            }
+        }
+    }
+}
+
+impl Log for UtilxLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        if IN_LOGGER.with(Cell::get) {
+            return true;
+        }
+        self.config
+            .lock()
+            .map(|config| metadata.level() <= config.level_for(metadata.target()))
+            .unwrap_or(true)
+    }
+
+    fn log(&self, record: &Record) {
+        // 重入时（例如写日志过程中 panic）只输出到控制台，避免在已持有的锁上死锁
+        let Some(_guard) = ReentrancyGuard::enter() else {
+            eprintln!("{}", record.args());
+            return;
+        };
+
+        let Ok(config) = self.config.lock().map(|config| config.clone()) else {
+            return;
+        };
+        if record.level() > config.level_for(record.target()) {
+            return;
+        }
+
+        // 控制台保持原有的输出格式
+        if config.console {
+            eprintln!("{}", record.args());
+        }
+
+        let Ok(mut sink) = self.sink.lock() else {
+            return;
+        };
+        let Some(sink) = sink.as_mut() else {
+            return;
+        };
+
+        let line = Self::format_record(&config, record);
+        if sink.needs_rotation(&config, line.len()) {
+            if let Err(e) = sink.rotate(&config) {
+                eprintln!("⚠️ 日志轮转失败: {}", e);
+            }
+        }
+
+        match sink.file.write_all(line.as_bytes()) {
+            Ok(_) => sink.size += line.len() as u64,
+            Err(e) => eprintln!("⚠️ 写入日志文件失败: {}", e),
+        }
+    }
+
+    fn flush(&self) {
+        let Some(_guard) = ReentrancyGuard::enter() else {
+            return;
+        };
+        if let Ok(mut sink) = self.sink.lock() {
+            if let Some(sink) = sink.as_mut() {
+                let _ = sink.file.flush();
+            }
+        }
+    }
+}
+
+fn logger() -> &'static UtilxLogger {
+    LOGGER.get_or_init(|| UtilxLogger {
+        config: Mutex::new(LogConfig::default()),
+        sink: Mutex::new(None),
+    })
+}
+
+/// 安装日志器（仅控制台），应在程序启动时最先调用
+pub fn init() {
+    let logger = logger();
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(logger.config.lock().map(|c| c.max_level()).unwrap_or(LevelFilter::Info));
+    }
+}
+
+/// 加载日志配置并开始写入日志目录
+pub fn attach_file(app_handle: &AppHandle) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let config = LogConfig::load(app_handle)?;
+    let log_dir = crate::app_paths::log_dir(app_handle)?;
+    let sink = FileSink::open(&log_dir)?;
+    cleanup(&log_dir, &config);
+
+    apply_config(config);
+    *logger().sink.lock().map_err(|e| e.to_string())? = Some(sink);
+
+    let log_file = log_dir.join(LOG_FILE_NAME);
+    let _ = LOG_FILE.set(log_file.clone());
+    Ok(log_file)
+}
+
+/// 当前线程此刻写日志是否不会阻塞在日志器自己的锁上（panic hook 中先检查再写日志）
+pub fn is_available() -> bool {
+    let logger = logger();
+    !IN_LOGGER.with(Cell::get) && logger.config.try_lock().is_ok() && logger.sink.try_lock().is_ok()
+}
+
+/// 当前日志文件路径
+pub fn current_log_file() -> Option<PathBuf> {
+    LOG_FILE.get().cloned()
+}
+
+/// 日志目录中的日志文件，按修改时间从新到旧排列
+pub fn log_files(app_handle: &AppHandle) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let log_dir = crate::app_paths::log_dir(app_handle)?;
+    if !log_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files: Vec<(PathBuf, std::time::SystemTime)> = fs::read_dir(&log_dir)?
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name == LOG_FILE_NAME || (name.starts_with(ROTATED_PREFIX) && name.ends_with(".log"))
+        })
+        .filter_map(|entry| Some((entry.path(), entry.metadata().ok()?.modified().ok()?)))
+        .collect();
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+/// 读取当前日志文件的最后若干行（不加锁，可在崩溃处理时调用）
+pub fn recent_lines(max_lines: usize) -> Vec<String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    /// 最多读取的尾部字节数
+    const TAIL_BYTES: u64 = 64 * 1024;
+
+    let Some(mut file) = current_log_file().and_then(|path| File::open(path).ok()) else {
+        return Vec::new();
+    };
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    if file.seek(SeekFrom::Start(len.saturating_sub(TAIL_BYTES))).is_err() {
+        return Vec::new();
+    }
+
+    let mut buffer = Vec::new();
+    if file.read_to_end(&mut buffer).is_err() {
+        return Vec::new();
+    }
+
+    let content = String::from_utf8_lossy(&buffer);
+    let lines: Vec<&str> = content.lines().collect();
+    // 从中间截断时第一行可能不完整，丢弃
+    let skip_partial = usize::from(len > TAIL_BYTES);
+    lines[skip_partial.min(lines.len())..]
+        .iter()
+        .rev()
+        .take(max_lines)
+        .rev()
+        .map(|line| line.to_string())
+        .collect()
+}
+
+/// 立即应用新的日志配置
+pub fn apply_config(config: LogConfig) {
+    let max_level = config.max_level();
+    if let Ok(mut current) = logger().config.lock() {
+        *current = config;
+    }
+    log::set_max_level(max_level);
+}
+
+/// 当前使用的日志配置
+pub fn current_config() -> LogConfig {
+    logger().config.lock().map(|c| c.clone()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("utilx-logging-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn rotated_files(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with(ROTATED_PREFIX))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn level_for_uses_longest_matching_target() {
+        let config = LogConfig {
+            level: "warn".to_string(),
+            targets: HashMap::from([
+                ("utilx".to_string(), "info".to_string()),
+                ("utilx::updater".to_string(), "trace".to_string()),
+                (FRONTEND_TARGET.to_string(), "debug".to_string()),
+            ]),
+            ..LogConfig::default()
+        };
+
+        assert_eq!(config.level_for("utilx::updater"), LevelFilter::Trace);
+        assert_eq!(config.level_for("utilx::updater::download"), LevelFilter::Trace);
+        assert_eq!(config.level_for("utilx::logging"), LevelFilter::Info);
+        assert_eq!(config.level_for(FRONTEND_TARGET), LevelFilter::Debug);
+        // 只匹配完整的模块段，不匹配名称前缀
+        assert_eq!(config.level_for("utilx_other"), LevelFilter::Warn);
+        assert_eq!(config.level_for("tauri"), LevelFilter::Warn);
+        assert_eq!(config.max_level(), LevelFilter::Trace);
+    }
+
+    #[test]
+    fn size_rotation_renames_the_current_file() {
+        let dir = temp_dir("rotation");
+        let config = LogConfig { rotation: LogRotation::Size, max_file_size_mb: 1, ..LogConfig::default() };
+
+        let mut sink = FileSink::open(&dir).unwrap();
+        assert!(!sink.needs_rotation(&config, 100), "empty files never rotate");
+        let line = "x".repeat(1024 * 1024);
+        sink.file.write_all(line.as_bytes()).unwrap();
+        sink.size += line.len() as u64;
+        assert!(sink.needs_rotation(&config, 1));
+
+        sink.rotate(&config).unwrap();
+        assert_eq!(sink.size, 0);
+        assert_eq!(fs::metadata(dir.join(LOG_FILE_NAME)).unwrap().len(), 0);
+        let rotated = rotated_files(&dir);
+        assert_eq!(rotated.len(), 1);
+        assert_eq!(fs::metadata(dir.join(&rotated[0])).unwrap().len(), line.len() as u64);
+
+        // 同一秒内再次轮转时不覆盖已有文件
+        sink.file.write_all(b"second\n").unwrap();
+        sink.rotate(&config).unwrap();
+        assert_eq!(rotated_files(&dir).len(), 2);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cleanup_removes_old_and_excess_rotated_files() {
+        let dir = temp_dir("cleanup");
+        let now = std::time::SystemTime::now();
+        let day = std::time::Duration::from_secs(24 * 60 * 60);
+        for (index, age_days) in [0u32, 1, 2, 10].into_iter().enumerate() {
+            let file = File::create(dir.join(format!("{}2024-01-0{}.log", ROTATED_PREFIX, index + 1))).unwrap();
+            file.set_modified(now - day * age_days).unwrap();
+        }
+        fs::write(dir.join(LOG_FILE_NAME), "current").unwrap();
+        fs::write(dir.join("other.log"), "unrelated").unwrap();
+
+        let config = LogConfig { retention_days: 7, max_files: 2, ..LogConfig::default() };
+        cleanup(&dir, &config);
+
+        // 10 天前的超过保留天数，2 天前的超过保留数量
+        assert_eq!(rotated_files(&dir), vec!["utilx-2024-01-01.log", "utilx-2024-01-02.log"]);
+        assert!(dir.join(LOG_FILE_NAME).exists());
+        assert!(dir.join("other.log").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reentrant_logging_does_not_deadlock() {
+        let logger = UtilxLogger { config: Mutex::new(LogConfig { console: false, ..LogConfig::default() }), sink: Mutex::new(None) };
+        let _sink = logger.sink.lock().unwrap();
+        let _guard = ReentrancyGuard::enter().unwrap();
+
+        // 模拟写日志时持有锁并发生 panic，panic hook 再次写日志
+        logger.log(&Record::builder().args(format_args!("from panic hook")).level(log::Level::Error).build());
+        logger.flush();
+        assert!(ReentrancyGuard::enter().is_none());
+    }
+}
@@ -1,26 +1,58 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod app_paths;
+mod i18n;
+mod logging;
+mod log_commands;
+mod crash_report;
+mod crash_commands;
+mod diagnostics;
+mod single_instance;
+mod routes;
+mod deep_link;
+mod deep_link_commands;
+mod external_links;
+mod link_commands;
+mod permissions;
+mod autostart;
 mod updater;
 mod update_config;
 mod update_commands;
 mod config_init;
 mod config_commands;
+mod config_profiles;
+mod profile_commands;
+mod settings_sync;
+mod sync_commands;
 mod menu_handler;
+mod app_menu;
+mod splash;
+mod splash_commands;
+mod tool_windows;
+mod mini_mode;
+mod mini_commands;
+mod window_commands;
+mod tray;
+mod theme_sync;
+mod theme_commands;
+mod global_shortcuts;
+mod shortcut_commands;
+mod clipboard_history;
+mod clipboard_commands;
+mod secrets_vault;
+mod vault_commands;
 
 use tauri::Manager;
-use std::time::Duration;
 use std::sync::Mutex;
 use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_deep_link::DeepLinkExt;
 
 // 辅助函数：从 setup 获取 app handle
 fn try_get_app_handle(app: &tauri::AppHandle) -> Option<tauri::AppHandle> {
     Some(app.clone())
 }
 
-// 嵌入配置文件到二进制文件中
-static DEFAULT_SPLASH_CONFIG: &str = include_str!("../splash-config.json");
-static DEFAULT_SPLASH_HTML: &str = include_str!("../splash.html");
 
 // learn more about Tauri commands at https://tauri.app/v1/guides/features/command
 #[tauri::command]
@@ -28,10 +60,14 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
-// 接收前端日志信息
+// 接收前端日志信息，保留前端的日志级别
 #[tauri::command]
-fn log_message(message: String) {
-    eprintln!("🌐 [前端日志] {}", message);
+fn log_message(message: String, level: Option<String>) {
+    let level = level
+        .as_deref()
+        .and_then(|level| level.parse::<log::Level>().ok())
+        .unwrap_or(log::Level::Info);
+    log::log!(target: logging::FRONTEND_TARGET, level, "🌐 [前端日志] {}", message);
 }
 
 
@@ -42,12 +78,12 @@ async fn save_debug_info(info: String, app_handle: tauri::AppHandle) -> Result<S
     use std::path::PathBuf;
 
     // 获取应用数据目录
-    let data_dir = app_handle.path().app_data_dir()
-        .map_err(|e| format!("无法获取应用数据目录: {}", e))?;
+    let data_dir = app_paths::data_dir(&app_handle)
+        .map_err(|e| i18n::tf("dirs.data_failed", &[("error", &e)]))?;
 
     // 确保目录存在
     fs::create_dir_all(&data_dir)
-        .map_err(|e| format!("无法创建调试目录: {}", e))?;
+        .map_err(|e| i18n::tf("debug.create_dir_failed", &[("error", &e)]))?;
 
     // 生成文件名
     let timestamp = std::time::SystemTime::now()
@@ -58,116 +94,103 @@ async fn save_debug_info(info: String, app_handle: tauri::AppHandle) -> Result<S
 
     // 保存调试信息
     fs::write(&file_path, info)
-        .map_err(|e| format!("无法保存调试信息: {}", e))?;
+        .map_err(|e| i18n::tf("debug.save_failed", &[("error", &e)]))?;
 
-    eprintln!("💾 [调试] 调试信息已保存到: {:?}", file_path);
-    Ok(format!("调试信息已保存到: {:?}", file_path))
+    log::info!("💾 [调试] 调试信息已保存到: {:?}", file_path);
+    Ok(i18n::tf("debug.saved", &[("path", &file_path.display())]))
 }
 
-// 打开外部链接
+// 生成诊断包（应用信息、脱敏配置、日志、崩溃报告和前端调试信息）
 #[tauri::command]
-fn open_external_link(url: String) -> Result<(), String> {
-    eprintln!("🔗 [RUST] Opening external link: {}", url);
-
-    // 使用系统命令打开浏览器，这在开发环境中更可靠
-    use std::process::Command;
-
-    let command = if cfg!(target_os = "macos") {
-        // macOS 使用 open 命令
-        vec!["open", &url]
-    } else if cfg!(target_os = "windows") {
-        // Windows 使用 start 命令
-        vec!["cmd", "/c", "start", &url]
-    } else {
-        // Linux 使用 xdg-open
-        vec!["xdg-open", &url]
-    };
-
-    match Command::new(&command[0]).args(&command[1..]).spawn() {
-        Ok(_) => {
-            eprintln!("🔗 [RUST] External link opened successfully using system command");
-            Ok(())
-        },
-        Err(e) => {
-            eprintln!("🔗 [RUST ERROR] Failed to open external link: {}", e);
-            Err(format!("Failed to open URL: {}", e))
+async fn create_diagnostic_bundle(
+    app_handle: tauri::AppHandle,
+    frontend_info: Option<String>,
+    reveal: Option<bool>,
+) -> Result<String, String> {
+    let bundle_path = diagnostics::create_bundle(&app_handle, frontend_info)
+        .map_err(|e| i18n::tf("diagnostics.create_failed", &[("error", &e)]))?;
+
+    // 在文件管理器中显示诊断包所在目录
+    if reveal.unwrap_or(true) {
+        if let Some(dir) = bundle_path.parent() {
+            if let Err(e) = config_commands::open_in_file_manager(dir) {
+                log::warn!("⚠️ 打开诊断包目录失败: {}", e);
+            }
         }
     }
+
+    Ok(bundle_path.to_string_lossy().to_string())
 }
 
 // 下载文件
 #[tauri::command]
-async fn download_file(url: String, filename: String, app_handle: tauri::AppHandle) -> Result<String, String> {
-    eprintln!("📥 [RUST] Starting download: {} -> {}", filename, url);
-
-    let window = app_handle.get_webview_window("main")
-        .ok_or_else(|| {
-            eprintln!("📥 [RUST ERROR] Failed to get main window");
-            "Failed to get main window".to_string()
-        })?;
+async fn download_file(url: String, filename: String, app_handle: tauri::AppHandle, window: tauri::WebviewWindow) -> Result<String, String> {
+    log::info!("📥 [RUST] Starting download: {} -> {} (window: {})", filename, url, window.label());
 
     // 获取下载目录
-    eprintln!("📥 [RUST] Getting download directory...");
+    log::info!("📥 [RUST] Getting download directory...");
     let download_dir = window.path().download_dir()
         .map_err(|e| {
-            eprintln!("📥 [RUST ERROR] Failed to get download directory: {}", e);
-            format!("Failed to get download directory: {}", e)
+            log::error!("📥 [RUST ERROR] Failed to get download directory: {}", e);
+            i18n::tf("download.dir_failed", &[("error", &e)])
         })?;
 
     let file_path = download_dir.join(&filename);
-    eprintln!("📥 [RUST] Target path: {}", file_path.display());
+    log::info!("📥 [RUST] Target path: {}", file_path.display());
 
     // 下载文件
-    eprintln!("📥 [RUST] Fetching file from URL...");
+    log::info!("📥 [RUST] Fetching file from URL...");
     let response = reqwest::get(&url).await
         .map_err(|e| {
-            eprintln!("📥 [RUST ERROR] Failed to fetch file: {}", e);
-            format!("Failed to fetch file: {}", e)
+            log::error!("📥 [RUST ERROR] Failed to fetch file: {}", e);
+            i18n::tf("download.fetch_failed", &[("error", &e)])
         })?;
 
-    eprintln!("📥 [RUST] Reading response bytes...");
+    log::info!("📥 [RUST] Reading response bytes...");
     let bytes = response.bytes().await
         .map_err(|e| {
-            eprintln!("📥 [RUST ERROR] Failed to read file: {}", e);
-            format!("Failed to read file: {}", e)
+            log::error!("📥 [RUST ERROR] Failed to read file: {}", e);
+            i18n::tf("download.read_failed", &[("error", &e)])
         })?;
 
-    eprintln!("📥 [RUST] Downloaded {} bytes", bytes.len());
+    log::info!("📥 [RUST] Downloaded {} bytes", bytes.len());
 
     // 保存文件
-    eprintln!("📥 [RUST] Saving file to disk...");
+    log::info!("📥 [RUST] Saving file to disk...");
     std::fs::write(&file_path, bytes)
         .map_err(|e| {
-            eprintln!("📥 [RUST ERROR] Failed to save file: {}", e);
-            format!("Failed to save file: {}", e)
+            log::error!("📥 [RUST ERROR] Failed to save file: {}", e);
+            i18n::tf("download.save_failed", &[("error", &e)])
         })?;
 
-    eprintln!("📥 [RUST] File saved successfully: {}", file_path.display());
+    log::info!("📥 [RUST] File saved successfully: {}", file_path.display());
 
     // 发送下载完成通知
     let app_handle_clone = app_handle.clone();
+    let title = i18n::t("notify.download_complete_title");
+    let body = i18n::tf("notify.download_complete_body", &[("file", &filename)]);
     tokio::spawn(async move {
         match send_system_notification(
-            "下载完成".to_string(),
-            format!("文件 {} 已下载完成", filename),
+            title,
+            body,
             app_handle_clone
         ).await {
             Ok(_) => {
-                eprintln!("📥 [RUST] Download completion notification sent");
+                log::info!("📥 [RUST] Download completion notification sent");
             },
             Err(e) => {
-                eprintln!("📥 [RUST ERROR] Failed to send download completion notification: {}", e);
+                log::error!("📥 [RUST ERROR] Failed to send download completion notification: {}", e);
             }
         }
     });
 
-    Ok(format!("File downloaded to: {}", file_path.display()))
+    Ok(i18n::tf("download.saved", &[("path", &file_path.display())]))
 }
 
 // 发送系统通知
 #[tauri::command]
 async fn send_system_notification(title: String, body: String, app_handle: tauri::AppHandle) -> Result<(), String> {
-    eprintln!("🔔 [RUST] Sending system notification: {} - {}", title, body);
+    log::info!("🔔 [RUST] Sending system notification: {} - {}", title, body);
 
     // 尝试使用 Tauri 的通知插件发送系统通知
     match app_handle.notification()
@@ -176,542 +199,78 @@ async fn send_system_notification(title: String, body: String, app_handle: tauri
         .body(&body)
         .show() {
         Ok(_) => {
-            eprintln!("🔔 [RUST] System notification sent successfully");
+            log::info!("🔔 [RUST] System notification sent successfully");
             Ok(())
         },
         Err(e) => {
-            eprintln!("🔔 [RUST ERROR] Failed to send system notification: {}", e);
+            log::error!("🔔 [RUST ERROR] Failed to send system notification: {}", e);
             // 降级到请求用户注意力
             match app_handle.webview_windows().values().next() {
                 Some(window) => {
                     match window.request_user_attention(Some(tauri::UserAttentionType::Critical)) {
                         Ok(_) => {
-                            eprintln!("🔔 [RUST] User attention requested as fallback");
+                            log::info!("🔔 [RUST] User attention requested as fallback");
                             Ok(())
                         },
                         Err(attention_err) => {
-                            eprintln!("🔔 [RUST ERROR] Failed to request user attention: {}", attention_err);
-                            Err(format!("Failed to send notification and request attention: {}", e))
+                            log::error!("🔔 [RUST ERROR] Failed to request user attention: {}", attention_err);
+                            Err(i18n::tf("notify.attention_failed", &[("error", &e)]))
                         }
                     }
                 },
                 None => {
-                    eprintln!("🔔 [RUST ERROR] No window available for attention request");
-                    Err(format!("Failed to send notification: {}", e))
+                    log::error!("🔔 [RUST ERROR] No window available for attention request");
+                    Err(i18n::tf("notify.failed", &[("error", &e)]))
                 }
             }
         }
     }
 }
 
-// 创建系统菜单
-fn create_system_menu(app_handle: &tauri::AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-    use tauri::menu::{Menu, MenuItem, Submenu};
-
-    eprintln!("🔧 创建系统菜单...");
-
-    // macOS 菜单栏设置
-    #[cfg(target_os = "macos")]
-    {
-        // 创建主页菜单 - 作为子菜单
-        let home_menu = Submenu::new(app_handle, "主页", true)?;
-        let home_item = MenuItem::with_id(
-            app_handle,
-            "go_home",
-            "返回首页",
-            true,
-            None::<&str>
-        )?;
-        let quit_item = MenuItem::with_id(
-            app_handle,
-            "quit_app",
-            "退出程序",
-            true,
-            None::<&str>
-        )?;
-        home_menu.append(&home_item)?;
-        home_menu.append(&quit_item)?;
-
-        // 创建版本更新菜单项
-        let check_updates_item = MenuItem::with_id(
-            app_handle,
-            "check_updates",
-            "版本更新",
-            true,
-            None::<&str>
-        )?;
-
-        // 创建关于菜单项
-        let about_item = MenuItem::with_id(
-            app_handle,
-            "show_about",
-            "关于",
-            true,
-            None::<&str>
-        )?;
-
-        // 创建刷新菜单项
-        let refresh_item = MenuItem::with_id(
-            app_handle,
-            "refresh_page",
-            "刷新页面",
-            true,
-            None::<&str>
-        )?;
-
-        // 创建帮助菜单
-        let help_menu = Submenu::new(app_handle, "帮助", true)?;
-        help_menu.append(&check_updates_item)?;
-        help_menu.append(&about_item)?;
-        help_menu.append(&refresh_item)?;
-
-        // 创建主菜单
-        let menu = Menu::with_items(app_handle, &[
-            &home_menu,
-            &help_menu,
-        ])?;
-
-        // 设置菜单到应用
-        app_handle.set_menu(menu)?;
-    }
-
-    // Windows/Linux 菜单栏设置
-    #[cfg(not(target_os = "macos"))]
-    {
-        // 创建主页菜单
-        let home_menu = Submenu::new(app_handle, "主页", true)?;
-        let go_home_item = MenuItem::with_id(
-            app_handle,
-            "go_home",
-            "返回首页",
-            true,
-            None::<&str>
-        )?;
-        let quit_item = MenuItem::with_id(
-            app_handle,
-            "quit_app",
-            "退出程序",
-            true,
-            None::<&str>
-        )?;
-        home_menu.append(&go_home_item)?;
-        home_menu.append(&quit_item)?;
-
-        // 创建版本更新菜单项
-        let check_updates_item = MenuItem::with_id(
-            app_handle,
-            "check_updates",
-            "版本更新",
-            true,
-            None::<&str>
-        )?;
-
-        // 创建关于菜单项
-        let about_item = MenuItem::with_id(
-            app_handle,
-            "show_about",
-            "关于",
-            true,
-            None::<&str>
-        )?;
-
-        // 创建刷新菜单项
-        let refresh_item = MenuItem::with_id(
-            app_handle,
-            "refresh_page",
-            "刷新页面",
-            true,
-            None::<&str>
-        )?;
-
-        // 创建帮助菜单
-        let help_menu = Submenu::new(app_handle, "帮助", true)?;
-        help_menu.append(&check_updates_item)?;
-        help_menu.append(&about_item)?;
-        help_menu.append(&refresh_item)?;
-
-        // 创建主菜单
-        let menu = Menu::with_items(app_handle, &[
-            &home_menu,
-            &help_menu,
-        ])?;
-
-        // 设置菜单到应用
-        app_handle.set_menu(menu)?;
-    }
-
-    Ok(())
-}
-
 // 退出程序命令
 #[tauri::command]
 async fn quit_app(app_handle: tauri::AppHandle) -> Result<(), String> {
-    eprintln!("🚪 [RUST] 退出程序");
+    log::info!("🚪 [RUST] 退出程序");
     app_handle.exit(0);
     Ok(())
 }
 
-// 菜单导航命令
+// 菜单导航命令（页面 ID 见路由表，如 home、updates、about）
 #[tauri::command]
 async fn navigate_to_page(app_handle: tauri::AppHandle, page: String) -> Result<(), String> {
-    eprintln!("🧭 [RUST] 导航到页面: {}", page);
-
-    if let Some(window) = app_handle.get_webview_window("main") {
-        match page.as_str() {
-            "home" => {
-                let _ = window.eval("window.location.href = '/';");
-            },
-            "updates" => {
-                let _ = window.eval("window.location.href = '/feedback/';");
-            },
-            "about" => {
-                let _ = window.eval("window.location.href = '/about/';");
-            },
-            _ => {
-                return Err(format!("未知页面: {}", page));
-            }
-        }
-        Ok(())
-    } else {
-        Err("无法找到主窗口".to_string())
-    }
+    log::info!("🧭 [RUST] 导航到页面: {}", page);
+    routes::navigate(&app_handle, &routes::Route::Page(page))
+        .map_err(|e| i18n::tf("navigation.failed", &[("error", &e)]))
 }
 
-#[tokio::main]
-async fn main() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_window_state::Builder::default().build())
-        .plugin(tauri_plugin_shell::init())
-        .plugin(tauri_plugin_opener::init())
-        .plugin(tauri_plugin_notification::init())
-        .setup(|app| {
-            // 初始化应用配置
-            if let Err(e) = config_init::initialize_app_config(app.handle()) {
-                eprintln!("⚠️ 配置初始化失败: {}", e);
-            }
-
-            // 创建并设置系统菜单
-            if let Err(e) = create_system_menu(app.handle()) {
-                eprintln!("⚠️ 设置系统菜单失败: {}", e);
-            } else {
-                eprintln!("✅ 系统菜单设置成功");
-            }
-
-            // 注册菜单事件处理程序
-            let app_handle = app.handle().clone();
-            app.on_menu_event(move |_window, event| {
-                eprintln!("📋 [菜单] 收到菜单事件: {:?}", event.id);
-                menu_handler::handle_menu_event(&app_handle, &event.id);
-            });
-
-            // 读取启动画面配置文件 - 先尝试外部文件，失败则使用内置配置
-            let current_dir = std::env::current_dir().unwrap();
-            let config_path = current_dir.join("splash-config.json");
-
-            let config_content = if config_path.exists() {
-                match std::fs::read_to_string(&config_path) {
-                    Ok(content) => {
-                        eprintln!("📄 读取外部启动画面配置文件: {}", config_path.display());
-                        content
-                    },
-                    Err(e) => {
-                        eprintln!("⚠️ 无法读取外部配置文件: {}，使用内置配置", e);
-                        DEFAULT_SPLASH_CONFIG.to_string()
-                    }
-                }
-            } else {
-                eprintln!("📄 使用内置启动画面配置");
-                DEFAULT_SPLASH_CONFIG.to_string()
-            };
-
-            let config: serde_json::Value = serde_json::from_str(&config_content).unwrap_or_default();
-
-            let is_dev = cfg!(debug_assertions);
-
-            let splash_enabled = config.get("enabled")
-                .and_then(|e| e.as_bool())
-                .unwrap_or(true);
-
-            let splash_duration = config.get("development")
-                .and_then(|dev| dev.get("duration"))
-                .or_else(|| config.get("duration"))
-                .and_then(|d| d.as_u64())
-                .unwrap_or(if is_dev { 3000 } else { 5000 });
-
-            let wait_for_main = config.get("waitForMainPage")
-                .and_then(|w| w.as_bool())
-                .unwrap_or(true);
-
-            let check_interval = config.get("development")
-                .and_then(|dev| dev.get("checkInterval"))
-                .or_else(|| config.get("checkInterval"))
-                .and_then(|c| c.as_u64())
-                .unwrap_or(if is_dev { 200 } else { 500 });
-
-            let max_wait_time = config.get("development")
-                .and_then(|dev| dev.get("maxWaitTime"))
-                .or_else(|| config.get("maxWaitTime"))
-                .and_then(|m| m.as_u64())
-                .unwrap_or(if is_dev { 15000 } else { 30000 });
-
-            // 读取启动画面窗口尺寸和居中设置
-            let (splash_width, splash_height) = config.get("windowSize")
-                .and_then(|ws| Some((
-                    ws.get("width")?.as_u64()? as f64,
-                    ws.get("height")?.as_u64()? as f64
-                )))
-                .unwrap_or((800.0, 600.0));
-
-            let center_splash_window = config.get("centerWindow")
-                .and_then(|c| c.as_bool())
-                .unwrap_or(true);
-
-            // 读取主窗口尺寸
-            let (main_width, main_height) = config.get("mainWindowSize")
-                .and_then(|ws| Some((
-                    ws.get("width")?.as_u64()? as f64,
-                    ws.get("height")?.as_u64()? as f64
-                )))
-                .unwrap_or((1400.0, 760.0));
-
-            eprintln!("🎨 启动画面配置:");
-            eprintln!("  启用: {}", splash_enabled);
-            eprintln!("  持续时间: {}ms", splash_duration);
-            eprintln!("  等待主页面: {}", wait_for_main);
-            eprintln!("  检查间隔: {}ms", check_interval);
-            eprintln!("  最大等待时间: {}ms", max_wait_time);
-            eprintln!("  启动窗口尺寸: {}x{}", splash_width, splash_height);
-            eprintln!("  主窗口尺寸: {}x{}", main_width, main_height);
-            eprintln!("  窗口居中: {}", center_splash_window);
-            eprintln!("  开发模式: {}", cfg!(debug_assertions));
-            eprintln!("🚀 开始启动流程...");
-
-            // 读取 splash.html 文件内容 - 先尝试外部文件，失败则使用内置文件
-            let splash_html_content = {
-                let splash_path = current_dir.join("splash.html");
-                if splash_path.exists() {
-                    match std::fs::read_to_string(&splash_path) {
-                        Ok(content) => {
-                            eprintln!("✅ 读取外部 splash.html，大小: {} 字符", content.len());
-                            content
-                        },
-                        Err(e) => {
-                            eprintln!("⚠️ 无法读取外部 splash.html: {}，使用内置文件", e);
-                            DEFAULT_SPLASH_HTML.to_string()
-                        }
-                    }
-                } else {
-                    eprintln!("📄 使用内置 splash.html，大小: {} 字符", DEFAULT_SPLASH_HTML.len());
-                    DEFAULT_SPLASH_HTML.to_string()
-                }
-            };
-
-            eprintln!("🔍 调试信息:");
-            eprintln!("当前工作目录: {}", current_dir.display());
-            eprintln!("启动画面: {}", splash_enabled);
-            eprintln!("splash.html 内容大小: {} 字符", splash_html_content.len());
-
-            let window = app.get_webview_window("main")
-                .expect("配置的 'main' 窗口不存在");
-
-            // 如果启用了启动画面，先设置启动画面的尺寸
-            if splash_enabled {
-                // 设置启动画面窗口尺寸（先设置尺寸再居中）
-                match window.set_size(tauri::Size::Logical(tauri::LogicalSize { width: splash_width, height: splash_height })) {
-                    Ok(_) => eprintln!("✅ 预设启动画面窗口尺寸为 {}x{}", splash_width, splash_height),
-                    Err(e) => eprintln!("❌ 预设启动画面窗口尺寸失败: {}", e),
-                }
-
-                // 启动画面居中
-                if center_splash_window {
-                    match window.center() {
-                        Ok(_) => eprintln!("✅ 启动画面窗口已居中"),
-                        Err(e) => eprintln!("❌ 启动画面窗口居中失败: {}", e),
-                    }
-                }
-            } else {
-                // 如果没有启动画面，使用配置文件中的主窗口尺寸
-                // 这里先不设置尺寸，让配置文件生效
-            }
-
-            // 如果启用了启动画面
-            if splash_enabled {
-                let splash_html = splash_html_content;
-
-                // 立即设置页面内容，避免任何灰色背景
-                let set_initial_content = format!(
-                    "document.open(); document.write(`{}`); document.close();",
-                    splash_html.replace('`', "\\`").replace("${", "\\${")
-                );
-
-                match window.eval(&set_initial_content) {
-                    Ok(_) => {
-                        eprintln!("✅ 立即设置页面内容，避免灰色背景");
-                        eprintln!("启动画面已加载");
-                    },
-                    Err(e) => eprintln!("❌ 设置初始页面内容失败: {}", e),
-                }
-
-                // 启动时设置窗口属性（会在启动完成后恢复）
-                // 启动画面不需要窗口装饰（尺寸和居中已在前面的代码中设置）
-                window.set_decorations(false)?;
-                window.set_resizable(false)?;
-                window.set_always_on_top(true)?;
-
-                eprintln!("🪟 窗口创建成功，启动画面已立即加载");
-
-            } else {
-                // 如果禁用启动画面，直接加载主应用
-                eprintln!("📄 启动画面已禁用，直接加载主应用");
-                window.set_decorations(true)?;
-                window.set_resizable(true)?;
-                window.set_always_on_top(false)?;
-            }
-
-            // 启动完成后的处理（使用配置文件中的参数）
-            let window_clone_finish = window.clone();
-            let splash_duration_clone = splash_duration;
-            let wait_for_main_clone = wait_for_main;
-            let check_interval_clone = check_interval;
-            let max_wait_time_clone = max_wait_time;
-
-            tokio::spawn(async move {
-                eprintln!("⏱️ 开始{}ms启动动画计时", splash_duration_clone);
-
-                // 等待配置的启动画面显示时间
-                tokio::time::sleep(Duration::from_millis(splash_duration_clone)).await;
-
-                eprintln!("🔄 凇查主页面状态...");
-
-                // 恢复窗口装饰，然后切换到主应用页面
-                eprintln!("🔄 恢复窗口装饰并切换到主应用页面");
-                match window_clone_finish.set_decorations(true) {
-                    Ok(_) => eprintln!("✅ 立即设置装饰成功"),
-                    Err(e) => eprintln!("❌ 立即设置装饰失败: {}", e),
-                }
-                match window_clone_finish.set_resizable(true) {
-                    Ok(_) => eprintln!("✅ 立即设置可调整大小成功"),
-                    Err(e) => eprintln!("❌ 立即设置可调整大小失败: {}", e),
-                }
-                match window_clone_finish.set_always_on_top(false) {
-                    Ok(_) => eprintln!("✅ 立即取消置顶成功"),
-                    Err(e) => eprintln!("❌ 立即取消置顶失败: {}", e),
-                }
-
-                // 先调整窗口大小到主窗口尺寸，然后加载主页面
-                eprintln!("📐 调整窗口到主窗口尺寸...");
-                match window_clone_finish.set_size(tauri::Size::Logical(tauri::LogicalSize { width: main_width, height: main_height })) {
-                    Ok(_) => eprintln!("✅ 调整窗口尺寸为 {}x{}", main_width, main_height),
-                    Err(e) => eprintln!("❌ 调整窗口尺寸失败: {}", e),
-                }
-
-                // 尺寸改变后稍等片刻再居中窗口，确保尺寸变化完成
-                tokio::time::sleep(Duration::from_millis(100)).await;
-                match window_clone_finish.center() {
-                    Ok(_) => eprintln!("✅ 窗口已重新居中"),
-                    Err(e) => eprintln!("❌ 窗口重新居中失败: {}", e),
-                }
-
-                // 现在切换到主应用页面（窗口已经是正确尺寸）
-                // 使用正确的 Tauri 方式导航页面，确保 Tauri API 正确注入
-                match window_clone_finish.eval(r#"window.location.replace('/index.html')"#) {
-                    Ok(_) => eprintln!("✅ 加载 Tauri API 测试页面"),
-                    Err(e) => eprintln!("❌ 加载测试页面失败: {}", e),
-                }
-
-                // 如果配置要求等待主页面准备好，则进行等待
-                let mut wait_time = 0;
-                if wait_for_main_clone {
-                    loop {
-                        // 检查主页面是否准备就绪的 JavaScript 代码
-                        let check_js = r#"
-                            // 检查 Vue 应用是否已挂载
-                            const appReady = document.querySelector('[data-v-app]') ||
-                                          document.querySelector('#app') ||
-                                          document.querySelector('.nuxt-loading') === null;
-
-                            // 检查是否还有加载中的元素
-                            const hasLoading = document.querySelector('.loading') ||
-                                            document.querySelector('[class*="loading"]') ||
-                                            document.querySelector('.nuxt-loading');
-
-                            if (appReady && !hasLoading) {
-                                'ready';
-                            } else {
-                                'loading';
-                            }
-                        "#;
-
-                        match window_clone_finish.eval(check_js) {
-                            Ok(result) => {
-                                // 使用 format! 来显示结果
-                                eprintln!("🔍 主页面检查结果: {:?}", result);
-                                // 由于 eval 返回的是 ()，我们无法直接检查结果
-                                // 假设页面还没有准备好，继续等待
-                            },
-                            Err(_) => {
-                                // 如果检查失败，等待一段时间后继续
-                                eprintln!("⚠️ 无法检查主页面状态，继续等待...");
-                            }
-                        }
-
-                        // 检查是否超过最大等待时间
-                        wait_time += check_interval_clone;
-                        if wait_time >= max_wait_time_clone {
-                            eprintln!("⚠️ 达到最大等待时间，强制切换");
-                            break;
-                        }
-
-                        eprintln!("⏳ 主页面加载中... (已等待{}ms)", wait_time);
-                        tokio::time::sleep(Duration::from_millis(check_interval_clone)).await;
-
-                        // 简单策略：等待一段时间后假设页面已加载
-                        if wait_time >= 2000 {  // 至少等待2秒
-                            eprintln!("✅ 主页面已准备就绪（基于时间判断）");
-                            break;
-                        }
-                    }
-                }
-
-                // 主窗口尺寸已经在前面设置，这里无需重复设置
-
-                // 再次强制设置窗口装饰
-                match window_clone_finish.set_decorations(true) {
-                    Ok(_) => eprintln!("✅ 最终确认窗口装饰"),
-                    Err(e) => eprintln!("❌ 最终确认窗口装饰失败: {}", e),
-                }
-
-                // 确保窗口可调整大小
-                match window_clone_finish.set_resizable(true) {
-                    Ok(_) => eprintln!("✅ 最终确认窗口可调整大小"),
-                    Err(e) => eprintln!("❌ 最终确认窗口可调整大小失败: {}", e),
-                }
-
-                // 主页面加载完成后再次确保窗口居中
-                match window_clone_finish.center() {
-                    Ok(_) => eprintln!("✅ 最终确认窗口居中"),
-                    Err(e) => eprintln!("❌ 最终确认窗口居中失败: {}", e),
-                }
-
-                // 设置焦点
-                match window_clone_finish.set_focus() {
-                    Ok(_) => eprintln!("✅ 设置焦点成功"),
-                    Err(e) => eprintln!("❌ 设置焦点失败: {}", e),
-                }
+// 导航到页面、分类或工具
+#[tauri::command]
+async fn navigate(app_handle: tauri::AppHandle, route: routes::Route) -> Result<(), String> {
+    routes::navigate(&app_handle, &route)
+        .map_err(|e| i18n::tf("navigation.failed", &[("error", &e)]))
+}
 
-                eprintln!("🎉 启动流程完成 (总耗时: {}ms)", splash_duration_clone + wait_time);
-            });
+// 获取路由表
+#[tauri::command]
+fn get_routes() -> routes::RouteTable {
+    routes::table().clone()
+}
 
-            Ok(())
-        })
-        .invoke_handler(tauri::generate_handler![
+/// 所有注册的命令；同一份列表既生成命令处理器，也生成 `COMMAND_NAMES` 供权限表检查
+macro_rules! app_commands {
+    ($($handler:tt)+) => {
+        $($handler)+![
             greet,
             log_message,
             save_debug_info,
-            open_external_link,
+            create_diagnostic_bundle,
+            link_commands::open_external_link,
+            link_commands::confirm_external_link,
             download_file,
             send_system_notification,
             navigate_to_page,
+            navigate,
+            get_routes,
             quit_app,
             update_commands::check_for_updates,
             update_commands::apply_hotfix_update,
@@ -736,6 +295,30 @@ async fn main() {
             config_commands::restore_config_backup,
             config_commands::get_user_settings,
             config_commands::save_user_settings,
+            config_commands::get_autostart_status,
+            config_commands::record_recent_tool,
+            config_commands::save_favorite_tools,
+            splash_commands::app_ready,
+            splash_commands::get_splash_config,
+            splash_commands::save_splash_config,
+            window_commands::open_tool_window,
+            window_commands::list_tool_windows,
+            mini_commands::get_mini_mode_config,
+            mini_commands::save_mini_mode_config,
+            mini_commands::open_mini_mode,
+            mini_commands::toggle_mini_mode,
+            theme_commands::get_theme_state,
+            log_commands::get_log_config,
+            log_commands::save_log_config,
+            log_commands::set_log_level,
+            crash_commands::get_pending_crash_reports,
+            crash_commands::get_crash_report,
+            crash_commands::export_crash_report,
+            crash_commands::keep_crash_report,
+            crash_commands::delete_crash_report,
+            deep_link_commands::take_deep_link,
+            deep_link_commands::parse_deep_link,
+            deep_link_commands::confirm_deep_link_file,
             config_commands::get_tools_config,
             config_commands::save_tools_config,
             config_commands::clear_cache,
@@ -743,9 +326,237 @@ async fn main() {
             config_commands::get_config_dir,
             config_commands::get_cache_dir,
             config_commands::get_log_dir,
-            config_commands::open_config_dir
-        ])
+            config_commands::open_config_dir,
+            vault_commands::vault_status,
+            vault_commands::vault_initialize,
+            vault_commands::vault_unlock,
+            vault_commands::vault_lock,
+            vault_commands::vault_change_password,
+            vault_commands::vault_set_auto_lock,
+            vault_commands::vault_list_secrets,
+            vault_commands::vault_set_secret,
+            vault_commands::vault_delete_secret,
+            vault_commands::vault_copy_secret,
+            vault_commands::vault_http_request,
+            profile_commands::list_profiles,
+            profile_commands::get_active_profile,
+            profile_commands::create_profile,
+            profile_commands::clone_profile,
+            profile_commands::switch_profile,
+            profile_commands::delete_profile,
+            profile_commands::export_profile,
+            sync_commands::get_sync_config,
+            sync_commands::save_sync_config,
+            sync_commands::get_sync_status,
+            sync_commands::sync_now,
+            sync_commands::resolve_sync_conflict,
+            shortcut_commands::get_shortcuts,
+            shortcut_commands::validate_shortcuts,
+            shortcut_commands::save_shortcuts,
+            shortcut_commands::reload_shortcuts,
+            clipboard_commands::get_clipboard_history,
+            clipboard_commands::search_clipboard_history,
+            clipboard_commands::copy_clipboard_entry,
+            clipboard_commands::pin_clipboard_entry,
+            clipboard_commands::delete_clipboard_entry,
+            clipboard_commands::clear_clipboard_history,
+            clipboard_commands::get_clipboard_image,
+            clipboard_commands::get_clipboard_history_config,
+            clipboard_commands::save_clipboard_history_config,
+            clipboard_commands::set_clipboard_history_paused,
+        ]
+    };
+}
+
+macro_rules! command_names {
+    ($($($segment:ident)::+),* $(,)?) => {
+        [$(command_names!(@last $($segment)::+)),*]
+    };
+    (@last $name:ident) => { stringify!($name) };
+    (@last $module:ident :: $($rest:ident)::+) => { command_names!(@last $($rest)::+) };
+}
+
+/// 所有注册命令的名称
+const COMMAND_NAMES: &[&str] = &app_commands!(command_names);
+
+#[tokio::main]
+async fn main() {
+    logging::init();
+    crash_report::install_hook();
+
+    for command in permissions::unassigned(COMMAND_NAMES) {
+        log::error!("🚫 命令 {} 没有分配能力，调用会被拒绝", command);
+    }
+
+    // 已有实例在运行时转发启动参数并退出
+    let instance_guard = match single_instance::acquire() {
+        Ok(single_instance::Instance::Primary(guard)) => Some(guard),
+        Ok(single_instance::Instance::Secondary) => return,
+        Err(e) => {
+            log::warn!("⚠️ 单实例检查失败: {}，继续启动", e);
+            None
+        }
+    };
+
+    tauri::Builder::default()
+        .plugin(
+            tauri_plugin_window_state::Builder::default()
+                .with_filename(app_paths::window_state_filename())
+                // 主窗口由启动流程负责显示，启动画面窗口不保存状态
+                .with_state_flags(tauri_plugin_window_state::StateFlags::all() & !tauri_plugin_window_state::StateFlags::VISIBLE)
+                .with_denylist(&[splash::SPLASH_LABEL])
+                .build()
+        )
+        .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .setup(move |app| {
+            // 开始写入日志文件
+            match logging::attach_file(app.handle()) {
+                Ok(path) => log::info!("📝 日志文件: {}", path.display()),
+                Err(e) => log::warn!("⚠️ 初始化日志文件失败: {}", e),
+            }
+
+            // 接收后续启动转发的参数
+            if let Some(guard) = instance_guard {
+                guard.listen(app.handle().clone());
+            }
+
+            // 注册 utilx:// 协议（AppImage 等未经安装的情况也能生效）
+            #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+            if let Err(e) = app.deep_link().register_all() {
+                log::warn!("⚠️ 注册深层链接协议失败: {}", e);
+            }
+
+            // 通过深层链接启动时保存链接内容，等待页面读取
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                for url in urls {
+                    deep_link::handle(app.handle(), url.as_str());
+                }
+            }
+
+            // macOS 通过系统事件传入深层链接
+            let deep_link_handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    deep_link::handle(&deep_link_handle, url.as_str());
+                }
+            });
+
+            // 崩溃报告写入应用数据目录，并检查上次运行是否崩溃
+            if let Err(e) = crash_report::init(app.handle()) {
+                log::warn!("⚠️ 初始化崩溃报告目录失败: {}", e);
+            }
+            crash_report::notify_pending(app.handle().clone());
+
+            // 初始化应用配置
+            if let Err(e) = config_init::initialize_app_config(app.handle()) {
+                log::warn!("⚠️ 配置初始化失败: {}", e);
+            }
+
+            // 使开机自启动项与 auto_start 设置一致
+            match autostart::reconcile(app.handle()) {
+                Ok(status) => log::info!("🚀 开机自启动: 设置={}, 已注册={}", status.enabled_in_settings, status.registered),
+                Err(e) => log::warn!("⚠️ 同步开机自启动失败: {}", e),
+            }
+
+            // 加载剪贴板历史并开始监听剪贴板
+            if let Err(e) = clipboard_history::start(app.handle()) {
+                log::warn!("⚠️ 启动剪贴板历史失败: {}", e);
+            }
+
+            // 启动保险库空闲自动锁定
+            secrets_vault::start_auto_lock(app.handle().clone());
+
+            // 启用同步时定期自动同步
+            settings_sync::start_auto_sync(app.handle().clone());
+
+            // 按 language 设置选择后端文案的语言（菜单、托盘和启动画面都会用到）
+            i18n::reload(app.handle());
+
+            // 创建并设置系统菜单
+            if let Err(e) = app_menu::create(app.handle()) {
+                log::warn!("⚠️ 设置系统菜单失败: {}", e);
+            } else {
+                log::info!("✅ 系统菜单设置成功");
+            }
+
+            // 创建系统托盘
+            if let Err(e) = tray::create_tray(app.handle()) {
+                log::warn!("⚠️ 创建系统托盘失败: {}", e);
+            } else {
+                log::info!("✅ 系统托盘创建成功");
+            }
+
+            // 跟随系统外观，应用到窗口、菜单和托盘图标
+            theme_sync::start(app.handle());
+
+            // 注册全局快捷键（需要在主线程空闲时完成，放到后台执行）
+            let shortcut_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                global_shortcuts::reload(&shortcut_handle);
+            });
+
+            // 注册菜单事件处理程序（应用菜单和托盘菜单）
+            let app_handle = app.handle().clone();
+            app.on_menu_event(move |_window, event| {
+                log::info!("📋 [菜单] 收到菜单事件: {:?}", event.id);
+                menu_handler::handle_menu_event(&app_handle, &event.id);
+            });
+
+            // 启动画面配置和自定义页面来自配置目录，缺失或无效时使用内置文件
+            let splash_config = splash::SplashConfig::load(app.handle());
+
+            log::info!("🎨 启动画面配置:");
+            log::info!("  启用: {}", splash_config.enabled);
+            log::info!("  最短显示时间: {}ms", splash_config.min_duration().as_millis());
+            log::info!("  等待主页面: {}", splash_config.wait_for_main_page);
+            log::info!("  最大等待时间: {}ms", splash_config.max_wait().as_millis());
+            log::info!("  启动窗口尺寸: {}x{}", splash_config.window_size.width, splash_config.window_size.height);
+            log::info!("  主窗口尺寸: {}x{}", splash_config.main_window_size.width, splash_config.main_window_size.height);
+            log::info!("  窗口居中: {}", splash_config.center_window);
+            log::info!("  开发模式: {}", cfg!(debug_assertions));
+            log::info!("🚀 开始启动流程...");
+
+            let splash_html_content = splash::load_html(app.handle());
+
+            // 主窗口在后台加载（配置中默认隐藏），启动画面在独立窗口中显示；
+            // 开机自启动且托盘图标存在时不显示任何窗口（没有托盘时隐藏后就无法再打开）
+            let has_tray = app.tray_by_id(tray::TRAY_ID).is_some();
+            if autostart::launched_at_login() && has_tray {
+                splash::start_hidden(app.handle(), &splash_config);
+            } else {
+                splash::start(app.handle(), splash_config, &splash_html_content);
+            }
+
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            // 开启关闭到托盘且托盘图标存在时，关闭主窗口只隐藏不退出（托盘创建失败时隐藏后就无法再打开）
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                let has_tray = window.app_handle().tray_by_id(tray::TRAY_ID).is_some();
+                if window.label() == tool_windows::MAIN_LABEL && has_tray && tray::minimize_to_tray_enabled(window.app_handle()) {
+                    api.prevent_close();
+                    let _ = window.hide();
+                    log::info!("📥 主窗口已隐藏到托盘");
+                } else if tool_windows::is_tool_window(window.label()) {
+                    tool_windows::forget(window.app_handle(), window.label());
+                }
+            }
+            if let tauri::WindowEvent::ThemeChanged(theme) = event {
+                theme_sync::on_window_theme_changed(window.app_handle(), *theme);
+            }
+        })
+        .invoke_handler(permissions::guard(app_commands!(tauri::generate_handler)))
         .manage(update_commands::UpdateManagerState(Mutex::new(None)))
+        .manage(deep_link::DeepLinkState::default())
+        .manage(external_links::ExternalLinkState::default())
+        .manage(splash::AppReadyState::default())
+        .manage(tool_windows::ToolWindowsState::default())
+        .manage(secrets_vault::SecretsVaultState(Mutex::new(secrets_vault::VaultSession::default())))
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
\ No newline at end of file
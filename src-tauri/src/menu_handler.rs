@@ -1,109 +1,103 @@
 // 菜单相关功能
-use tauri::{AppHandle, Manager};
+use crate::routes::Route;
+use tauri::AppHandle;
 
-// 由于 Tauri v2 菜单 API 复杂，我们使用简化的方式
-// 直接通过 JavaScript 注入来处理菜单操作
-
-// 处理菜单事件
+// 处理菜单事件（应用菜单和托盘菜单）
 pub fn handle_menu_event(app_handle: &AppHandle, event_id: &tauri::menu::MenuId) {
     match event_id.as_ref() {
         "go_home" => {
-            eprintln!("🏠 [菜单] 强制返回首页");
-            if let Some(window) = app_handle.get_webview_window("main") {
-                // 强制返回首页，无论当前在哪个页面（包括外部链接）
-                let _ = window.eval(r#"
-                    // 强制返回首页的 JavaScript 代码
-                    console.log('🏠 [MENU] 强制返回首页');
-                    console.log('当前 URL:', window.location.href);
-                    console.log('当前 hostname:', window.location.hostname);
-                    console.log('当前 protocol:', window.location.protocol);
-
-                    // 尝试多种方法返回首页
-                    try {
-                        // 检查是否在外部链接或不同协议
-                        const isExternal = window.location.protocol !== 'tauri:' &&
-                                          (window.location.hostname !== 'localhost' &&
-                                           window.location.hostname !== '127.0.0.1' &&
-                                           !window.location.hostname.includes('tauri.localhost'));
-
-                        console.log('是否为外部链接:', isExternal);
-
-                        if (isExternal) {
-                            // 如果是外部链接，尝试返回到应用首页
-                            console.log('检测到外部链接，尝试返回应用首页');
-
-                            // 方法1: 尝试回到历史记录中的首页
-                            if (window.history.length > 1) {
-                                window.history.go(-(window.history.length - 1));
-                            }
-
-                            // 方法2: 延迟跳转到首页
-                            setTimeout(() => {
-                                console.log('延迟检查，仍在尝试返回首页');
-                                window.location.href = '/';
-                            }, 200);
-
-                        } else {
-                            // 如果在应用内，直接返回首页
-                            console.log('在应用内，返回首页');
-                            window.location.href = '/';
-                        }
-
-                        // 方法3: 清除历史记录
-                        if (window.history.length > 1) {
-                            window.history.replaceState({}, '', '/');
-                        }
-
-                    } catch (error) {
-                        console.error('返回首页时出错:', error);
-                        // 最后的备用方案
-                        try {
-                            window.location.href = '/';
-                        } catch (e) {
-                            console.error('备用方案也失败:', e);
-                            // 强制刷新页面
-                            window.location.reload();
-                        }
-                    }
-                "#);
-            }
+            log::info!("🏠 [菜单] 返回首页");
+            navigate_or_log(app_handle, Route::home());
         },
         "check_updates" => {
-            eprintln!("🔄 [菜单] 版本更新");
-            if let Some(window) = app_handle.get_webview_window("main") {
-                let _ = window.eval("window.location.href = '/feedback/';");
-            }
+            log::info!("🔄 [菜单] 版本更新");
+            navigate_or_log(app_handle, Route::Page("updates".to_string()));
         },
         "show_about" => {
-            eprintln!("ℹ️ [菜单] 关于");
-            if let Some(window) = app_handle.get_webview_window("main") {
-                let _ = window.eval("window.location.href = '/about/';");
-            }
+            log::info!("ℹ️ [菜单] 关于");
+            navigate_or_log(app_handle, Route::Page("about".to_string()));
         },
         "refresh_page" => {
-            eprintln!("🔄 [菜单] 刷新页面");
-            if let Some(window) = app_handle.get_webview_window("main") {
-                // 使用 Tauri 的刷新方法，并添加备用方案
-                let _ = window.eval(r#"
-                    console.log('🔄 [MENU] 刷新当前页面');
-                    try {
-                        // 方法1: 使用 location.reload() 强制刷新
-                        window.location.reload(true);
-                    } catch (error) {
-                        console.error('刷新页面时出错:', error);
-                        // 方法2: 重新导航到当前页面
-                        window.location.href = window.location.href;
-                    }
-                "#);
+            log::info!("🔄 [菜单] 刷新页面");
+            with_focused_window(app_handle, |window| window.reload());
+        },
+        "toggle_fullscreen" => {
+            log::info!("🪟 [菜单] 切换全屏");
+            with_focused_window(app_handle, |window| window.set_fullscreen(!window.is_fullscreen()?));
+        },
+        "minimize_window" => {
+            log::info!("🪟 [菜单] 最小化");
+            with_focused_window(app_handle, |window| window.minimize());
+        },
+        "toggle_maximize" => {
+            log::info!("🪟 [菜单] 缩放");
+            with_focused_window(app_handle, |window| {
+                if window.is_maximized()? { window.unmaximize() } else { window.maximize() }
+            });
+        },
+        "close_window" => {
+            log::info!("🪟 [菜单] 关闭窗口");
+            with_focused_window(app_handle, |window| window.close());
+        },
+        "toggle_mini_mode" => {
+            log::info!("📌 [菜单] 切换迷你模式");
+            if let Err(e) = crate::mini_mode::toggle(app_handle) {
+                log::error!("❌ 切换迷你模式失败: {}", e);
+            }
+        },
+        "toggle_mini_click_through" => {
+            log::info!("📌 [菜单] 切换鼠标穿透");
+            if let Err(e) = crate::mini_mode::toggle_click_through(app_handle) {
+                log::error!("❌ 切换鼠标穿透失败: {}", e);
             }
         },
+        "toggle_window" => {
+            log::info!("🪟 [菜单] 显示/隐藏窗口");
+            crate::tray::toggle_main_window(app_handle);
+        },
+        id if id.starts_with(crate::tray::OPEN_TOOL_PREFIX) => {
+            let tool_id = &id[crate::tray::OPEN_TOOL_PREFIX.len()..];
+            log::info!("🧰 [菜单] 打开工具: {}", tool_id);
+            open_tool(app_handle, tool_id);
+        },
+        id if id.starts_with(crate::app_menu::OPEN_PAGE_PREFIX) => {
+            let page_id = &id[crate::app_menu::OPEN_PAGE_PREFIX.len()..];
+            log::info!("📄 [菜单] 打开页面: {}", page_id);
+            navigate_or_log(app_handle, Route::Page(page_id.to_string()));
+        },
+        id if id.starts_with(crate::app_menu::OPEN_CATEGORY_PREFIX) => {
+            let category_id = &id[crate::app_menu::OPEN_CATEGORY_PREFIX.len()..];
+            log::info!("🗂️ [菜单] 打开分类: {}", category_id);
+            navigate_or_log(app_handle, Route::Category(category_id.to_string()));
+        },
         "quit_app" => {
-            eprintln!("🚪 [菜单] 退出程序");
-            // 使用 Tauri 的退出方法
-            std::process::exit(0);
+            log::info!("🚪 [菜单] 退出程序");
+            // 通过 Tauri 退出，让窗口状态保存和日志刷新等退出处理正常执行
+            app_handle.exit(0);
         },
         _ => {
-            eprintln!("❓ [菜单] 未知菜单事件: {:?}", event_id);
+            log::info!("❓ [菜单] 未知菜单事件: {:?}", event_id);
+        }
+    }
+}
+
+/// 显示主窗口并打开指定工具
+pub fn open_tool(app_handle: &AppHandle, tool_id: &str) {
+    navigate_or_log(app_handle, Route::Tool(tool_id.to_string()));
+}
+
+/// 导航失败（如未知路由）时只记录日志
+fn navigate_or_log(app_handle: &AppHandle, route: Route) {
+    if let Err(e) = crate::routes::navigate(app_handle, &route) {
+        log::warn!("⚠️ 导航失败 {:?}: {}", route, e);
+    }
+}
+
+/// 对当前聚焦的窗口（没有时为主窗口）执行操作，失败时只记录日志
+fn with_focused_window(app_handle: &AppHandle, f: impl FnOnce(&tauri::WebviewWindow) -> tauri::Result<()>) {
+    if let Some(window) = crate::tool_windows::focused_window(app_handle) {
+        if let Err(e) = f(&window) {
+            log::error!("❌ 窗口操作失败: {}", e);
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,31 @@
+use crate::i18n;
+use crate::mini_mode::{self, MiniModeConfig};
+use tauri::{AppHandle, command};
+
+/// 获取迷你模式配置
+#[command]
+pub async fn get_mini_mode_config(app_handle: AppHandle) -> Result<MiniModeConfig, String> {
+    MiniModeConfig::load(&app_handle)
+        .map_err(|e| i18n::tf("mini.read_config_failed", &[("error", &e)]))
+}
+
+/// 保存迷你模式配置，迷你窗口已打开时立即生效
+#[command]
+pub async fn save_mini_mode_config(app_handle: AppHandle, config: MiniModeConfig) -> Result<(), String> {
+    mini_mode::update(&app_handle, config)
+        .map_err(|e| i18n::tf("mini.save_config_failed", &[("error", &e)]))
+}
+
+/// 在迷你窗口中打开工具（不指定时使用上次的工具）
+#[command]
+pub async fn open_mini_mode(app_handle: AppHandle, tool: Option<String>) -> Result<MiniModeConfig, String> {
+    mini_mode::open(&app_handle, tool.as_deref())
+        .map_err(|e| i18n::tf("mini.open_failed", &[("error", &e)]))
+}
+
+/// 打开或关闭迷你窗口，返回切换后是否打开
+#[command]
+pub async fn toggle_mini_mode(app_handle: AppHandle) -> Result<bool, String> {
+    mini_mode::toggle(&app_handle)
+        .map_err(|e| i18n::tf("mini.toggle_failed", &[("error", &e)]))
+}
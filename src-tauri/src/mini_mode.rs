@@ -0,0 +1,183 @@
+// 迷你模式：在紧凑、置顶、可选鼠标穿透的窗口中打开一个小工具
+// 窗口位置和尺寸由 window-state 插件保存，工具、透明度和鼠标穿透保存在 mini-mode.json
+use crate::i18n;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// 迷你窗口标签
+pub const MINI_LABEL: &str = "mini";
+
+/// 配置变化时发给迷你窗口的事件
+pub const CHANGED_EVENT: &str = "mini-mode-changed";
+
+/// 配置文件（位于配置目录）
+const CONFIG_FILE: &str = "mini-mode.json";
+
+/// 适合在迷你模式中使用的工具
+pub const SUGGESTED_TOOLS: &[&str] = &["stopwatch", "countdown-timer", "color-picker", "timestamp-convert"];
+
+/// 迷你窗口的默认尺寸
+const DEFAULT_SIZE: (f64, f64) = (320.0, 420.0);
+
+/// 迷你窗口的最小尺寸
+const MIN_SIZE: (f64, f64) = (200.0, 120.0);
+
+/// 迷你模式配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MiniModeConfig {
+    /// 迷你窗口中打开的工具
+    pub tool: String,
+    /// 窗口内容的不透明度（0.2 - 1.0）
+    pub opacity: f64,
+    /// 鼠标穿透：点击会落到下面的窗口上
+    pub click_through: bool,
+}
+
+impl Default for MiniModeConfig {
+    fn default() -> Self {
+        Self {
+            tool: SUGGESTED_TOOLS[0].to_string(),
+            opacity: 1.0,
+            click_through: false,
+        }
+    }
+}
+
+impl MiniModeConfig {
+    /// 从配置目录加载
+    pub fn load(app_handle: &AppHandle) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_path = crate::app_paths::config_dir(app_handle)?.join(CONFIG_FILE);
+        if config_path.exists() {
+            let content = fs::read_to_string(&config_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// 保存到配置目录
+    pub fn save(&self, app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        self.validate()?;
+        let config_dir = crate::app_paths::config_dir(app_handle)?;
+        fs::create_dir_all(&config_dir)?;
+        fs::write(config_dir.join(CONFIG_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 检查工具和透明度
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if crate::routes::find_tool(&self.tool).is_none() {
+            return Err(i18n::tf("tools.unknown", &[("tool", &self.tool)]).into());
+        }
+        if !(0.2..=1.0).contains(&self.opacity) {
+            return Err(i18n::t("mini.invalid_opacity").into());
+        }
+        Ok(())
+    }
+
+    /// 工具页面的路径
+    fn path(&self) -> String {
+        format!("tools/{}/", self.tool)
+    }
+}
+
+/// 设置置顶、无边框和鼠标穿透，并把配置发给页面（页面负责应用透明度）
+fn apply(window: &WebviewWindow, config: &MiniModeConfig) -> Result<(), Box<dyn std::error::Error>> {
+    window.set_always_on_top(true)?;
+    window.set_decorations(false)?;
+    window.set_ignore_cursor_events(config.click_through)?;
+    window.emit_to(MINI_LABEL, CHANGED_EVENT, config)?;
+    Ok(())
+}
+
+/// 迷你窗口是否已打开
+pub fn is_open(app_handle: &AppHandle) -> bool {
+    app_handle.get_webview_window(MINI_LABEL).is_some()
+}
+
+/// 打开迷你窗口；tool 为 None 时使用上次的工具
+pub fn open(app_handle: &AppHandle, tool: Option<&str>) -> Result<MiniModeConfig, Box<dyn std::error::Error>> {
+    let mut config = MiniModeConfig::load(app_handle)?;
+    if let Some(tool) = tool {
+        if tool != config.tool {
+            config.tool = tool.to_string();
+            config.save(app_handle)?;
+        }
+    }
+    config.validate()?;
+
+    let window = match app_handle.get_webview_window(MINI_LABEL) {
+        Some(window) => {
+            window.navigate(crate::routes::app_origin(app_handle).join(&config.path())?)?;
+            window
+        }
+        None => {
+            let route = crate::routes::find_tool(&config.tool).ok_or_else(|| i18n::tf("tools.unknown", &[("tool", &config.tool)]))?;
+            let builder = WebviewWindowBuilder::new(app_handle, MINI_LABEL, WebviewUrl::App(config.path().into()))
+                .title(i18n::tf("mini.window_title", &[("tool", &route.name)]))
+                .inner_size(DEFAULT_SIZE.0, DEFAULT_SIZE.1)
+                .min_inner_size(MIN_SIZE.0, MIN_SIZE.1)
+                .skip_taskbar(true);
+            // macOS 上透明窗口需要私有 API，只在其他平台开启
+            #[cfg(not(target_os = "macos"))]
+            let builder = builder.transparent(true);
+            let window = builder.build()?;
+            #[cfg(not(target_os = "macos"))]
+            let _ = window.remove_menu();
+            window
+        }
+    };
+
+    apply(&window, &config)?;
+    window.show()?;
+    if !config.click_through {
+        window.set_focus()?;
+    }
+    log::info!("📌 迷你模式已打开: {}", config.tool);
+    Ok(config)
+}
+
+/// 关闭迷你窗口
+pub fn close(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(window) = app_handle.get_webview_window(MINI_LABEL) {
+        window.close()?;
+        log::info!("📌 迷你模式已关闭");
+    }
+    Ok(())
+}
+
+/// 打开或关闭迷你窗口，返回切换后是否打开
+pub fn toggle(app_handle: &AppHandle) -> Result<bool, Box<dyn std::error::Error>> {
+    if is_open(app_handle) {
+        close(app_handle)?;
+        Ok(false)
+    } else {
+        open(app_handle, None)?;
+        Ok(true)
+    }
+}
+
+/// 保存配置，迷你窗口已打开时立即生效
+pub fn update(app_handle: &AppHandle, config: MiniModeConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let previous = MiniModeConfig::load(app_handle)?;
+    config.save(app_handle)?;
+
+    if let Some(window) = app_handle.get_webview_window(MINI_LABEL) {
+        if previous.tool != config.tool {
+            window.navigate(crate::routes::app_origin(app_handle).join(&config.path())?)?;
+        }
+        apply(&window, &config)?;
+    }
+    Ok(())
+}
+
+/// 切换鼠标穿透，返回切换后的状态
+pub fn toggle_click_through(app_handle: &AppHandle) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut config = MiniModeConfig::load(app_handle)?;
+    config.click_through = !config.click_through;
+    update(app_handle, config.clone())?;
+    log::info!("📌 迷你模式鼠标穿透: {}", config.click_through);
+    Ok(config.click_through)
+}
@@ -0,0 +1,311 @@
+// 命令权限：自定义命令按能力分组，在调用前根据来源页面和窗口检查
+use crate::i18n;
+use tauri::ipc::Invoke;
+use tauri::{Manager, Runtime, Webview};
+
+/// 命令所属的能力
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// 页面导航和日志，任何页面都可以调用
+    App,
+    /// 通知、快捷键、收藏等桌面集成
+    Desktop,
+    /// 检查和安装更新
+    Updates,
+    /// 设置、配置文件、备份和同步
+    Config,
+    /// 需要访问网络的工具
+    NetworkTools,
+    /// 读写本地文件
+    Files,
+    /// 剪贴板历史
+    Clipboard,
+    /// 密钥保险箱
+    Vault,
+}
+
+impl Capability {
+    pub fn name(self) -> &'static str {
+        match self {
+            Capability::App => "app",
+            Capability::Desktop => "desktop",
+            Capability::Updates => "updates",
+            Capability::Config => "config",
+            Capability::NetworkTools => "network-tools",
+            Capability::Files => "files",
+            Capability::Clipboard => "clipboard",
+            Capability::Vault => "vault",
+        }
+    }
+
+    /// 非应用页面（例如主窗口中打开的外部网站）是否可以使用
+    fn allows_remote(self) -> bool {
+        matches!(self, Capability::App)
+    }
+}
+
+/// 命令与能力的对应关系；未列出的命令一律拒绝
+const COMMANDS: &[(&str, Capability)] = &[
+    ("greet", Capability::App),
+    ("log_message", Capability::App),
+    ("navigate_to_page", Capability::App),
+    ("navigate", Capability::App),
+    ("get_routes", Capability::App),
+    ("app_ready", Capability::App),
+    ("send_system_notification", Capability::Desktop),
+    ("quit_app", Capability::Desktop),
+    ("take_deep_link", Capability::Desktop),
+    ("parse_deep_link", Capability::Desktop),
+    ("confirm_deep_link_file", Capability::Desktop),
+    ("get_app_info", Capability::Desktop),
+    ("get_autostart_status", Capability::Desktop),
+    ("record_recent_tool", Capability::Desktop),
+    ("save_favorite_tools", Capability::Desktop),
+    ("open_tool_window", Capability::Desktop),
+    ("list_tool_windows", Capability::Desktop),
+    ("get_mini_mode_config", Capability::Desktop),
+    ("save_mini_mode_config", Capability::Desktop),
+    ("open_mini_mode", Capability::Desktop),
+    ("toggle_mini_mode", Capability::Desktop),
+    ("get_theme_state", Capability::Desktop),
+    ("get_shortcuts", Capability::Desktop),
+    ("validate_shortcuts", Capability::Desktop),
+    ("save_shortcuts", Capability::Desktop),
+    ("reload_shortcuts", Capability::Desktop),
+    ("check_for_updates", Capability::Updates),
+    ("apply_hotfix_update", Capability::Updates),
+    ("download_major_update", Capability::Updates),
+    ("install_major_update", Capability::Updates),
+    ("get_current_version", Capability::Updates),
+    ("get_update_config", Capability::Updates),
+    ("update_update_config", Capability::Updates),
+    ("ignore_version", Capability::Updates),
+    ("get_update_history", Capability::Updates),
+    ("clear_update_history", Capability::Updates),
+    ("restart_app", Capability::Updates),
+    ("check_admin_privileges", Capability::Updates),
+    ("request_admin_restart", Capability::Updates),
+    ("get_update_progress", Capability::Updates),
+    ("cancel_update", Capability::Updates),
+    ("import_config", Capability::Config),
+    ("reset_config", Capability::Config),
+    ("backup_config", Capability::Config),
+    ("list_config_backups", Capability::Config),
+    ("restore_config_backup", Capability::Config),
+    ("get_user_settings", Capability::Config),
+    ("save_user_settings", Capability::Config),
+    ("get_tools_config", Capability::Config),
+    ("save_tools_config", Capability::Config),
+    ("clear_cache", Capability::Config),
+    ("get_config_dir", Capability::Config),
+    ("get_cache_dir", Capability::Config),
+    ("get_log_dir", Capability::Config),
+    ("get_splash_config", Capability::Config),
+    ("save_splash_config", Capability::Config),
+    ("get_log_config", Capability::Config),
+    ("save_log_config", Capability::Config),
+    ("set_log_level", Capability::Config),
+    ("list_profiles", Capability::Config),
+    ("get_active_profile", Capability::Config),
+    ("create_profile", Capability::Config),
+    ("clone_profile", Capability::Config),
+    ("switch_profile", Capability::Config),
+    ("delete_profile", Capability::Config),
+    ("get_sync_config", Capability::Config),
+    ("save_sync_config", Capability::Config),
+    ("get_sync_status", Capability::Config),
+    ("sync_now", Capability::Config),
+    ("resolve_sync_conflict", Capability::Config),
+    ("open_external_link", Capability::NetworkTools),
+    ("confirm_external_link", Capability::NetworkTools),
+    ("download_file", Capability::NetworkTools),
+    ("save_debug_info", Capability::Files),
+    ("create_diagnostic_bundle", Capability::Files),
+    ("export_config", Capability::Files),
+    ("export_profile", Capability::Files),
+    ("open_config_dir", Capability::Files),
+    ("get_pending_crash_reports", Capability::Files),
+    ("get_crash_report", Capability::Files),
+    ("export_crash_report", Capability::Files),
+    ("keep_crash_report", Capability::Files),
+    ("delete_crash_report", Capability::Files),
+    ("get_clipboard_history", Capability::Clipboard),
+    ("search_clipboard_history", Capability::Clipboard),
+    ("copy_clipboard_entry", Capability::Clipboard),
+    ("pin_clipboard_entry", Capability::Clipboard),
+    ("delete_clipboard_entry", Capability::Clipboard),
+    ("clear_clipboard_history", Capability::Clipboard),
+    ("get_clipboard_image", Capability::Clipboard),
+    ("get_clipboard_history_config", Capability::Clipboard),
+    ("save_clipboard_history_config", Capability::Clipboard),
+    ("set_clipboard_history_paused", Capability::Clipboard),
+    ("vault_status", Capability::Vault),
+    ("vault_initialize", Capability::Vault),
+    ("vault_unlock", Capability::Vault),
+    ("vault_lock", Capability::Vault),
+    ("vault_change_password", Capability::Vault),
+    ("vault_set_auto_lock", Capability::Vault),
+    ("vault_list_secrets", Capability::Vault),
+    ("vault_set_secret", Capability::Vault),
+    ("vault_delete_secret", Capability::Vault),
+    ("vault_copy_secret", Capability::Vault),
+    ("vault_http_request", Capability::Vault),
+];
+
+/// 敏感命令：只允许主窗口中的应用页面调用
+const SENSITIVE_COMMANDS: &[&str] = &[
+    "restart_app",
+    "request_admin_restart",
+    "apply_hotfix_update",
+    "install_major_update",
+    "import_config",
+    "reset_config",
+    "restore_config_backup",
+    "open_config_dir",
+    "quit_app",
+];
+
+/// 查找命令所属的能力
+pub fn capability_of(command: &str) -> Option<Capability> {
+    COMMANDS.iter().find(|(name, _)| *name == command).map(|(_, capability)| *capability)
+}
+
+/// 已注册但没有分配能力的命令（这些命令的调用会被一律拒绝）
+pub fn unassigned<'a>(registered: &[&'a str]) -> Vec<&'a str> {
+    registered.iter().copied().filter(|command| capability_of(command).is_none()).collect()
+}
+
+/// 调用方是否为应用自身的页面（打包资源或开发服务器）
+///
+/// 这里检查的是 webview 顶层页面的地址，不是发起调用的框架；框架内的调用由 `from_top_level` 另行拒绝
+fn is_app_origin<R: Runtime>(webview: &Webview<R>) -> bool {
+    let Ok(url) = webview.url() else {
+        return false;
+    };
+    match (url.scheme(), url.host_str()) {
+        // macOS / Linux 使用 tauri://localhost，Windows 使用 http(s)://tauri.localhost
+        ("tauri", Some("localhost")) => true,
+        ("http" | "https", Some("tauri.localhost")) => true,
+        _ => {
+            cfg!(debug_assertions)
+                && matches!(
+                    &webview.app_handle().config().build.dev_url,
+                    Some(dev_url) if dev_url.origin() == url.origin()
+                )
+        }
+    }
+}
+
+/// 两个地址是否同源（自定义协议的 origin 是不透明的，不能直接比较 `Url::origin`）
+fn same_origin(a: &url::Url, b: &url::Url) -> bool {
+    a.scheme() == b.scheme() && a.host_str() == b.host_str() && a.port_or_known_default() == b.port_or_known_default()
+}
+
+/// 调用是否来自顶层页面
+///
+/// 通过自定义协议发起的调用带有发起框架的 Origin 请求头，与顶层页面不同源说明来自 iframe 等子框架。
+/// 没有该请求头时（postMessage 回退通道）无法区分，按顶层页面处理；应用页面的 CSP 禁止嵌入框架
+fn from_top_level(origin_header: Option<&str>, top_level: &url::Url) -> bool {
+    match origin_header.map(url::Url::parse) {
+        None => true,
+        Some(Ok(origin)) => same_origin(&origin, top_level),
+        Some(Err(_)) => false,
+    }
+}
+
+/// 检查调用是否被允许，返回拒绝原因
+pub fn check<R: Runtime>(command: &str, webview: &Webview<R>, origin_header: Option<&str>) -> Result<Capability, String> {
+    let capability = capability_of(command)
+        .ok_or_else(|| i18n::tf("permission.unassigned", &[("command", &command)]))?;
+
+    let top_level = webview.url().ok();
+    if !top_level.is_some_and(|url| from_top_level(origin_header, &url)) {
+        return Err(i18n::t("permission.frame_denied"));
+    }
+
+    if !is_app_origin(webview) && !capability.allows_remote() {
+        return Err(i18n::tf("permission.capability_denied", &[("capability", &capability.name())]));
+    }
+    if SENSITIVE_COMMANDS.contains(&command) && webview.window().label() != crate::tool_windows::MAIN_LABEL {
+        return Err(i18n::tf("permission.main_only", &[("command", &command)]));
+    }
+    Ok(capability)
+}
+
+/// 在命令处理器外加一层权限检查
+pub fn guard<R, F>(handler: F) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    move |invoke: Invoke<R>| {
+        let command = invoke.message.command().to_string();
+        let origin_header = invoke.message.headers().get(tauri::http::header::ORIGIN).and_then(|v| v.to_str().ok());
+        if let Err(reason) = check(&command, invoke.message.webview_ref(), origin_header) {
+            let url = invoke.message.webview_ref().url().map(|url| url.to_string()).unwrap_or_default();
+            log::warn!("🚫 拒绝命令调用: {} (来源: {}): {}", command, url, reason);
+            invoke.resolver.reject(i18n::tf("permission.rejected", &[("reason", &reason)]));
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(value: &str) -> url::Url {
+        url::Url::parse(value).unwrap()
+    }
+
+    #[test]
+    fn capability_of_finds_assigned_commands() {
+        assert_eq!(capability_of("navigate_to_page"), Some(Capability::App));
+        assert_eq!(capability_of("import_config"), Some(Capability::Config));
+        assert_eq!(capability_of("no_such_command"), None);
+    }
+
+    #[test]
+    fn every_registered_command_has_a_capability() {
+        assert!(crate::COMMAND_NAMES.contains(&"sync_now"));
+        assert_eq!(unassigned(crate::COMMAND_NAMES), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn every_assigned_command_is_registered() {
+        for (command, _) in COMMANDS {
+            assert!(crate::COMMAND_NAMES.contains(command), "命令 {} 已分配能力但没有注册", command);
+        }
+        for command in SENSITIVE_COMMANDS {
+            assert!(capability_of(command).is_some(), "敏感命令 {} 没有分配能力", command);
+        }
+    }
+
+    #[test]
+    fn commands_are_assigned_once() {
+        for (index, (command, _)) in COMMANDS.iter().enumerate() {
+            assert!(!COMMANDS[..index].iter().any(|(other, _)| other == command), "命令 {} 重复分配", command);
+        }
+    }
+
+    #[test]
+    fn only_app_capability_allows_remote_pages() {
+        assert!(Capability::App.allows_remote());
+        assert!(!Capability::Config.allows_remote());
+        assert!(!Capability::Vault.allows_remote());
+    }
+
+    #[test]
+    fn calls_from_frames_are_rejected() {
+        let app = url("tauri://localhost/tools/json-formatter");
+        assert!(from_top_level(None, &app));
+        assert!(from_top_level(Some("tauri://localhost"), &app));
+        assert!(!from_top_level(Some("https://example.com"), &app));
+        assert!(!from_top_level(Some("null"), &app));
+
+        let windows_app = url("http://tauri.localhost/");
+        assert!(from_top_level(Some("http://tauri.localhost"), &windows_app));
+        assert!(!from_top_level(Some("http://tauri.localhost:8080"), &windows_app));
+    }
+}
@@ -0,0 +1,61 @@
+use crate::config_profiles::{self, ProfileInfo};
+use crate::i18n;
+use tauri::{AppHandle, command};
+
+/// 列出配置方案
+#[command]
+pub async fn list_profiles(app_handle: AppHandle) -> Result<Vec<ProfileInfo>, String> {
+    config_profiles::list_profiles(&app_handle)
+        .map_err(|e| i18n::tf("profile.list_failed", &[("error", &e)]))
+}
+
+/// 获取当前配置方案
+#[command]
+pub async fn get_active_profile(app_handle: AppHandle) -> Result<String, String> {
+    Ok(config_profiles::active_profile(&app_handle)
+        .unwrap_or_else(|| config_profiles::DEFAULT_PROFILE.to_string()))
+}
+
+/// 创建配置方案
+#[command]
+pub async fn create_profile(app_handle: AppHandle, name: String) -> Result<String, String> {
+    config_profiles::create_profile(&app_handle, &name)
+        .map_err(|e| i18n::tf("profile.create_failed", &[("error", &e)]))?;
+    Ok(i18n::tf("profile.created", &[("name", &name)]))
+}
+
+/// 复制配置方案
+#[command]
+pub async fn clone_profile(app_handle: AppHandle, source: String, name: String) -> Result<String, String> {
+    config_profiles::clone_profile(&app_handle, &source, &name)
+        .map_err(|e| i18n::tf("profile.clone_failed", &[("error", &e)]))?;
+    Ok(i18n::tf("profile.created", &[("name", &name)]))
+}
+
+/// 切换配置方案（无需重启）
+#[command]
+pub async fn switch_profile(app_handle: AppHandle, name: String) -> Result<String, String> {
+    config_profiles::switch_profile(&app_handle, &name)
+        .map_err(|e| i18n::tf("profile.switch_failed", &[("error", &e)]))?;
+
+    // 不同方案的 auto_start、语言、主题和快捷键可能不同
+    crate::config_init::apply_settings(&app_handle);
+
+    Ok(i18n::tf("profile.switched", &[("name", &name)]))
+}
+
+/// 删除配置方案
+#[command]
+pub async fn delete_profile(app_handle: AppHandle, name: String) -> Result<String, String> {
+    config_profiles::delete_profile(&app_handle, &name)
+        .map_err(|e| i18n::tf("profile.delete_failed", &[("error", &e)]))?;
+    Ok(i18n::tf("profile.deleted", &[("name", &name)]))
+}
+
+/// 导出配置方案
+#[command]
+pub async fn export_profile(app_handle: AppHandle, name: String, export_path: String) -> Result<String, String> {
+    config_profiles::export_profile(&app_handle, &name, &export_path)
+        .map_err(|e| i18n::tf("profile.export_failed", &[("error", &e)]))?;
+    Ok(i18n::tf("profile.exported", &[("name", &name)]))
+}
@@ -0,0 +1,154 @@
+// 路由表：前端已知的页面、分类和工具（由 scripts/generate-routes.mjs 生成 routes.json），以及基于事件的导航
+use crate::i18n;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// 前端监听的导航事件
+pub const NAVIGATE_EVENT: &str = "navigate";
+
+/// 静态页面路由
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageRoute {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+}
+
+/// 分类路由
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryRoute {
+    pub id: String,
+    pub name: String,
+}
+
+/// 工具路由
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolRoute {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+}
+
+/// 路由表
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RouteTable {
+    pub pages: Vec<PageRoute>,
+    pub categories: Vec<CategoryRoute>,
+    pub tools: Vec<ToolRoute>,
+}
+
+/// 导航目标
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "lowercase")]
+pub enum Route {
+    Page(String),
+    Category(String),
+    Tool(String),
+}
+
+impl Route {
+    pub fn home() -> Self {
+        Route::Page("home".to_string())
+    }
+}
+
+/// 发送给前端的导航事件
+#[derive(Debug, Clone, Serialize)]
+pub struct NavigateEvent {
+    pub route: Route,
+    pub path: String,
+    pub title: String,
+}
+
+static ROUTES: OnceLock<RouteTable> = OnceLock::new();
+
+/// 内置路由表
+pub fn table() -> &'static RouteTable {
+    ROUTES.get_or_init(|| {
+        const ROUTES_JSON: &str = include_str!("../routes.json");
+        serde_json::from_str(ROUTES_JSON).unwrap_or_else(|e| {
+            log::error!("❌ 解析内置路由表失败: {}", e);
+            RouteTable::default()
+        })
+    })
+}
+
+/// 按 ID 查找页面
+pub fn find_page(id: &str) -> Option<&'static PageRoute> {
+    table().pages.iter().find(|page| page.id == id)
+}
+
+/// 按 ID 查找分类
+pub fn find_category(id: &str) -> Option<&'static CategoryRoute> {
+    table().categories.iter().find(|category| category.id == id)
+}
+
+/// 按 ID 查找工具
+pub fn find_tool(id: &str) -> Option<&'static ToolRoute> {
+    table().tools.iter().find(|tool| tool.id == id)
+}
+
+/// 解析导航目标，未知路由返回错误
+pub fn resolve(route: &Route) -> Result<NavigateEvent, Box<dyn std::error::Error>> {
+    let (path, title) = match route {
+        Route::Page(id) => {
+            let page = find_page(id).ok_or_else(|| i18n::tf("navigation.unknown_page", &[("id", &id)]))?;
+            (page.path.clone(), page.name.clone())
+        }
+        Route::Category(id) => {
+            let category = find_category(id).ok_or_else(|| i18n::tf("navigation.unknown_category", &[("id", &id)]))?;
+            (format!("/category/{}/", category.id), category.name.clone())
+        }
+        Route::Tool(id) => {
+            let tool = find_tool(id).ok_or_else(|| i18n::tf("navigation.unknown_tool", &[("id", &id)]))?;
+            (format!("/tools/{}/", tool.id), tool.name.clone())
+        }
+    };
+
+    Ok(NavigateEvent { route: route.clone(), path, title })
+}
+
+/// 应用页面的源（开发模式为 devUrl，否则为 Tauri 自定义协议）
+pub fn app_origin(app_handle: &AppHandle) -> url::Url {
+    if tauri::is_dev() {
+        if let Some(dev_url) = &app_handle.config().build.dev_url {
+            return dev_url.clone();
+        }
+    }
+
+    let origin = if cfg!(windows) { "http://tauri.localhost" } else { "tauri://localhost" };
+    url::Url::parse(origin).expect("内置的应用源地址必须有效")
+}
+
+/// 比较协议、主机和端口（`tauri://` 这类自定义协议的 `Url::origin` 是不透明的，不能直接比较）
+fn same_origin(a: &url::Url, b: &url::Url) -> bool {
+    a.scheme() == b.scheme() && a.host_str() == b.host_str() && a.port_or_known_default() == b.port_or_known_default()
+}
+
+/// 窗口当前是否停留在应用以外的页面
+fn is_external(app_handle: &AppHandle, window: &tauri::WebviewWindow) -> bool {
+    match window.url() {
+        Ok(current) => !same_origin(&current, &app_origin(app_handle)),
+        Err(_) => false,
+    }
+}
+
+/// 在主窗口中导航到指定路由
+pub fn navigate(app_handle: &AppHandle, route: &Route) -> Result<(), Box<dyn std::error::Error>> {
+    let event = resolve(route)?;
+    let window = app_handle.get_webview_window(crate::tool_windows::MAIN_LABEL).ok_or_else(|| i18n::t("window.main_not_found"))?;
+
+    crate::tray::show_main_window(app_handle);
+
+    // 外部页面上没有前端路由，直接加载应用内的地址
+    if is_external(app_handle, &window) {
+        log::info!("🧭 当前为外部页面，返回应用: {}", event.path);
+        window.navigate(app_origin(app_handle).join(&event.path)?)?;
+        return Ok(());
+    }
+
+    log::info!("🧭 导航到: {}", event.path);
+    app_handle.emit_to(window.label(), NAVIGATE_EVENT, &event)?;
+    Ok(())
+}
@@ -0,0 +1,738 @@
+use serde::{Deserialize, Serialize};
+use crate::i18n;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, OsRng, Payload};
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305, XNonce};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+/// 保险库文件名（位于配置目录）
+pub const VAULT_FILE_NAME: &str = "secrets-vault.json";
+
+/// 当前保险库文件格式版本
+const VAULT_VERSION: u32 = 1;
+
+/// 用于校验主密码的固定明文
+const VERIFIER_PLAINTEXT: &[u8] = b"utilx-secrets-vault";
+
+/// 默认空闲自动锁定时间（分钟）
+const DEFAULT_AUTO_LOCK_MINUTES: u32 = 15;
+
+/// 派生出的加密密钥，离开作用域时清零
+pub type VaultKey = Zeroizing<[u8; 32]>;
+
+/// 密钥派生参数（Argon2id）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub algorithm: String,
+    pub salt: String,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    /// 使用随机盐生成新的派生参数
+    pub fn generate() -> Self {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        Self {
+            algorithm: "argon2id".to_string(),
+            salt: BASE64.encode(salt),
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// 加密后的数据（XChaCha20-Poly1305）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SealedValue {
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// 保险库中的一条密钥
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultEntry {
+    #[serde(flatten)]
+    pub sealed: SealedValue,
+    pub description: Option<String>,
+    /// 允许把该密钥发送到的主机；为空时不能用于 HTTP 请求
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 返回给前端的密钥信息（不含明文）
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultEntryInfo {
+    pub name: String,
+    pub description: Option<String>,
+    pub allowed_hosts: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 保险库状态
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultStatus {
+    pub initialized: bool,
+    pub unlocked: bool,
+    pub auto_lock_minutes: u32,
+    pub entry_count: usize,
+}
+
+/// 保险库文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VaultFile {
+    pub version: u32,
+    pub kdf: KdfParams,
+    pub verifier: SealedValue,
+    pub auto_lock_minutes: u32,
+    pub entries: BTreeMap<String, VaultEntry>,
+}
+
+impl VaultFile {
+    /// 从配置目录加载保险库，不存在时返回 None
+    pub fn load(app_handle: &AppHandle) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        let vault_path = crate::app_paths::config_dir(app_handle)?.join(VAULT_FILE_NAME);
+        if !vault_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&vault_path)?;
+        let vault: VaultFile = serde_json::from_str(&content)?;
+        if vault.version > VAULT_VERSION {
+            return Err(i18n::tf("vault.unsupported_version", &[("version", &vault.version)]).into());
+        }
+        Ok(Some(vault))
+    }
+
+    /// 保存保险库到配置目录
+    ///
+    /// 先写入临时文件并刷到磁盘再重命名，写入中途崩溃或断电时旧文件保持完整
+    pub fn save(&self, app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        let config_dir = crate::app_paths::config_dir(app_handle)?;
+        fs::create_dir_all(&config_dir)?;
+
+        let vault_path = config_dir.join(VAULT_FILE_NAME);
+        let temp_path = config_dir.join(format!("{}.tmp", VAULT_FILE_NAME));
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp_path, &vault_path)?;
+
+        // 重命名本身也要落盘（Windows 不能打开目录，跳过）
+        #[cfg(unix)]
+        if let Ok(dir) = fs::File::open(&config_dir) {
+            let _ = dir.sync_all();
+        }
+        Ok(())
+    }
+
+    /// 使用主密码创建空的保险库，返回保险库和派生出的密钥
+    fn create(master_password: &str, kdf: KdfParams) -> Result<(Self, VaultKey), Box<dyn std::error::Error>> {
+        let key = derive_key(master_password, &kdf)?;
+        let vault = VaultFile {
+            version: VAULT_VERSION,
+            verifier: seal(&key, VERIFIER_PLAINTEXT, b"verifier")?,
+            kdf,
+            auto_lock_minutes: DEFAULT_AUTO_LOCK_MINUTES,
+            entries: BTreeMap::new(),
+        };
+        Ok((vault, key))
+    }
+
+    /// 校验主密码，正确时返回派生出的密钥
+    fn unlock_key(&self, master_password: &str) -> Result<VaultKey, Box<dyn std::error::Error>> {
+        let key = derive_key(master_password, &self.kdf)?;
+        let verifier = open(&key, &self.verifier, b"verifier").map_err(|_| i18n::t("vault.wrong_password"))?;
+        if verifier.as_slice() != VERIFIER_PLAINTEXT {
+            return Err(i18n::t("vault.wrong_password").into());
+        }
+        Ok(key)
+    }
+
+    /// 使用新的派生参数和主密码重新加密所有条目，返回新密钥
+    fn rekey(&mut self, old_password: &str, new_password: &str, kdf: KdfParams) -> Result<VaultKey, Box<dyn std::error::Error>> {
+        let old_key = self.unlock_key(old_password).map_err(|_| i18n::t("vault.wrong_old_password"))?;
+        let new_key = derive_key(new_password, &kdf)?;
+
+        for (name, entry) in self.entries.iter_mut() {
+            let plaintext = open(&old_key, &entry.sealed, name.as_bytes())?;
+            entry.sealed = seal(&new_key, &plaintext, name.as_bytes())?;
+        }
+        self.verifier = seal(&new_key, VERIFIER_PLAINTEXT, b"verifier")?;
+        self.kdf = kdf;
+        Ok(new_key)
+    }
+}
+
+/// 密钥值的加盐哈希，锁定后仍保留在内存中，用来判断剪贴板内容是否是保险库中的值
+///
+/// 盐每次启动随机生成且不写入磁盘
+pub struct SecretFingerprints {
+    salt: [u8; 32],
+    hashes: HashMap<String, [u8; 32]>,
+}
+
+impl SecretFingerprints {
+    fn new() -> Self {
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        Self { salt, hashes: HashMap::new() }
+    }
+
+    /// 解密所有条目并计算哈希
+    fn from_vault(vault: &VaultFile, key: &VaultKey) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut fingerprints = Self::new();
+        for (name, entry) in &vault.entries {
+            let plaintext = open(key, &entry.sealed, name.as_bytes())?;
+            fingerprints.insert(name, &plaintext);
+        }
+        Ok(fingerprints)
+    }
+
+    fn hash(&self, value: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.salt);
+        hasher.update(value);
+        hasher.finalize().into()
+    }
+
+    fn insert(&mut self, name: &str, value: &[u8]) {
+        let hash = self.hash(value);
+        self.hashes.insert(name.to_string(), hash);
+    }
+
+    fn remove(&mut self, name: &str) {
+        self.hashes.remove(name);
+    }
+
+    fn contains(&self, value: &[u8]) -> bool {
+        let hash = self.hash(value);
+        self.hashes.values().any(|known| *known == hash)
+    }
+}
+
+/// 会话状态：只在内存中保存派生密钥
+pub struct VaultSession {
+    key: Option<VaultKey>,
+    last_activity: Instant,
+    auto_lock_after: Duration,
+    /// 本次运行中解锁后得到的密钥哈希；从未解锁或保险库文件被替换后为 None
+    fingerprints: Option<SecretFingerprints>,
+}
+
+impl Default for VaultSession {
+    fn default() -> Self {
+        Self {
+            key: None,
+            last_activity: Instant::now(),
+            auto_lock_after: Duration::from_secs(DEFAULT_AUTO_LOCK_MINUTES as u64 * 60),
+            fingerprints: None,
+        }
+    }
+}
+
+impl VaultSession {
+    /// 当前是否已解锁
+    pub fn is_unlocked(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// 记录一次活动，推迟自动锁定
+    pub fn touch(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// 锁定保险库，丢弃内存中的密钥
+    pub fn lock(&mut self) {
+        self.key = None;
+    }
+
+    /// 空闲超过自动锁定时间时锁定，返回是否因此锁定
+    fn lock_if_idle(&mut self) -> bool {
+        let expired = self.is_unlocked()
+            && !self.auto_lock_after.is_zero()
+            && self.last_activity.elapsed() >= self.auto_lock_after;
+        if expired {
+            self.lock();
+        }
+        expired
+    }
+
+    fn key(&mut self) -> Result<&VaultKey, Box<dyn std::error::Error>> {
+        self.last_activity = Instant::now();
+        self.key.as_ref().ok_or_else(|| i18n::t("vault.is_locked").into())
+    }
+}
+
+/// 保险库状态（由 Tauri 管理）
+pub struct SecretsVaultState(pub Mutex<VaultSession>);
+
+/// 从主密码派生加密密钥
+pub fn derive_key(password: &str, kdf: &KdfParams) -> Result<VaultKey, Box<dyn std::error::Error>> {
+    if kdf.algorithm != "argon2id" {
+        return Err(i18n::tf("vault.unsupported_kdf", &[("algorithm", &kdf.algorithm)]).into());
+    }
+
+    let salt = BASE64.decode(&kdf.salt)?;
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(32))
+        .map_err(|e| i18n::tf("vault.invalid_kdf_params", &[("error", &e)]))?;
+
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), &salt, key.as_mut())
+        .map_err(|e| i18n::tf("vault.kdf_failed", &[("error", &e)]))?;
+    Ok(key)
+}
+
+/// 加密数据，`aad` 会参与认证但不加密
+pub fn seal(key: &VaultKey, plaintext: &[u8], aad: &[u8]) -> Result<SealedValue, Box<dyn std::error::Error>> {
+    let cipher = XChaCha20Poly1305::new(key.as_ref().into());
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, Payload { msg: plaintext, aad })
+        .map_err(|_| i18n::t("vault.encrypt_failed"))?;
+
+    Ok(SealedValue {
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+/// 解密数据，密钥错误或数据被篡改时返回错误
+pub fn open(key: &VaultKey, sealed: &SealedValue, aad: &[u8]) -> Result<Zeroizing<Vec<u8>>, Box<dyn std::error::Error>> {
+    let nonce = BASE64.decode(&sealed.nonce)?;
+    if nonce.len() != 24 {
+        return Err(i18n::t("vault.invalid_nonce").into());
+    }
+    let ciphertext = BASE64.decode(&sealed.ciphertext)?;
+
+    let cipher = XChaCha20Poly1305::new(key.as_ref().into());
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(&nonce), Payload { msg: &ciphertext, aad })
+        .map_err(|_| i18n::t("vault.decrypt_failed"))?;
+    Ok(Zeroizing::new(plaintext))
+}
+
+/// 获取保险库状态
+pub fn status(app_handle: &AppHandle, state: &SecretsVaultState) -> Result<VaultStatus, Box<dyn std::error::Error>> {
+    let vault = VaultFile::load(app_handle)?;
+    let session = state.0.lock().unwrap();
+
+    Ok(VaultStatus {
+        initialized: vault.is_some(),
+        unlocked: session.is_unlocked(),
+        auto_lock_minutes: vault.as_ref().map(|v| v.auto_lock_minutes).unwrap_or(DEFAULT_AUTO_LOCK_MINUTES),
+        entry_count: vault.as_ref().map(|v| v.entries.len()).unwrap_or(0),
+    })
+}
+
+/// 使用主密码创建新的保险库，并保持解锁
+pub fn initialize(app_handle: &AppHandle, state: &SecretsVaultState, master_password: &str) -> Result<(), Box<dyn std::error::Error>> {
+    // 读取、修改和保存保险库文件期间一直持有会话锁，避免并发的修改互相覆盖
+    let mut session = state.0.lock().unwrap();
+    if VaultFile::load(app_handle)?.is_some() {
+        return Err(i18n::t("vault.already_exists").into());
+    }
+    if master_password.chars().count() < 8 {
+        return Err(i18n::t("vault.password_too_short").into());
+    }
+
+    let (vault, key) = VaultFile::create(master_password, KdfParams::generate())?;
+    vault.save(app_handle)?;
+
+    session.auto_lock_after = Duration::from_secs(vault.auto_lock_minutes as u64 * 60);
+    session.key = Some(key);
+    session.fingerprints = Some(SecretFingerprints::new());
+    session.touch();
+
+    log::info!("🔐 保险库已创建");
+    Ok(())
+}
+
+/// 使用主密码解锁保险库
+pub fn unlock(app_handle: &AppHandle, state: &SecretsVaultState, master_password: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let vault = VaultFile::load(app_handle)?.ok_or_else(|| i18n::t("vault.not_created"))?;
+    let key = vault.unlock_key(master_password)?;
+    let fingerprints = SecretFingerprints::from_vault(&vault, &key)?;
+
+    let mut session = state.0.lock().unwrap();
+    session.auto_lock_after = Duration::from_secs(vault.auto_lock_minutes as u64 * 60);
+    session.key = Some(key);
+    session.fingerprints = Some(fingerprints);
+    session.touch();
+
+    log::info!("🔓 保险库已解锁");
+    Ok(())
+}
+
+/// 修改主密码，所有条目使用新密钥重新加密
+pub fn change_password(app_handle: &AppHandle, state: &SecretsVaultState, old_password: &str, new_password: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if new_password.chars().count() < 8 {
+        return Err(i18n::t("vault.password_too_short").into());
+    }
+
+    let mut session = state.0.lock().unwrap();
+    let mut vault = VaultFile::load(app_handle)?.ok_or_else(|| i18n::t("vault.not_created"))?;
+    let new_key = vault.rekey(old_password, new_password, KdfParams::generate())?;
+    vault.save(app_handle)?;
+
+    session.fingerprints = Some(SecretFingerprints::from_vault(&vault, &new_key)?);
+    session.key = Some(new_key);
+    session.touch();
+
+    log::info!("🔐 保险库主密码已更新");
+    Ok(())
+}
+
+/// 写入或更新一条密钥；allowed_hosts 为允许发送该密钥的主机
+pub fn set_secret(
+    app_handle: &AppHandle,
+    state: &SecretsVaultState,
+    name: &str,
+    value: &str,
+    description: Option<String>,
+    allowed_hosts: Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    validate_secret_name(name)?;
+    let allowed_hosts = normalize_hosts(allowed_hosts)?;
+
+    let mut session = state.0.lock().unwrap();
+    let mut vault = VaultFile::load(app_handle)?.ok_or_else(|| i18n::t("vault.not_created"))?;
+    let sealed = seal(session.key()?, value.as_bytes(), name.as_bytes())?;
+
+    let now = chrono::Utc::now();
+    let created_at = vault.entries.get(name).map(|e| e.created_at).unwrap_or(now);
+    vault.entries.insert(name.to_string(), VaultEntry {
+        sealed,
+        description,
+        allowed_hosts,
+        created_at,
+        updated_at: now,
+    });
+    vault.save(app_handle)?;
+
+    if let Some(fingerprints) = session.fingerprints.as_mut() {
+        fingerprints.insert(name, value.as_bytes());
+    }
+    Ok(())
+}
+
+/// 删除一条密钥
+pub fn delete_secret(app_handle: &AppHandle, state: &SecretsVaultState, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut session = state.0.lock().unwrap();
+    session.key()?;
+    let mut vault = VaultFile::load(app_handle)?.ok_or_else(|| i18n::t("vault.not_created"))?;
+
+    if vault.entries.remove(name).is_none() {
+        return Err(i18n::tf("vault.secret_not_found", &[("name", &name)]).into());
+    }
+    vault.save(app_handle)?;
+
+    if let Some(fingerprints) = session.fingerprints.as_mut() {
+        fingerprints.remove(name);
+    }
+    Ok(())
+}
+
+/// 列出所有密钥（不含明文）
+pub fn list_secrets(app_handle: &AppHandle) -> Result<Vec<VaultEntryInfo>, Box<dyn std::error::Error>> {
+    let vault = VaultFile::load(app_handle)?.ok_or_else(|| i18n::t("vault.not_created"))?;
+    Ok(vault.entries.iter().map(|(name, entry)| VaultEntryInfo {
+        name: name.clone(),
+        description: entry.description.clone(),
+        allowed_hosts: entry.allowed_hosts.clone(),
+        created_at: entry.created_at,
+        updated_at: entry.updated_at,
+    }).collect())
+}
+
+/// 在后端解密一条密钥并交给回调使用，明文不会离开此函数
+pub fn with_secret<R>(
+    app_handle: &AppHandle,
+    state: &SecretsVaultState,
+    name: &str,
+    f: impl FnOnce(&str) -> R,
+) -> Result<R, Box<dyn std::error::Error>> {
+    let vault = VaultFile::load(app_handle)?.ok_or_else(|| i18n::t("vault.not_created"))?;
+    let entry = vault.entries.get(name).ok_or_else(|| i18n::tf("vault.secret_not_found", &[("name", &name)]))?;
+
+    let plaintext = {
+        let mut session = state.0.lock().unwrap();
+        open(session.key()?, &entry.sealed, name.as_bytes())?
+    };
+    let value = std::str::from_utf8(&plaintext)?;
+    Ok(f(value))
+}
+
+/// 把一条密钥复制到系统剪贴板，明文不经过页面（剪贴板历史会跳过保险库中的值）
+pub fn copy_secret(app_handle: &AppHandle, state: &SecretsVaultState, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+    let value = with_secret(app_handle, state, name, |v| Zeroizing::new(v.to_string()))?;
+    app_handle.clipboard().write_text(value.as_str())?;
+    Ok(())
+}
+
+/// 与 with_secret 相同，但只在 host 位于该密钥的允许主机列表中时才解密
+pub fn with_secret_for_host<R>(
+    app_handle: &AppHandle,
+    state: &SecretsVaultState,
+    name: &str,
+    host: &str,
+    f: impl FnOnce(&str) -> R,
+) -> Result<R, Box<dyn std::error::Error>> {
+    let vault = VaultFile::load(app_handle)?.ok_or_else(|| i18n::t("vault.not_created"))?;
+    let entry = vault.entries.get(name).ok_or_else(|| i18n::tf("vault.secret_not_found", &[("name", &name)]))?;
+    if !host_allowed(&entry.allowed_hosts, host) {
+        return Err(i18n::tf("vault.host_not_allowed", &[("name", &name), ("host", &host)]).into());
+    }
+    with_secret(app_handle, state, name, f)
+}
+
+/// 主机是否在允许列表中（不区分大小写，不含子域名）
+pub fn host_allowed(allowed_hosts: &[String], host: &str) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    !host.is_empty() && allowed_hosts.contains(&host)
+}
+
+/// 统一为小写、去重，拒绝带协议、端口或路径的写法
+fn normalize_hosts(hosts: Vec<String>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut normalized: Vec<String> = Vec::new();
+    for host in hosts {
+        let host = host.trim().trim_end_matches('.').to_ascii_lowercase();
+        if host.is_empty() {
+            continue;
+        }
+        if host.contains(|c: char| matches!(c, '/' | ':' | '@' | '*') || c.is_whitespace()) {
+            return Err(i18n::tf("vault.invalid_host", &[("host", &host)]).into());
+        }
+        if !normalized.contains(&host) {
+            normalized.push(host);
+        }
+    }
+    Ok(normalized)
+}
+
+/// 锁定保险库（导入或恢复配置替换了保险库文件后调用，旧的会话密钥不再有效）
+pub fn lock(app_handle: &AppHandle, reason: &str) {
+    let Some(state) = app_handle.try_state::<SecretsVaultState>() else {
+        return;
+    };
+    let was_unlocked = {
+        let mut session = state.0.lock().unwrap();
+        let was_unlocked = session.is_unlocked();
+        session.lock();
+        // 文件已被替换，旧的哈希不再对应当前的密钥
+        session.fingerprints = None;
+        was_unlocked
+    };
+    if was_unlocked {
+        log::info!("🔒 保险库已锁定: {}", reason);
+        let _ = app_handle.emit("vault-locked", reason);
+    }
+}
+
+/// 检查文本是否可能是某条密钥的值；不记录活动，不会推迟自动锁定
+///
+/// 锁定后使用解锁时记下的哈希判断。本次运行中还没有解锁过且保险库中有密钥时无法判断，一律返回 true
+pub fn contains_value(app_handle: &AppHandle, state: &SecretsVaultState, text: &str) -> bool {
+    let session = state.0.lock().unwrap();
+    match session.fingerprints.as_ref() {
+        Some(fingerprints) => fingerprints.contains(text.as_bytes()),
+        None => matches!(VaultFile::load(app_handle), Ok(Some(vault)) if !vault.entries.is_empty()),
+    }
+}
+
+/// 设置空闲自动锁定时间（分钟，0 表示不自动锁定）
+pub fn set_auto_lock(app_handle: &AppHandle, state: &SecretsVaultState, minutes: u32) -> Result<(), Box<dyn std::error::Error>> {
+    let mut session = state.0.lock().unwrap();
+    session.key()?;
+    let mut vault = VaultFile::load(app_handle)?.ok_or_else(|| i18n::t("vault.not_created"))?;
+
+    vault.auto_lock_minutes = minutes;
+    vault.save(app_handle)?;
+
+    session.auto_lock_after = Duration::from_secs(minutes as u64 * 60);
+    session.touch();
+    Ok(())
+}
+
+/// 启动空闲自动锁定检查
+pub fn start_auto_lock(app_handle: AppHandle) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(30));
+
+        loop {
+            interval.tick().await;
+
+            let state = app_handle.state::<SecretsVaultState>();
+            let locked = state.0.lock().unwrap().lock_if_idle();
+
+            if locked {
+                log::info!("🔒 保险库空闲超时，已自动锁定");
+                let _ = app_handle.emit("vault-locked", "idle");
+            }
+        }
+    });
+}
+
+/// 密钥名称只允许字母、数字和 `-_.`
+fn validate_secret_name(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(i18n::tf("vault.invalid_name", &[("name", &name)]).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试用的低成本派生参数
+    fn test_kdf() -> KdfParams {
+        KdfParams { memory_kib: 64, iterations: 1, ..KdfParams::generate() }
+    }
+
+    fn entry(key: &VaultKey, name: &str, value: &str) -> VaultEntry {
+        let now = chrono::Utc::now();
+        VaultEntry {
+            sealed: seal(key, value.as_bytes(), name.as_bytes()).unwrap(),
+            description: None,
+            allowed_hosts: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn derive_key_depends_on_password_and_salt() {
+        let kdf = test_kdf();
+        let key = derive_key("correct horse", &kdf).unwrap();
+        assert_eq!(*key, *derive_key("correct horse", &kdf).unwrap());
+        assert_ne!(*key, *derive_key("wrong horse", &kdf).unwrap());
+        assert_ne!(*key, *derive_key("correct horse", &test_kdf()).unwrap());
+
+        let unsupported = KdfParams { algorithm: "pbkdf2".to_string(), ..test_kdf() };
+        assert!(derive_key("correct horse", &unsupported).is_err());
+    }
+
+    #[test]
+    fn seal_and_open_round_trip() {
+        let key = derive_key("correct horse", &test_kdf()).unwrap();
+        let sealed = seal(&key, b"sk-secret", b"api-key").unwrap();
+        assert_eq!(open(&key, &sealed, b"api-key").unwrap().as_slice(), b"sk-secret");
+
+        // 附加数据或密钥不同都无法解密
+        assert!(open(&key, &sealed, b"other-name").is_err());
+        let other_key = derive_key("wrong horse", &test_kdf()).unwrap();
+        assert!(open(&other_key, &sealed, b"api-key").is_err());
+    }
+
+    #[test]
+    fn unlock_rejects_wrong_password() {
+        let (vault, key) = VaultFile::create("correct horse", test_kdf()).unwrap();
+        assert_eq!(*vault.unlock_key("correct horse").unwrap(), *key);
+        assert!(vault.unlock_key("wrong horse").is_err());
+    }
+
+    #[test]
+    fn change_password_reencrypts_entries() {
+        let (mut vault, old_key) = VaultFile::create("correct horse", test_kdf()).unwrap();
+        vault.entries.insert("api-key".to_string(), entry(&old_key, "api-key", "sk-secret"));
+
+        assert!(vault.rekey("wrong horse", "battery staple", test_kdf()).is_err());
+        let new_key = vault.rekey("correct horse", "battery staple", test_kdf()).unwrap();
+
+        assert!(vault.unlock_key("correct horse").is_err());
+        assert_eq!(*vault.unlock_key("battery staple").unwrap(), *new_key);
+        let sealed = &vault.entries["api-key"].sealed;
+        assert_eq!(open(&new_key, sealed, b"api-key").unwrap().as_slice(), b"sk-secret");
+        assert!(open(&old_key, sealed, b"api-key").is_err());
+    }
+
+    #[test]
+    fn session_locks_after_idle_timeout() {
+        let (_, key) = VaultFile::create("correct horse", test_kdf()).unwrap();
+        let mut session = VaultSession { key: Some(key), ..VaultSession::default() };
+        assert!(!session.lock_if_idle());
+        assert!(session.is_unlocked());
+
+        session.last_activity = Instant::now() - Duration::from_secs(120);
+        session.auto_lock_after = Duration::from_secs(60);
+        assert!(session.lock_if_idle());
+        assert!(!session.is_unlocked());
+        assert!(!session.lock_if_idle());
+    }
+
+    #[test]
+    fn zero_auto_lock_never_locks() {
+        let (_, key) = VaultFile::create("correct horse", test_kdf()).unwrap();
+        let mut session = VaultSession {
+            key: Some(key),
+            last_activity: Instant::now() - Duration::from_secs(3600),
+            auto_lock_after: Duration::ZERO,
+            fingerprints: None,
+        };
+        assert!(!session.lock_if_idle());
+        assert!(session.is_unlocked());
+    }
+
+    #[test]
+    fn fingerprints_match_values_without_storing_them() {
+        let (mut vault, key) = VaultFile::create("correct horse", test_kdf()).unwrap();
+        vault.entries.insert("api-key".to_string(), entry(&key, "api-key", "s3cret-value"));
+
+        let mut fingerprints = SecretFingerprints::from_vault(&vault, &key).unwrap();
+        assert!(fingerprints.contains(b"s3cret-value"));
+        assert!(!fingerprints.contains(b"s3cret-valu"));
+        assert!(!fingerprints.hashes.values().any(|hash| hash.as_slice() == b"s3cret-value"));
+
+        fingerprints.insert("token", b"another");
+        assert!(fingerprints.contains(b"another"));
+        fingerprints.remove("api-key");
+        assert!(!fingerprints.contains(b"s3cret-value"));
+
+        // 每次运行的盐不同，哈希不能在两次运行之间比较
+        let other = SecretFingerprints::new();
+        assert_ne!(other.hash(b"another"), fingerprints.hash(b"another"));
+
+        // 条目用其他密钥加密时无法计算哈希
+        let other_key = derive_key("correct horse", &test_kdf()).unwrap();
+        assert!(SecretFingerprints::from_vault(&vault, &other_key).is_err());
+    }
+
+    #[test]
+    fn locking_keeps_fingerprints() {
+        let mut session = VaultSession::default();
+        let (_, key) = VaultFile::create("correct horse", test_kdf()).unwrap();
+        let mut fingerprints = SecretFingerprints::new();
+        fingerprints.insert("api-key", b"s3cret-value");
+        session.key = Some(key);
+        session.fingerprints = Some(fingerprints);
+
+        session.lock();
+        assert!(!session.is_unlocked());
+        assert!(session.fingerprints.as_ref().unwrap().contains(b"s3cret-value"));
+    }
+
+}
@@ -0,0 +1,1047 @@
+use serde::{Deserialize, Serialize};
+use crate::i18n;
+use sha2::Digest;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::config_init::EXPORT_SECTIONS;
+use crate::secrets_vault::{self, KdfParams, SealedValue, SecretsVaultState};
+
+/// 同步配置文件名
+const SYNC_CONFIG_FILE: &str = "sync-config.json";
+
+/// 本地同步状态文件名
+const SYNC_STATE_FILE: &str = "sync-state.json";
+
+/// 远端目录名
+const REMOTE_DIR: &str = "utilx-sync";
+
+/// 本地同步内容修改时间的记录文件名
+const LOCAL_CHANGES_FILE: &str = "sync-local-changes.json";
+
+/// 远端清单和配置包文件名（配置包每次推送写入新文件，旧版本的清单固定使用 bundle.json）
+const MANIFEST_FILE: &str = "manifest.json";
+const BUNDLE_FILE: &str = "bundle.json";
+
+/// 推送时远端已被其他设备修改，重新同步的最多次数
+const MAX_SYNC_ATTEMPTS: usize = 3;
+
+/// 同步目标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SyncTarget {
+    /// 本地或网络共享文件夹
+    Folder { path: String },
+    /// WebDAV 服务器，密码保存在保险库中
+    Webdav {
+        url: String,
+        username: String,
+        password_secret: Option<String>,
+    },
+}
+
+/// 冲突处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    /// 以修改时间较新的一方为准
+    #[default]
+    LastWriterWins,
+    /// 记录冲突，由用户选择保留哪一方
+    Manual,
+}
+
+/// 同步配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConfig {
+    pub enabled: bool,
+    pub target: Option<SyncTarget>,
+    #[serde(default)]
+    pub conflict_strategy: ConflictStrategy,
+    /// 端到端加密口令在保险库中的名称，为空表示不加密
+    pub encryption_secret: Option<String>,
+    /// 本设备标识
+    pub device_id: String,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target: None,
+            conflict_strategy: ConflictStrategy::default(),
+            encryption_secret: None,
+            device_id: random_id(),
+        }
+    }
+}
+
+impl SyncConfig {
+    /// 同步是否需要从保险库读取 WebDAV 密码或加密口令
+    pub fn needs_vault(&self) -> bool {
+        self.encryption_secret.is_some()
+            || matches!(&self.target, Some(SyncTarget::Webdav { password_secret: Some(_), .. }))
+    }
+
+    /// 从文件加载同步配置
+    pub fn load(app_handle: &AppHandle) -> Result<Self, Box<dyn std::error::Error>> {
+        let config_path = crate::app_paths::config_dir(app_handle)?.join(SYNC_CONFIG_FILE);
+
+        if config_path.exists() {
+            let content = fs::read_to_string(&config_path)?;
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            let config = SyncConfig::default();
+            config.save(app_handle)?;
+            Ok(config)
+        }
+    }
+
+    /// 保存同步配置到文件
+    pub fn save(&self, app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        let config_dir = crate::app_paths::config_dir(app_handle)?;
+        fs::create_dir_all(&config_dir)?;
+        fs::write(config_dir.join(SYNC_CONFIG_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// 远端清单中每个配置的版本信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteRevision {
+    pub revision: u64,
+    pub device_id: String,
+    /// 该配置内容的修改时间（不是推送时间）
+    pub modified_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// 远端清单
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Manifest {
+    files: BTreeMap<String, RemoteRevision>,
+    encrypted: bool,
+    /// 当前配置包文件名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bundle: Option<String>,
+    /// 上一个配置包，保留到下次推送，让正在按旧清单读取的设备仍能读到
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    previous_bundle: Option<String>,
+}
+
+impl Manifest {
+    fn bundle_file(&self) -> &str {
+        self.bundle.as_deref().unwrap_or(BUNDLE_FILE)
+    }
+}
+
+/// 读取时远端清单的版本，写入清单前据此检查是否已被其他设备修改
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ManifestVersion {
+    /// 远端还没有清单
+    Missing,
+    /// WebDAV 的 ETag 或文件夹中清单内容的哈希
+    Tagged(String),
+    /// WebDAV 服务器没有返回 ETag，无法检查
+    Untagged,
+}
+
+/// 加密后的配置包
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptedBundle {
+    kdf: KdfParams,
+    payload: SealedValue,
+}
+
+/// 本地记录的上次同步状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SyncedRevision {
+    revision: u64,
+    hash: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SyncState {
+    files: BTreeMap<String, SyncedRevision>,
+    last_sync_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// 自动同步因保险库锁定而跳过，解锁后的下一次自动同步会清除
+    #[serde(default)]
+    waiting_for_vault: bool,
+}
+
+impl SyncState {
+    fn load(app_handle: &AppHandle) -> Result<Self, Box<dyn std::error::Error>> {
+        let state_path = crate::app_paths::config_dir(app_handle)?.join(SYNC_STATE_FILE);
+        if state_path.exists() {
+            Ok(serde_json::from_str(&fs::read_to_string(&state_path)?)?)
+        } else {
+            Ok(SyncState::default())
+        }
+    }
+
+    fn save(&self, app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        let state_path = crate::app_paths::config_dir(app_handle)?.join(SYNC_STATE_FILE);
+        fs::write(state_path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// 同步状态（供设置页显示）
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub last_sync_time: Option<chrono::DateTime<chrono::Utc>>,
+    pub waiting_for_vault: bool,
+}
+
+/// 读取同步状态
+pub fn status(app_handle: &AppHandle) -> Result<SyncStatus, Box<dyn std::error::Error>> {
+    let state = SyncState::load(app_handle)?;
+    Ok(SyncStatus {
+        last_sync_time: state.last_sync_time,
+        waiting_for_vault: state.waiting_for_vault,
+    })
+}
+
+/// 本地会同步的内容最近一次变化；只比较会同步的内容，本机状态字段（如 last_check_time）的变化不算
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LocalChange {
+    hash: String,
+    modified_at: chrono::DateTime<chrono::Utc>,
+}
+
+type LocalChanges = BTreeMap<String, LocalChange>;
+
+/// 读写修改时间记录时持有，避免保存设置和同步同时写入
+static LOCAL_CHANGES_LOCK: Mutex<()> = Mutex::new(());
+
+/// 在锁内读取、修改并保存修改时间记录
+fn update_local_changes<R>(app_handle: &AppHandle, update: impl FnOnce(&mut LocalChanges) -> R) -> Result<R, Box<dyn std::error::Error>> {
+    let _guard = LOCAL_CHANGES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = crate::app_paths::config_dir(app_handle)?.join(LOCAL_CHANGES_FILE);
+    let mut changes: LocalChanges = match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+        Err(_) => LocalChanges::new(),
+    };
+    let result = update(&mut changes);
+    fs::write(&path, serde_json::to_string_pretty(&changes)?)?;
+    Ok(result)
+}
+
+/// 配置写入后调用：会同步的内容有变化时记录修改时间
+pub fn record_local_changes(app_handle: &AppHandle) {
+    let profile = crate::config_profiles::active_profile(app_handle);
+    let result = crate::config_init::build_export_data(app_handle, profile.as_deref()).and_then(|local| {
+        update_local_changes(app_handle, |changes| {
+            for (section, file_name) in EXPORT_SECTIONS {
+                let Some(value) = local.get(section) else {
+                    continue;
+                };
+                let hash = content_hash(value);
+                match changes.get(section) {
+                    Some(change) if change.hash == hash => {}
+                    // 第一次记录时不知道内容何时修改，用文件的修改时间
+                    None => {
+                        let modified_at = file_modified_at(app_handle, file_name).unwrap_or_else(chrono::Utc::now);
+                        changes.insert(section.to_string(), LocalChange { hash, modified_at });
+                    }
+                    Some(_) => {
+                        changes.insert(section.to_string(), LocalChange { hash, modified_at: chrono::Utc::now() });
+                    }
+                }
+            }
+        })
+    });
+    if let Err(e) = result {
+        log::warn!("⚠️ 记录配置修改时间失败: {}", e);
+    }
+}
+
+/// 一项未解决的同步冲突
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncConflict {
+    pub section: String,
+    pub local_modified_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub remote: RemoteRevision,
+}
+
+/// 同步结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    pub pushed: Vec<String>,
+    pub pulled: Vec<String>,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// 冲突时保留哪一方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictChoice {
+    Local,
+    Remote,
+}
+
+/// 已连接的同步目标
+enum Remote {
+    Folder(PathBuf),
+    Webdav {
+        client: reqwest::Client,
+        base_url: String,
+        username: String,
+        password: Option<String>,
+    },
+}
+
+impl Remote {
+    fn connect(app_handle: &AppHandle, target: &SyncTarget) -> Result<Self, Box<dyn std::error::Error>> {
+        match target {
+            SyncTarget::Folder { path } => Ok(Remote::Folder(PathBuf::from(path).join(REMOTE_DIR))),
+            SyncTarget::Webdav { url, username, password_secret } => {
+                let parsed = url::Url::parse(url)?;
+                if !matches!(parsed.scheme(), "http" | "https") {
+                    return Err(i18n::tf("sync.unsupported_scheme", &[("scheme", &parsed.scheme())]).into());
+                }
+
+                let password = match password_secret {
+                    Some(name) => {
+                        let vault = app_handle.state::<SecretsVaultState>();
+                        Some(secrets_vault::with_secret(app_handle, &vault, name, |v| v.to_string())?)
+                    }
+                    None => None,
+                };
+
+                Ok(Remote::Webdav {
+                    client: reqwest::Client::new(),
+                    base_url: format!("{}/{}", url.trim_end_matches('/'), REMOTE_DIR),
+                    username: username.clone(),
+                    password,
+                })
+            }
+        }
+    }
+
+    /// 读取远端文件及其版本（WebDAV 为 ETag，文件夹为内容哈希），不存在时返回 None
+    async fn read_versioned(&self, name: &str) -> Result<Option<(Vec<u8>, ManifestVersion)>, Box<dyn std::error::Error>> {
+        match self {
+            Remote::Folder(dir) => {
+                let path = dir.join(name);
+                if path.exists() {
+                    let bytes = fs::read(path)?;
+                    let version = ManifestVersion::Tagged(bytes_hash(&bytes));
+                    Ok(Some((bytes, version)))
+                } else {
+                    Ok(None)
+                }
+            }
+            Remote::Webdav { client, base_url, username, password } => {
+                let response = client
+                    .get(format!("{}/{}", base_url, name))
+                    .basic_auth(username, password.as_ref())
+                    .send()
+                    .await?;
+
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                if !response.status().is_success() {
+                    return Err(i18n::tf("sync.webdav_read_failed", &[("name", &name), ("status", &response.status())]).into());
+                }
+                let version = response
+                    .headers()
+                    .get(reqwest::header::ETAG)
+                    .and_then(|etag| etag.to_str().ok())
+                    .map(|etag| ManifestVersion::Tagged(etag.to_string()))
+                    .unwrap_or(ManifestVersion::Untagged);
+                Ok(Some((response.bytes().await?.to_vec(), version)))
+            }
+        }
+    }
+
+    /// 读取远端文件，不存在时返回 None
+    async fn read(&self, name: &str) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+        Ok(self.read_versioned(name).await?.map(|(bytes, _)| bytes))
+    }
+
+    /// 写入远端文件
+    async fn write(&self, name: &str, content: Vec<u8>) -> Result<(), Box<dyn std::error::Error>> {
+        match self {
+            Remote::Folder(dir) => {
+                fs::create_dir_all(dir)?;
+                // 先写临时文件再重命名，避免另一台设备读到写了一半的文件
+                let temp_path = dir.join(format!("{}.tmp", name));
+                fs::write(&temp_path, content)?;
+                fs::rename(&temp_path, dir.join(name))?;
+                Ok(())
+            }
+            Remote::Webdav { client, base_url, username, password } => {
+                // 目录已存在时 MKCOL 返回 405，忽略即可
+                let _ = client
+                    .request(reqwest::Method::from_bytes(b"MKCOL")?, base_url.as_str())
+                    .basic_auth(username, password.as_ref())
+                    .send()
+                    .await;
+
+                let response = client
+                    .put(format!("{}/{}", base_url, name))
+                    .basic_auth(username, password.as_ref())
+                    .body(content)
+                    .send()
+                    .await?;
+
+                if !response.status().is_success() {
+                    return Err(i18n::tf("sync.webdav_write_failed", &[("name", &name), ("status", &response.status())]).into());
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// 远端清单仍是 expected 版本时写入清单；返回 false 表示已被其他设备修改，没有写入
+    async fn write_manifest(&self, content: Vec<u8>, expected: &ManifestVersion) -> Result<bool, Box<dyn std::error::Error>> {
+        match self {
+            Remote::Folder(_) => {
+                // 文件夹没有原子的比较并交换，写入前再检查一次
+                let current = self.read_versioned(MANIFEST_FILE).await?.map(|(_, version)| version);
+                if current.unwrap_or(ManifestVersion::Missing) != *expected {
+                    return Ok(false);
+                }
+                self.write(MANIFEST_FILE, content).await?;
+                Ok(true)
+            }
+            Remote::Webdav { client, base_url, username, password } => {
+                let request = client
+                    .put(format!("{}/{}", base_url, MANIFEST_FILE))
+                    .basic_auth(username, password.as_ref())
+                    .body(content);
+                let request = match expected {
+                    ManifestVersion::Missing => request.header(reqwest::header::IF_NONE_MATCH, "*"),
+                    ManifestVersion::Tagged(etag) => request.header(reqwest::header::IF_MATCH, etag.as_str()),
+                    ManifestVersion::Untagged => request,
+                };
+
+                let response = request.send().await?;
+                if response.status() == reqwest::StatusCode::PRECONDITION_FAILED {
+                    return Ok(false);
+                }
+                if !response.status().is_success() {
+                    return Err(i18n::tf("sync.webdav_write_failed", &[("name", &MANIFEST_FILE), ("status", &response.status())]).into());
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    /// 删除远端文件；失败时只会留下一个多余的文件，忽略即可
+    async fn remove(&self, name: &str) {
+        match self {
+            Remote::Folder(dir) => {
+                let _ = fs::remove_file(dir.join(name));
+            }
+            Remote::Webdav { client, base_url, username, password } => {
+                let _ = client
+                    .delete(format!("{}/{}", base_url, name))
+                    .basic_auth(username, password.as_ref())
+                    .send()
+                    .await;
+            }
+        }
+    }
+}
+
+/// 生成随机标识（设备标识、配置包文件名）
+fn random_id() -> String {
+    use chacha20poly1305::aead::rand_core::RngCore;
+    let mut bytes = [0u8; 8];
+    chacha20poly1305::aead::OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn bytes_hash(bytes: &[u8]) -> String {
+    format!("{:x}", sha2::Sha256::digest(bytes))
+}
+
+/// 对象的键按字母排序，使键顺序不同的相同内容得到相同的哈希
+fn canonical(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            serde_json::Value::Object(keys.into_iter().map(|key| (key.clone(), canonical(&map[key]))).collect())
+        }
+        serde_json::Value::Array(items) => serde_json::Value::Array(items.iter().map(canonical).collect()),
+        other => other.clone(),
+    }
+}
+
+/// 计算配置内容的哈希，用于判断本地是否有修改以及两边内容是否相同
+fn content_hash(value: &serde_json::Value) -> String {
+    bytes_hash(canonical(value).to_string().as_bytes())
+}
+
+/// 本地配置文件的修改时间（没有内容修改记录时使用）
+fn file_modified_at(app_handle: &AppHandle, file_name: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let profile_path = crate::config_profiles::active_profile(app_handle)
+        .and_then(|profile| crate::config_profiles::overlay_path(app_handle, &profile, file_name).ok())
+        .filter(|path| path.exists());
+    let path = profile_path.or_else(|| Some(crate::app_paths::config_dir(app_handle).ok()?.join(file_name)))?;
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    Some(modified.into())
+}
+
+/// 本地某项配置当前内容的修改时间
+fn local_modified_at(app_handle: &AppHandle, changes: &LocalChanges, section: &str, hash: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    match changes.get(section) {
+        Some(change) if change.hash == hash => Some(change.modified_at),
+        _ => EXPORT_SECTIONS
+            .iter()
+            .find(|(key, _)| *key == section)
+            .and_then(|(_, file_name)| file_modified_at(app_handle, file_name)),
+    }
+}
+
+/// 读取端到端加密口令派生所需的口令
+fn encryption_passphrase(app_handle: &AppHandle, config: &SyncConfig) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    match &config.encryption_secret {
+        Some(name) => {
+            let vault = app_handle.state::<SecretsVaultState>();
+            Ok(Some(secrets_vault::with_secret(app_handle, &vault, name, |v| v.to_string())?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// 读取到的远端清单和配置包，以及读取时清单的版本
+struct RemoteSnapshot {
+    manifest: Manifest,
+    version: ManifestVersion,
+    bundle: serde_json::Map<String, serde_json::Value>,
+}
+
+/// 读取远端清单和配置包
+async fn fetch_remote(remote: &Remote, passphrase: Option<&str>) -> Result<RemoteSnapshot, Box<dyn std::error::Error>> {
+    let Some((manifest_bytes, version)) = remote.read_versioned(MANIFEST_FILE).await? else {
+        return Ok(RemoteSnapshot {
+            manifest: Manifest::default(),
+            version: ManifestVersion::Missing,
+            bundle: serde_json::Map::new(),
+        });
+    };
+    let manifest: Manifest = serde_json::from_slice(&manifest_bytes)?;
+
+    // 加密时的附加数据固定为 bundle.json，与配置包实际的文件名无关
+    let bundle = match remote.read(manifest.bundle_file()).await? {
+        Some(bytes) if manifest.encrypted => {
+            let passphrase = passphrase.ok_or_else(|| i18n::t("sync.remote_encrypted"))?;
+            let encrypted: EncryptedBundle = serde_json::from_slice(&bytes)?;
+            let key = secrets_vault::derive_key(passphrase, &encrypted.kdf)?;
+            let plaintext = secrets_vault::open(&key, &encrypted.payload, BUNDLE_FILE.as_bytes())?;
+            serde_json::from_slice(&plaintext)?
+        }
+        Some(bytes) => serde_json::from_slice(&bytes)?,
+        None => serde_json::Map::new(),
+    };
+
+    // 配置包与导出文件格式相同，其他版本的客户端写入的内容先迁移到当前格式
+    let bundle = if bundle.is_empty() {
+        bundle
+    } else {
+        match crate::config_init::migrate_export_data(serde_json::Value::Object(bundle))? {
+            serde_json::Value::Object(bundle) => bundle,
+            _ => unreachable!("migrate_export_data 返回 JSON 对象"),
+        }
+    };
+
+    Ok(RemoteSnapshot { manifest, version, bundle })
+}
+
+/// 写入远端配置包和清单；返回 false 表示读取后远端已被其他设备修改，没有生效
+async fn store_remote(remote: &Remote, passphrase: Option<&str>, snapshot: RemoteSnapshot) -> Result<bool, Box<dyn std::error::Error>> {
+    let RemoteSnapshot { mut manifest, version, mut bundle } = snapshot;
+
+    crate::config_init::insert_export_metadata(&mut bundle);
+    let bundle_bytes = serde_json::to_vec_pretty(&bundle)?;
+    let bundle_bytes = match passphrase {
+        Some(passphrase) => {
+            let kdf = KdfParams::generate();
+            let key = secrets_vault::derive_key(passphrase, &kdf)?;
+            let payload = secrets_vault::seal(&key, &bundle_bytes, BUNDLE_FILE.as_bytes())?;
+            serde_json::to_vec_pretty(&EncryptedBundle { kdf, payload })?
+        }
+        None => bundle_bytes,
+    };
+    manifest.encrypted = passphrase.is_some();
+
+    // 配置包写入新文件，清单更新成功后其他设备才会读取它；同时推送的设备只有一方的清单能写入
+    let bundle_file = format!("bundle-{}.json", random_id());
+    let stale_bundle = manifest.previous_bundle.take();
+    if version != ManifestVersion::Missing {
+        manifest.previous_bundle = Some(manifest.bundle_file().to_string());
+    }
+    manifest.bundle = Some(bundle_file.clone());
+
+    remote.write(&bundle_file, bundle_bytes).await?;
+    if !remote.write_manifest(serde_json::to_vec_pretty(&manifest)?, &version).await? {
+        remote.remove(&bundle_file).await;
+        return Ok(false);
+    }
+    if let Some(stale_bundle) = stale_bundle.filter(|stale| Some(stale) != manifest.previous_bundle.as_ref()) {
+        remote.remove(&stale_bundle).await;
+    }
+    Ok(true)
+}
+
+/// 将远端配置写入本地（保留远端配置包中没有的本机字段）
+fn apply_remote_section(app_handle: &AppHandle, file_name: &str, remote_value: &serde_json::Value) -> Result<(), Box<dyn std::error::Error>> {
+    let profile = crate::config_profiles::active_profile(app_handle);
+    let mut local = crate::config_profiles::read_effective(app_handle, profile.as_deref(), file_name)?
+        .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+
+    match (local.as_object_mut(), remote_value.as_object()) {
+        (Some(local_map), Some(remote_map)) => {
+            for (key, value) in remote_map {
+                local_map.insert(key.clone(), value.clone());
+            }
+        }
+        _ => local = remote_value.clone(),
+    }
+
+    crate::config_profiles::write_effective(app_handle, file_name, &local)
+}
+
+/// 需要写入本地的远端配置
+struct PulledSection {
+    section: &'static str,
+    file_name: &'static str,
+    value: serde_json::Value,
+    revision: RemoteRevision,
+}
+
+/// 一次同步比较的结果；远端已经更新，拉取的配置由调用方写入本地
+#[derive(Default)]
+struct SyncOutcome {
+    report: SyncReport,
+    pulled: Vec<PulledSection>,
+}
+
+/// 比较本地和远端的各项配置，推送本地修改并返回需要拉取的配置
+///
+/// `local_modified_at` 按配置项和内容哈希返回本地内容的修改时间；返回 None 表示推送时远端已被其他设备修改，需要重新同步
+async fn sync_sections(
+    remote: &Remote,
+    passphrase: Option<&str>,
+    config: &SyncConfig,
+    local: &serde_json::Map<String, serde_json::Value>,
+    state: &mut SyncState,
+    local_modified_at: impl Fn(&str, &str) -> Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<Option<SyncOutcome>, Box<dyn std::error::Error>> {
+    let mut snapshot = fetch_remote(remote, passphrase).await?;
+    let mut outcome = SyncOutcome::default();
+    let now = chrono::Utc::now();
+
+    for (section, file_name) in EXPORT_SECTIONS {
+        let local_value = local.get(section);
+        let remote_value = snapshot.bundle.get(section).cloned();
+        let remote_revision = snapshot.manifest.files.get(section).cloned();
+        let synced = state.files.get(section).cloned().unwrap_or_default();
+        let local_hash = local_value.map(content_hash).unwrap_or_default();
+
+        // 两边内容相同（例如第二台设备第一次同步）时只记录同步状态
+        if let (Some(remote_value), Some(remote_revision)) = (&remote_value, &remote_revision) {
+            if local_value.is_some() && content_hash(remote_value) == local_hash {
+                state.files.insert(section.to_string(), SyncedRevision { revision: remote_revision.revision, hash: local_hash });
+                continue;
+            }
+        }
+
+        let local_changed = local_value.is_some() && local_hash != synced.hash;
+        let remote_changed = remote_value.is_some() && remote_revision.as_ref().is_some_and(|r| r.revision > synced.revision);
+
+        let take_remote = match (local_changed, remote_changed) {
+            (false, false) => continue,
+            (true, false) => false,
+            (false, true) => true,
+            (true, true) => {
+                let remote_revision = remote_revision.clone().expect("remote_changed 时远端版本存在");
+                let local_modified_at = local_modified_at(section, &local_hash);
+                match config.conflict_strategy {
+                    ConflictStrategy::Manual => {
+                        outcome.report.conflicts.push(SyncConflict {
+                            section: section.to_string(),
+                            local_modified_at,
+                            remote: remote_revision,
+                        });
+                        continue;
+                    }
+                    ConflictStrategy::LastWriterWins => {
+                        local_modified_at.is_none_or(|t| t < remote_revision.modified_at)
+                    }
+                }
+            }
+        };
+
+        if take_remote {
+            let (Some(value), Some(revision)) = (remote_value, remote_revision) else {
+                continue;
+            };
+            state.files.insert(section.to_string(), SyncedRevision { revision: revision.revision, hash: content_hash(&value) });
+            outcome.report.pulled.push(section.to_string());
+            outcome.pulled.push(PulledSection { section, file_name, value, revision });
+        } else if let Some(local_value) = local_value {
+            let revision = remote_revision.map(|r| r.revision).unwrap_or(0).max(synced.revision) + 1;
+            snapshot.bundle.insert(section.to_string(), local_value.clone());
+            snapshot.manifest.files.insert(section.to_string(), RemoteRevision {
+                revision,
+                device_id: config.device_id.clone(),
+                modified_at: local_modified_at(section, &local_hash).unwrap_or(now),
+            });
+            state.files.insert(section.to_string(), SyncedRevision { revision, hash: local_hash });
+            outcome.report.pushed.push(section.to_string());
+        }
+    }
+
+    if !outcome.report.pushed.is_empty() && !store_remote(remote, passphrase, snapshot).await? {
+        return Ok(None);
+    }
+    Ok(Some(outcome))
+}
+
+/// 执行一次双向同步
+pub async fn sync_now(app_handle: &AppHandle) -> Result<SyncReport, Box<dyn std::error::Error>> {
+    let config = SyncConfig::load(app_handle)?;
+    let target = config.target.as_ref().ok_or_else(|| i18n::t("sync.no_target"))?;
+    let remote = Remote::connect(app_handle, target)?;
+    let passphrase = encryption_passphrase(app_handle, &config)?;
+    let active_profile = crate::config_profiles::active_profile(app_handle);
+
+    for attempt in 1..=MAX_SYNC_ATTEMPTS {
+        let mut state = SyncState::load(app_handle)?;
+        let local = crate::config_init::build_export_data(app_handle, active_profile.as_deref())?;
+        let changes = update_local_changes(app_handle, |changes| changes.clone())?;
+
+        let outcome = sync_sections(&remote, passphrase.as_deref(), &config, &local, &mut state, |section, hash| {
+            local_modified_at(app_handle, &changes, section, hash)
+        })
+        .await?;
+        let Some(outcome) = outcome else {
+            log::info!("🔄 远端在同步期间被其他设备修改，重新同步 ({}/{})", attempt, MAX_SYNC_ATTEMPTS);
+            continue;
+        };
+
+        if !outcome.pulled.is_empty() {
+            for pulled in &outcome.pulled {
+                apply_remote_section(app_handle, pulled.file_name, &pulled.value)?;
+            }
+            crate::config_init::apply_settings(app_handle);
+
+            // 拉取后本地文件可能被规范化，重新计算哈希避免下次误判为本地修改；内容的修改时间沿用远端的
+            let local = crate::config_init::build_export_data(app_handle, active_profile.as_deref())?;
+            update_local_changes(app_handle, |changes| {
+                for pulled in &outcome.pulled {
+                    let Some(value) = local.get(pulled.section) else {
+                        continue;
+                    };
+                    let hash = content_hash(value);
+                    if let Some(synced) = state.files.get_mut(pulled.section) {
+                        synced.hash = hash.clone();
+                    }
+                    changes.insert(pulled.section.to_string(), LocalChange { hash, modified_at: pulled.revision.modified_at });
+                }
+            })?;
+        }
+
+        state.last_sync_time = Some(chrono::Utc::now());
+        state.waiting_for_vault = false;
+        state.save(app_handle)?;
+
+        let report = outcome.report;
+        log::info!(
+            "🔄 同步完成：推送 {:?}，拉取 {:?}，冲突 {}",
+            report.pushed, report.pulled, report.conflicts.len()
+        );
+        return Ok(report);
+    }
+
+    Err(i18n::t("sync.remote_changed").into())
+}
+
+/// 手动解决同步冲突
+pub async fn resolve_conflict(app_handle: &AppHandle, section: &str, choice: ConflictChoice) -> Result<(), Box<dyn std::error::Error>> {
+    let file_name = EXPORT_SECTIONS.iter()
+        .find(|(key, _)| *key == section)
+        .map(|(_, file_name)| *file_name)
+        .ok_or_else(|| i18n::tf("sync.unknown_section", &[("section", &section)]))?;
+
+    let config = SyncConfig::load(app_handle)?;
+    let target = config.target.as_ref().ok_or_else(|| i18n::t("sync.no_target"))?;
+    let remote = Remote::connect(app_handle, target)?;
+    let passphrase = encryption_passphrase(app_handle, &config)?;
+
+    let mut snapshot = fetch_remote(&remote, passphrase.as_deref()).await?;
+    let mut state = SyncState::load(app_handle)?;
+    let remote_revision = snapshot.manifest.files.get(section).cloned();
+
+    match choice {
+        ConflictChoice::Remote => {
+            let remote_value = snapshot.bundle.get(section).ok_or_else(|| i18n::t("sync.remote_missing"))?;
+            apply_remote_section(app_handle, file_name, remote_value)?;
+            crate::config_init::apply_settings(app_handle);
+
+            let active_profile = crate::config_profiles::active_profile(app_handle);
+            let local = crate::config_init::build_export_data(app_handle, active_profile.as_deref())?;
+            let hash = local.get(section).map(content_hash).unwrap_or_default();
+            if let Some(revision) = &remote_revision {
+                let change = LocalChange { hash: hash.clone(), modified_at: revision.modified_at };
+                update_local_changes(app_handle, |changes| changes.insert(section.to_string(), change))?;
+            }
+            state.files.insert(section.to_string(), SyncedRevision {
+                revision: remote_revision.map(|r| r.revision).unwrap_or(0),
+                hash,
+            });
+        }
+        ConflictChoice::Local => {
+            let active_profile = crate::config_profiles::active_profile(app_handle);
+            let local = crate::config_init::build_export_data(app_handle, active_profile.as_deref())?;
+            let local_value = local.get(section).ok_or_else(|| i18n::t("sync.local_missing"))?;
+
+            let revision = remote_revision.map(|r| r.revision).unwrap_or(0) + 1;
+            snapshot.bundle.insert(section.to_string(), local_value.clone());
+            snapshot.manifest.files.insert(section.to_string(), RemoteRevision {
+                revision,
+                device_id: config.device_id.clone(),
+                modified_at: chrono::Utc::now(),
+            });
+            if !store_remote(&remote, passphrase.as_deref(), snapshot).await? {
+                return Err(i18n::t("sync.remote_changed").into());
+            }
+
+            state.files.insert(section.to_string(), SyncedRevision {
+                revision,
+                hash: content_hash(local_value),
+            });
+        }
+    }
+
+    state.save(app_handle)?;
+    log::info!("✅ 同步冲突已解决: {} ({:?})", section, choice);
+    Ok(())
+}
+
+/// 记录自动同步在等待保险库解锁，只在第一次跳过时写日志
+fn mark_waiting_for_vault(app_handle: &AppHandle) {
+    let Ok(mut state) = SyncState::load(app_handle) else {
+        return;
+    };
+    if state.waiting_for_vault {
+        return;
+    }
+    log::info!("🔒 保险库已锁定，自动同步将在解锁后进行");
+    state.waiting_for_vault = true;
+    if let Err(e) = state.save(app_handle) {
+        log::warn!("⚠️ 保存同步状态失败: {}", e);
+    }
+}
+
+/// 启用同步时，启动后和之后每 30 分钟自动同步一次
+pub fn start_auto_sync(app_handle: AppHandle) {
+    use tauri::Emitter;
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(30 * 60));
+
+        loop {
+            interval.tick().await;
+
+            let Some(config) = SyncConfig::load(&app_handle).ok().filter(|c| c.enabled && c.target.is_some()) else {
+                continue;
+            };
+
+            // 需要保险库中的密码或口令时，锁定期间跳过，不弹出失败提示
+            let vault_locked = !app_handle.state::<SecretsVaultState>().0.lock().unwrap().is_unlocked();
+            if config.needs_vault() && vault_locked {
+                mark_waiting_for_vault(&app_handle);
+                continue;
+            }
+
+            match sync_now(&app_handle).await {
+                Ok(report) => {
+                    let _ = app_handle.emit("sync-completed", &report);
+                }
+                Err(e) => {
+                    log::warn!("⚠️ 自动同步失败: {}", e);
+                    let _ = app_handle.emit("sync-failed", e.to_string());
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn temp_remote(name: &str) -> (PathBuf, Remote) {
+        let dir = std::env::temp_dir().join(format!("utilx-sync-{}-{}", name, random_id()));
+        (dir.clone(), Remote::Folder(dir.join(REMOTE_DIR)))
+    }
+
+    fn config(device_id: &str, conflict_strategy: ConflictStrategy) -> SyncConfig {
+        SyncConfig {
+            enabled: true,
+            target: None,
+            conflict_strategy,
+            encryption_secret: None,
+            device_id: device_id.to_string(),
+        }
+    }
+
+    fn local(settings: serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+        let mut local = serde_json::Map::new();
+        local.insert("user_settings".to_string(), settings);
+        local
+    }
+
+    fn at(hour: u32) -> Option<chrono::DateTime<chrono::Utc>> {
+        use chrono::TimeZone;
+        chrono::Utc.with_ymd_and_hms(2024, 1, 1, hour, 0, 0).single()
+    }
+
+    /// 设备 A 推送 settings 后，返回 A 的同步状态
+    async fn push_from_a(remote: &Remote, settings: serde_json::Value, modified_hour: u32) -> SyncState {
+        let mut state = SyncState::default();
+        let outcome = sync_sections(remote, None, &config("a", ConflictStrategy::Manual), &local(settings), &mut state, |_, _| at(modified_hour))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.report.pushed, vec!["user_settings"]);
+        state
+    }
+
+    #[test]
+    fn content_hash_ignores_key_order() {
+        let a = json!({ "theme": "dark", "nested": { "x": 1, "y": [{ "b": 2, "a": 1 }] } });
+        let b = json!({ "nested": { "y": [{ "a": 1, "b": 2 }], "x": 1 }, "theme": "dark" });
+        assert_eq!(content_hash(&a), content_hash(&b));
+        assert_ne!(content_hash(&a), content_hash(&json!({ "theme": "light" })));
+    }
+
+    #[tokio::test]
+    async fn first_sync_with_identical_content_is_not_a_conflict() {
+        let (dir, remote) = temp_remote("first");
+        push_from_a(&remote, json!({ "theme": "dark" }), 1).await;
+
+        // 设备 B 第一次同步，两边都像是有修改，但内容相同
+        let mut state = SyncState::default();
+        let outcome = sync_sections(&remote, None, &config("b", ConflictStrategy::Manual), &local(json!({ "theme": "dark" })), &mut state, |_, _| at(2))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(outcome.report.conflicts.is_empty());
+        assert!(outcome.report.pushed.is_empty());
+        assert!(outcome.pulled.is_empty());
+        assert_eq!(state.files["user_settings"].revision, 1);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn one_sided_remote_change_is_pulled() {
+        let (dir, remote) = temp_remote("pull");
+        let mut state_a = push_from_a(&remote, json!({ "theme": "dark" }), 1).await;
+
+        // 设备 B 与 A 同步后修改并推送
+        let mut state_b = SyncState::default();
+        sync_sections(&remote, None, &config("b", ConflictStrategy::Manual), &local(json!({ "theme": "dark" })), &mut state_b, |_, _| at(1))
+            .await
+            .unwrap()
+            .unwrap();
+        let outcome = sync_sections(&remote, None, &config("b", ConflictStrategy::Manual), &local(json!({ "theme": "light" })), &mut state_b, |_, _| at(3))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.report.pushed, vec!["user_settings"]);
+
+        // A 本地没有修改，直接拉取
+        let outcome = sync_sections(&remote, None, &config("a", ConflictStrategy::Manual), &local(json!({ "theme": "dark" })), &mut state_a, |_, _| at(1))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(outcome.report.conflicts.is_empty());
+        assert_eq!(outcome.report.pulled, vec!["user_settings"]);
+        assert_eq!(outcome.pulled[0].value, json!({ "theme": "light" }));
+        assert_eq!(outcome.pulled[0].revision.modified_at, at(3).unwrap());
+        assert_eq!(state_a.files["user_settings"].revision, 2);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn changes_on_both_sides_are_reported_as_conflict() {
+        let (dir, remote) = temp_remote("conflict");
+        push_from_a(&remote, json!({ "theme": "dark" }), 1).await;
+
+        let mut state = SyncState::default();
+        let outcome = sync_sections(&remote, None, &config("b", ConflictStrategy::Manual), &local(json!({ "theme": "light" })), &mut state, |_, _| at(2))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.report.conflicts.len(), 1);
+        assert_eq!(outcome.report.conflicts[0].section, "user_settings");
+        assert_eq!(outcome.report.conflicts[0].local_modified_at, at(2));
+        assert!(outcome.report.pushed.is_empty());
+        assert!(outcome.pulled.is_empty());
+        assert!(!state.files.contains_key("user_settings"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn last_writer_wins_compares_content_timestamps() {
+        let (dir, remote) = temp_remote("lww");
+        push_from_a(&remote, json!({ "theme": "dark" }), 5).await;
+
+        // 本地内容较旧，取远端
+        let mut state = SyncState::default();
+        let outcome = sync_sections(&remote, None, &config("b", ConflictStrategy::LastWriterWins), &local(json!({ "theme": "light" })), &mut state, |_, _| at(4))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.report.pulled, vec!["user_settings"]);
+
+        // 本地内容较新，推送并沿用本地内容的修改时间
+        let mut state = SyncState::default();
+        let outcome = sync_sections(&remote, None, &config("b", ConflictStrategy::LastWriterWins), &local(json!({ "theme": "light" })), &mut state, |_, _| at(6))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.report.pushed, vec!["user_settings"]);
+        let snapshot = fetch_remote(&remote, None).await.unwrap();
+        assert_eq!(snapshot.bundle["user_settings"], json!({ "theme": "light" }));
+        assert_eq!(snapshot.manifest.files["user_settings"].modified_at, at(6).unwrap());
+        assert_eq!(snapshot.manifest.files["user_settings"].revision, 2);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn store_remote_refuses_when_manifest_changed_concurrently() {
+        let (dir, remote) = temp_remote("cas");
+        let mut state = push_from_a(&remote, json!({ "theme": "dark" }), 1).await;
+
+        let stale = fetch_remote(&remote, None).await.unwrap();
+        let outcome = sync_sections(&remote, None, &config("a", ConflictStrategy::Manual), &local(json!({ "theme": "light" })), &mut state, |_, _| at(2))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.report.pushed, vec!["user_settings"]);
+
+        assert!(!store_remote(&remote, None, stale).await.unwrap());
+        let snapshot = fetch_remote(&remote, None).await.unwrap();
+        assert_eq!(snapshot.bundle["user_settings"], json!({ "theme": "light" }));
+        // 写入失败的配置包已删除，只剩当前和上一个配置包
+        assert_eq!(fs::read_dir(dir.join(REMOTE_DIR)).unwrap().count(), 3);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+}
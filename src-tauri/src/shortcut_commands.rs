@@ -0,0 +1,40 @@
+use crate::global_shortcuts::{self, ShortcutBinding, ShortcutConflict, ShortcutStatus};
+use crate::i18n;
+use tauri::{AppHandle, command};
+
+/// 获取快捷键绑定
+#[command]
+pub async fn get_shortcuts(app_handle: AppHandle) -> Result<Vec<ShortcutBinding>, String> {
+    Ok(global_shortcuts::load_bindings(&app_handle))
+}
+
+/// 检查快捷键绑定是否有效或重复（不注册）
+#[command]
+pub async fn validate_shortcuts(bindings: Vec<ShortcutBinding>) -> Result<Vec<ShortcutConflict>, String> {
+    Ok(global_shortcuts::validate(&bindings))
+}
+
+/// 保存快捷键绑定并重新注册；返回实际注册结果（包括被其他程序占用的组合键）
+#[command]
+pub async fn save_shortcuts(app_handle: AppHandle, bindings: Vec<ShortcutBinding>) -> Result<ShortcutStatus, String> {
+    let conflicts = global_shortcuts::validate(&bindings);
+    if !conflicts.is_empty() {
+        let details: Vec<String> = conflicts
+            .iter()
+            .map(|c| format!("{}: {}", c.accelerator, c.reason))
+            .collect();
+        return Err(i18n::tf("shortcuts.conflicts", &[("details", &details.join("; "))]));
+    }
+
+    global_shortcuts::save_bindings(&app_handle, &bindings)
+        .map_err(|e| i18n::tf("shortcuts.save_failed", &[("error", &e)]))?;
+    global_shortcuts::apply(&app_handle)
+        .map_err(|e| i18n::tf("shortcuts.register_failed", &[("error", &e)]))
+}
+
+/// 按当前设置重新注册快捷键
+#[command]
+pub async fn reload_shortcuts(app_handle: AppHandle) -> Result<ShortcutStatus, String> {
+    global_shortcuts::apply(&app_handle)
+        .map_err(|e| i18n::tf("shortcuts.register_failed", &[("error", &e)]))
+}
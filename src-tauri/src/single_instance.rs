@@ -0,0 +1,438 @@
+// 单实例：使用运行时目录中的锁文件保证只有一个实例，第二次启动时把参数转发给已运行的实例
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+/// 连接已运行实例的重试次数（对方可能还在启动中）
+const CONNECT_RETRIES: u32 = 10;
+
+/// 每次重试的间隔
+const CONNECT_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// 读写转发参数的超时，避免不发送数据的连接一直阻塞监听线程
+const IO_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// 转发给已运行实例的启动参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForwardedArgs {
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    /// Windows 下用于校验连接来源的令牌
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+}
+
+/// 启动检查结果
+pub enum Instance {
+    /// 当前是第一个实例
+    Primary(InstanceGuard),
+    /// 已有实例在运行，参数已转发
+    Secondary,
+}
+
+/// 持有单实例锁，程序运行期间不能释放
+pub struct InstanceGuard {
+    _lock: File,
+    endpoint: platform::Endpoint,
+}
+
+/// 运行时目录：优先使用 XDG_RUNTIME_DIR（只有当前用户可以访问），否则在临时目录中使用当前用户私有的子目录
+fn runtime_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let xdg = std::env::var_os("XDG_RUNTIME_DIR")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute() && p.is_dir());
+    match xdg {
+        Some(dir) => Ok(dir),
+        None => platform::private_dir(&std::env::temp_dir().join(user_name())),
+    }
+}
+
+/// 区分用户的名称：`utilx-<用户名>`
+fn user_name() -> String {
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>();
+
+    if user.is_empty() { "utilx".to_string() } else { format!("utilx-{}", user) }
+}
+
+/// 锁文件和通信端点的文件名前缀，区分用户和便携模式目录
+fn instance_name() -> String {
+    let mut name = user_name();
+    if let Some(root) = crate::app_paths::portable_root() {
+        let hash = Sha256::digest(root.to_string_lossy().as_bytes());
+        name.push_str(&format!("-{:x}", hash)[..9]);
+    }
+    name
+}
+
+/// 获取单实例锁；已有实例时转发当前启动参数
+pub fn acquire() -> Result<Instance, Box<dyn std::error::Error>> {
+    let dir = runtime_dir()?;
+    fs::create_dir_all(&dir)?;
+    let name = instance_name();
+
+    let lock = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(dir.join(format!("{}.lock", name)))?;
+
+    match lock.try_lock() {
+        Ok(()) => {
+            let endpoint = platform::bind(&dir, &name)?;
+            Ok(Instance::Primary(InstanceGuard { _lock: lock, endpoint }))
+        }
+        Err(std::fs::TryLockError::WouldBlock) => {
+            let message = ForwardedArgs {
+                args: std::env::args().skip(1).collect(),
+                cwd: std::env::current_dir().ok().map(|p| p.to_string_lossy().to_string()),
+                token: None,
+            };
+            match forward(&dir, &name, message) {
+                Ok(()) => log::info!("📨 已有实例在运行，启动参数已转发"),
+                Err(e) => log::error!("❌ 已有实例在运行，但转发启动参数失败: {}", e),
+            }
+            Ok(Instance::Secondary)
+        }
+        Err(std::fs::TryLockError::Error(e)) => Err(e.into()),
+    }
+}
+
+fn forward(dir: &Path, name: &str, message: ForwardedArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let mut last_error: Box<dyn std::error::Error> = "无法连接到已运行的实例".into();
+
+    for _ in 0..CONNECT_RETRIES {
+        match platform::send(dir, name, message.clone()) {
+            Ok(()) => return Ok(()),
+            Err(e) => last_error = e,
+        }
+        std::thread::sleep(CONNECT_RETRY_INTERVAL);
+    }
+
+    Err(last_error)
+}
+
+impl InstanceGuard {
+    /// 开始接收其他实例转发的参数
+    pub fn listen(self, app_handle: AppHandle) {
+        std::thread::spawn(move || {
+            // 锁随监听线程一直持有到程序退出
+            let InstanceGuard { _lock, endpoint } = self;
+            endpoint.accept_loop(|message| handle_forwarded(&app_handle, message));
+        });
+    }
+}
+
+/// 读取一行 JSON 参数并回复确认
+fn read_message<S: std::io::Read + Write>(stream: S) -> Result<ForwardedArgs, Box<dyn std::error::Error>> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let message: ForwardedArgs = serde_json::from_str(line.trim())?;
+    reader.get_mut().write_all(b"ok\n")?;
+    Ok(message)
+}
+
+/// 发送一行 JSON 参数并等待确认
+fn write_message<S: std::io::Read + Write>(mut stream: S, message: &ForwardedArgs) -> Result<(), Box<dyn std::error::Error>> {
+    stream.write_all(format!("{}\n", serde_json::to_string(message)?).as_bytes())?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    if reply.trim() != "ok" {
+        return Err("已运行的实例未确认收到参数".into());
+    }
+    Ok(())
+}
+
+/// 从转发的参数中解析出的操作
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ForwardedAction {
+    deep_links: Vec<String>,
+    tool: Option<String>,
+    files: Vec<PathBuf>,
+}
+
+/// 解析转发过来的参数；开机自启动时返回 None（应用已在运行，保持当前窗口状态）
+fn parse_forwarded(message: &ForwardedArgs) -> Option<ForwardedAction> {
+    if message.args.iter().any(|arg| arg == crate::autostart::AUTOSTART_ARG) {
+        return None;
+    }
+
+    let cwd = message.cwd.as_deref().map(Path::new);
+    let mut action = ForwardedAction::default();
+    let mut args = message.args.iter();
+    while let Some(arg) = args.next() {
+        if crate::deep_link::is_deep_link(arg) {
+            action.deep_links.push(arg.clone());
+        } else if let Some(tool_id) = arg.strip_prefix("--tool=") {
+            action.tool = Some(tool_id.to_string());
+        } else if arg == "--tool" {
+            action.tool = args.next().cloned().or(action.tool);
+        } else if !arg.starts_with('-') {
+            // 文件路径相对于第二个实例的工作目录
+            let path = match cwd {
+                Some(cwd) => cwd.join(arg),
+                None => PathBuf::from(arg),
+            };
+            if path.is_file() {
+                action.files.push(path);
+            }
+        }
+    }
+    Some(action)
+}
+
+/// 处理转发过来的参数：显示主窗口，深层链接、工具和文件都由 Rust 端跳转到对应工具
+fn handle_forwarded(app_handle: &AppHandle, message: ForwardedArgs) {
+    log::info!("📨 收到其他实例的启动参数: {:?}", message.args);
+    let Some(action) = parse_forwarded(&message) else {
+        return;
+    };
+    crate::tray::show_main_window(app_handle);
+
+    for link in &action.deep_links {
+        crate::deep_link::handle(app_handle, link);
+    }
+
+    // 同时指定了工具和文件时用该工具打开文件
+    let tool = action.tool.as_deref();
+    match (tool, action.files.is_empty()) {
+        (Some(tool_id), true) => crate::menu_handler::open_tool(app_handle, tool_id),
+        _ => {
+            for path in &action.files {
+                crate::deep_link::open_file(app_handle, tool, path);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+mod platform {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    pub struct Endpoint {
+        listener: UnixListener,
+    }
+
+    fn socket_path(dir: &Path, name: &str) -> PathBuf {
+        dir.join(format!("{}.sock", name))
+    }
+
+    /// 创建只有当前用户可以访问的目录（0700）
+    ///
+    /// 目录已存在时可能是其他用户事先创建的，必须是当前用户拥有的真实目录且其他用户没有任何权限
+    pub fn private_dir(path: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+        match fs::DirBuilder::new().mode(0o700).create(path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        let metadata = fs::symlink_metadata(path)?;
+        // SAFETY: geteuid 没有前置条件，总是成功
+        let uid = unsafe { libc::geteuid() };
+        if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+            return Err(format!("运行时目录 {} 不是当前用户私有的目录", path.display()).into());
+        }
+        Ok(path.to_path_buf())
+    }
+
+    pub fn bind(dir: &Path, name: &str) -> Result<Endpoint, Box<dyn std::error::Error>> {
+        let path = socket_path(dir, name);
+        // 已持有锁，残留的 socket 文件一定来自异常退出的实例
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        // 目录本身只有当前用户可以访问，这里再收紧 socket 文件的权限
+        let listener = UnixListener::bind(&path)?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+        Ok(Endpoint { listener })
+    }
+
+    pub fn send(dir: &Path, name: &str, message: ForwardedArgs) -> Result<(), Box<dyn std::error::Error>> {
+        let stream = UnixStream::connect(socket_path(dir, name))?;
+        write_message(stream, &message)
+    }
+
+    fn set_timeouts(stream: UnixStream) -> std::io::Result<UnixStream> {
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+        Ok(stream)
+    }
+
+    impl Endpoint {
+        pub fn accept_loop(self, mut handle: impl FnMut(ForwardedArgs)) {
+            for stream in self.listener.incoming() {
+                match stream.and_then(set_timeouts).map_err(|e| e.into()).and_then(read_message) {
+                    Ok(message) => handle(message),
+                    Err(e) => log::warn!("⚠️ 读取转发参数失败: {}", e),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::*;
+    use chacha20poly1305::aead::rand_core::RngCore;
+    use chacha20poly1305::aead::OsRng;
+    use std::net::{Ipv4Addr, TcpListener, TcpStream};
+
+    /// 监听本机回环地址，端口和令牌写在运行时目录中
+    pub struct Endpoint {
+        listener: TcpListener,
+        token: String,
+    }
+
+    fn port_file(dir: &Path, name: &str) -> PathBuf {
+        dir.join(format!("{}.port", name))
+    }
+
+    /// Windows 的临时目录位于用户配置目录下，其他用户无法访问
+    pub fn private_dir(path: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        fs::create_dir_all(path)?;
+        Ok(path.to_path_buf())
+    }
+
+    pub fn bind(dir: &Path, name: &str) -> Result<Endpoint, Box<dyn std::error::Error>> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let port = listener.local_addr()?.port();
+
+        let mut seed = [0u8; 16];
+        OsRng.fill_bytes(&mut seed);
+        let token = format!("{:x}", Sha256::digest(seed));
+
+        fs::write(port_file(dir, name), format!("{}\n{}", port, token))?;
+        Ok(Endpoint { listener, token })
+    }
+
+    pub fn send(dir: &Path, name: &str, mut message: ForwardedArgs) -> Result<(), Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(port_file(dir, name))?;
+        let mut lines = content.lines();
+        let port: u16 = lines.next().ok_or("端口文件为空")?.trim().parse()?;
+        message.token = lines.next().map(|t| t.trim().to_string());
+
+        let stream = TcpStream::connect((Ipv4Addr::LOCALHOST, port))?;
+        write_message(stream, &message)
+    }
+
+    fn set_timeouts(stream: TcpStream) -> std::io::Result<TcpStream> {
+        stream.set_read_timeout(Some(IO_TIMEOUT))?;
+        stream.set_write_timeout(Some(IO_TIMEOUT))?;
+        Ok(stream)
+    }
+
+    impl Endpoint {
+        pub fn accept_loop(self, mut handle: impl FnMut(ForwardedArgs)) {
+            for stream in self.listener.incoming() {
+                match stream.and_then(set_timeouts).map_err(|e| e.into()).and_then(read_message) {
+                    Ok(message) if message.token.as_deref() == Some(self.token.as_str()) => handle(message),
+                    Ok(_) => log::warn!("⚠️ 拒绝令牌无效的转发请求"),
+                    Err(e) => log::warn!("⚠️ 读取转发参数失败: {}", e),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(args: &[&str], cwd: Option<&Path>) -> ForwardedArgs {
+        ForwardedArgs {
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            cwd: cwd.map(|p| p.to_string_lossy().to_string()),
+            token: None,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("utilx-instance-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn parse_forwarded_collects_links_tool_and_existing_files() {
+        let dir = temp_dir("parse");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("data.json"), "{}").unwrap();
+
+        let action = parse_forwarded(&message(
+            &["utilx://tool/json-formatter", "--tool", "json-formatter", "data.json", "missing.json", "--verbose"],
+            Some(&dir),
+        ))
+        .unwrap();
+        assert_eq!(action.deep_links, vec!["utilx://tool/json-formatter"]);
+        assert_eq!(action.tool.as_deref(), Some("json-formatter"));
+        assert_eq!(action.files, vec![dir.join("data.json")]);
+
+        let action = parse_forwarded(&message(&["--tool=hash-calculator"], None)).unwrap();
+        assert_eq!(action.tool.as_deref(), Some("hash-calculator"));
+        assert!(action.files.is_empty());
+
+        // 末尾的 --tool 没有值时保留之前的工具
+        let action = parse_forwarded(&message(&["--tool=hash-calculator", "--tool"], None)).unwrap();
+        assert_eq!(action.tool.as_deref(), Some("hash-calculator"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parse_forwarded_ignores_autostart_launches() {
+        assert_eq!(parse_forwarded(&message(&[crate::autostart::AUTOSTART_ARG, "--tool=x"], None)), None);
+    }
+
+    #[test]
+    fn forwarded_args_round_trip() {
+        let dir = temp_dir("forward");
+        let dir = platform::private_dir(&dir).unwrap();
+        let endpoint = platform::bind(&dir, "test").unwrap();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || endpoint.accept_loop(|message| sender.send(message).unwrap()));
+
+        platform::send(&dir, "test", message(&["--tool=base64", "a b.txt"], Some(Path::new("/work")))).unwrap();
+        let received = receiver.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+        assert_eq!(received.args, vec!["--tool=base64", "a b.txt"]);
+        assert_eq!(received.cwd.as_deref(), Some("/work"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_dir_rejects_directories_other_users_can_access() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir("private");
+        platform::private_dir(&dir).unwrap();
+        assert_eq!(fs::metadata(&dir).unwrap().permissions().mode() & 0o777, 0o700);
+
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(platform::private_dir(&dir).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+        let target = temp_dir("private-target");
+        fs::create_dir_all(&target).unwrap();
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o700)).unwrap();
+        std::os::unix::fs::symlink(&target, &dir).unwrap();
+        assert!(platform::private_dir(&dir).is_err());
+
+        let _ = fs::remove_file(&dir);
+        let _ = fs::remove_dir_all(&target);
+    }
+}
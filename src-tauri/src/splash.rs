@@ -0,0 +1,488 @@
+// 启动画面：独立的无边框窗口，主窗口在后台加载，主页面调用 app_ready 命令后关闭启动画面并显示主窗口
+use crate::i18n;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+use tokio::sync::watch;
+
+/// 启动画面窗口标签
+pub const SPLASH_LABEL: &str = "splash";
+
+/// 配置目录中的启动画面配置文件
+const CONFIG_FILE: &str = "splash-config.json";
+
+/// 配置目录中的自定义启动画面页面
+const HTML_FILE: &str = "splash.html";
+
+/// 自定义页面的最大长度
+const MAX_HTML_BYTES: usize = 256 * 1024;
+
+/// 自定义页面只允许内联样式和 data: 图片/字体，禁止脚本和网络请求
+const CUSTOM_HTML_CSP: &str = "default-src 'none'; style-src 'unsafe-inline'; img-src data:; font-src data:";
+
+/// 主页面是否已就绪（由 Tauri 管理）
+pub struct AppReadyState(watch::Sender<bool>);
+
+impl Default for AppReadyState {
+    fn default() -> Self {
+        Self(watch::channel(false).0)
+    }
+}
+
+/// 窗口尺寸（逻辑像素）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WindowSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// 开发模式下覆盖的配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DevelopmentOverrides {
+    pub duration: Option<u64>,
+    pub max_wait_time: Option<u64>,
+}
+
+/// 启动画面配置（splash-config.json）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+pub struct SplashConfig {
+    /// 是否显示启动画面
+    pub enabled: bool,
+    /// 启动画面最短显示时间（毫秒）；默认 0，主页面就绪后立即关闭
+    pub duration: u64,
+    /// 是否等待主页面就绪后再显示主窗口
+    pub wait_for_main_page: bool,
+    /// 等待主页面就绪的最长时间（毫秒），超时后显示错误状态
+    pub max_wait_time: u64,
+    /// 启动画面窗口尺寸
+    pub window_size: WindowSize,
+    /// 主窗口尺寸
+    pub main_window_size: WindowSize,
+    /// 启动画面是否居中
+    pub center_window: bool,
+    /// 开发模式下的覆盖配置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub development: Option<DevelopmentOverrides>,
+}
+
+impl Default for SplashConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            duration: 0,
+            wait_for_main_page: true,
+            max_wait_time: 15000,
+            window_size: WindowSize { width: 800.0, height: 600.0 },
+            main_window_size: WindowSize { width: 1200.0, height: 760.0 },
+            center_window: true,
+            development: None,
+        }
+    }
+}
+
+impl SplashConfig {
+    fn development(&self) -> Option<&DevelopmentOverrides> {
+        self.development.as_ref().filter(|_| cfg!(debug_assertions))
+    }
+
+    /// 最短显示时间（开发模式下使用 development 中的值）
+    pub fn min_duration(&self) -> Duration {
+        Duration::from_millis(self.development().and_then(|d| d.duration).unwrap_or(self.duration))
+    }
+
+    /// 等待主页面就绪的最长时间
+    pub fn max_wait(&self) -> Duration {
+        Duration::from_millis(self.development().and_then(|d| d.max_wait_time).unwrap_or(self.max_wait_time))
+    }
+
+    /// 解析并校验配置，未知字段和超出范围的数值都视为错误
+    pub fn parse(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let config: Self = serde_json::from_str(content)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 检查时长和窗口尺寸的范围
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let development = self.development.clone().unwrap_or_default();
+        for duration in std::iter::once(self.duration).chain(development.duration) {
+            if duration > 30_000 {
+                return Err(i18n::t("splash.invalid_duration").into());
+            }
+        }
+        for max_wait_time in std::iter::once(self.max_wait_time).chain(development.max_wait_time) {
+            if !(1_000..=120_000).contains(&max_wait_time) {
+                return Err(i18n::t("splash.invalid_max_wait").into());
+            }
+        }
+        for (id, size) in [("splash.invalid_window_size", self.window_size), ("splash.invalid_main_window_size", self.main_window_size)] {
+            let valid = |value: f64| (200.0..=4096.0).contains(&value);
+            if !valid(size.width) || !valid(size.height) {
+                return Err(i18n::t(id).into());
+            }
+        }
+        Ok(())
+    }
+
+    /// 从配置目录加载，文件不存在或无效时使用内置配置
+    pub fn load(app_handle: &AppHandle) -> Self {
+        match read_override(app_handle, CONFIG_FILE) {
+            Ok(Some(content)) => match Self::parse(&content) {
+                Ok(config) => {
+                    log::info!("📄 使用配置目录中的启动画面配置");
+                    return config;
+                }
+                Err(e) => log::warn!("⚠️ 启动画面配置无效: {}，使用内置配置", e),
+            },
+            Ok(None) => {}
+            Err(e) => log::warn!("⚠️ 无法读取启动画面配置: {}，使用内置配置", e),
+        }
+        Self::parse(DEFAULT_SPLASH_CONFIG).unwrap_or_else(|e| {
+            log::error!("❌ 内置启动画面配置无效: {}", e);
+            Self::default()
+        })
+    }
+
+    /// 保存到配置目录
+    pub fn save(&self, app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        self.validate()?;
+        let config_dir = crate::app_paths::config_dir(app_handle)?;
+        fs::create_dir_all(&config_dir)?;
+        fs::write(config_dir.join(CONFIG_FILE), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// 设置页面使用的启动画面设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplashSettings {
+    pub config: SplashConfig,
+    /// 自定义页面（已清理），None 表示使用内置页面
+    pub custom_html: Option<String>,
+}
+
+/// 读取配置目录中的覆盖文件
+fn read_override(app_handle: &AppHandle, file_name: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let path = crate::app_paths::config_dir(app_handle)?.join(file_name);
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(fs::read_to_string(path)?))
+}
+
+/// 清理自定义页面：移除脚本、内嵌框架、事件属性和脚本链接，并加上禁止脚本和网络请求的 CSP
+pub fn sanitize_html(html: &str) -> Result<String, Box<dyn std::error::Error>> {
+    if html.len() > MAX_HTML_BYTES {
+        return Err(i18n::tf("splash.html_too_large", &[("size", &(MAX_HTML_BYTES / 1024))]).into());
+    }
+
+    let script_blocks = regex::Regex::new(r"(?is)<script\b[^>]*>.*?</script\s*>")?;
+    let blocked_tags = regex::Regex::new(
+        r"(?is)</?(?:script|iframe|frame|frameset|object|embed|applet|base|form|link|meta)\b[^>]*>",
+    )?;
+    let event_attributes = regex::Regex::new(r#"(?i)\s+on[a-z]+\s*=\s*(?:"[^"]*"|'[^']*'|[^\s>]+)"#)?;
+    let script_urls = regex::Regex::new(r"(?i)(?:javascript|vbscript)\s*:")?;
+
+    let html = script_blocks.replace_all(html, "");
+    let html = blocked_tags.replace_all(&html, "");
+    let html = event_attributes.replace_all(&html, "");
+    let html = script_urls.replace_all(&html, "blocked:");
+
+    let policy = format!(
+        "<meta charset=\"UTF-8\"><meta http-equiv=\"Content-Security-Policy\" content=\"{}\">",
+        CUSTOM_HTML_CSP
+    );
+    let head = regex::Regex::new(r"(?i)<head\b[^>]*>")?;
+    Ok(match head.find(&html) {
+        Some(m) => format!("{}{}{}", &html[..m.end()], policy, &html[m.end()..]),
+        None => format!("{}{}", policy, html),
+    })
+}
+
+/// 读取配置目录中的自定义页面（已清理）
+pub fn load_custom_html(app_handle: &AppHandle) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    match read_override(app_handle, HTML_FILE)? {
+        Some(html) => Ok(Some(sanitize_html(&html)?)),
+        None => Ok(None),
+    }
+}
+
+/// 保存自定义页面，None 或空内容表示恢复内置页面
+pub fn save_custom_html(app_handle: &AppHandle, html: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let path = crate::app_paths::config_dir(app_handle)?.join(HTML_FILE);
+    match html.map(str::trim).filter(|html| !html.is_empty()) {
+        Some(html) => {
+            let html = sanitize_html(html)?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, html)?;
+        }
+        None => {
+            if path.exists() {
+                fs::remove_file(&path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 启动时使用的页面：配置目录中的自定义页面，或内置页面
+pub fn load_html(app_handle: &AppHandle) -> String {
+    match load_custom_html(app_handle) {
+        Ok(Some(html)) => {
+            log::info!("✅ 使用自定义启动画面页面，大小: {} 字符", html.len());
+            html
+        }
+        Ok(None) => DEFAULT_SPLASH_HTML.to_string(),
+        Err(e) => {
+            log::warn!("⚠️ 无法读取自定义启动画面页面: {}，使用内置页面", e);
+            DEFAULT_SPLASH_HTML.to_string()
+        }
+    }
+}
+
+/// 内置的启动画面配置
+pub const DEFAULT_SPLASH_CONFIG: &str = include_str!("../splash-config.json");
+
+/// 内置的启动画面页面
+pub const DEFAULT_SPLASH_HTML: &str = include_str!("../splash.html");
+
+/// 启动画面页面没有提供 showStartupError 时使用的错误页
+const TIMEOUT_HTML: &str = r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="UTF-8">
+<title>有条工具</title>
+<style>
+  html, body { width: 100%; height: 100%; margin: 0; }
+  body {
+    font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
+    background: linear-gradient(135deg, #1e3a8a 0%, #1e40af 50%, #3730a3 100%);
+    color: white; display: flex; flex-direction: column; justify-content: center; align-items: center;
+  }
+  h1 { font-size: 28px; margin-bottom: 12px; }
+  p { color: #cbd5e1; }
+</style>
+</head>
+<body>
+  <h1>启动超时</h1>
+  <p id="message"></p>
+</body>
+</html>"#;
+
+/// 主页面通知已就绪
+pub fn mark_ready(app_handle: &AppHandle) {
+    if let Some(state) = app_handle.try_state::<AppReadyState>() {
+        if !state.0.send_replace(true) {
+            log::info!("✅ 主页面已就绪");
+        }
+    }
+}
+
+/// 等待主页面就绪；timeout 为 None 时一直等待。超时返回 false
+pub async fn wait_ready(app_handle: &AppHandle, timeout: Option<Duration>) -> bool {
+    let Some(state) = app_handle.try_state::<AppReadyState>() else {
+        return false;
+    };
+    let mut ready = state.0.subscribe();
+    match timeout {
+        Some(timeout) => {
+            let result = tokio::time::timeout(timeout, ready.wait_for(|ready| *ready)).await;
+            matches!(result, Ok(Ok(_)))
+        }
+        None => ready.wait_for(|ready| *ready).await.is_ok(),
+    }
+}
+
+/// 创建无边框的启动画面窗口，页面内容来自 html。data: 页面没有应用的源，无法调用任何命令
+fn create_window(app_handle: &AppHandle, config: &SplashConfig, html: &str) -> Result<tauri::WebviewWindow, Box<dyn std::error::Error>> {
+    let url = format!("data:text/html;charset=utf-8;base64,{}", BASE64.encode(html));
+    let builder = WebviewWindowBuilder::new(app_handle, SPLASH_LABEL, WebviewUrl::External(url.parse()?))
+        .title(i18n::t("app.name"))
+        .inner_size(config.window_size.width, config.window_size.height)
+        .decorations(false)
+        .resizable(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .focused(true);
+    let builder = if config.center_window { builder.center() } else { builder };
+
+    let window = builder.build()?;
+    // Windows/Linux 上应用菜单会附加到所有窗口，启动画面不需要
+    #[cfg(not(target_os = "macos"))]
+    let _ = window.remove_menu();
+    Ok(window)
+}
+
+/// 按配置设置主窗口尺寸并居中，返回是否找到主窗口
+fn prepare_main_window(app_handle: &AppHandle, config: &SplashConfig) -> bool {
+    let Some(window) = app_handle.get_webview_window(crate::tool_windows::MAIN_LABEL) else {
+        log::error!("❌ 无法找到主窗口");
+        return false;
+    };
+
+    let size = config.main_window_size;
+    if let Err(e) = window.set_size(tauri::Size::Logical(tauri::LogicalSize { width: size.width, height: size.height })) {
+        log::error!("❌ 调整主窗口尺寸失败: {}", e);
+    }
+    if let Err(e) = window.center() {
+        log::error!("❌ 主窗口居中失败: {}", e);
+    }
+    true
+}
+
+/// 按配置设置主窗口尺寸并显示
+fn show_main_window(app_handle: &AppHandle, config: &SplashConfig) {
+    if !prepare_main_window(app_handle, config) {
+        return;
+    }
+    crate::tray::show_main_window(app_handle);
+
+    // 主窗口显示后再恢复上次打开的工具窗口
+    crate::tool_windows::restore(app_handle);
+}
+
+/// 在启动画面中显示超时错误，并允许用户关闭它
+fn show_timeout_error(splash: &tauri::WebviewWindow, timeout: Duration) -> tauri::Result<()> {
+    let message = i18n::tf("splash.startup_timeout", &[("seconds", &timeout.as_secs())]);
+    let script = format!(
+        "(function (message) {{
+            if (typeof window.showStartupError === 'function') {{
+                window.showStartupError(message);
+            }} else {{
+                document.open(); document.write({html}); document.close();
+                document.getElementById('message').textContent = message;
+            }}
+        }})({message});",
+        html = serde_json::to_string(TIMEOUT_HTML).unwrap_or_default(),
+        message = serde_json::to_string(&message).unwrap_or_default(),
+    );
+    splash.eval(script)?;
+    splash.set_always_on_top(false)?;
+    splash.set_decorations(true)?;
+    Ok(())
+}
+
+/// 开机自启动时不显示启动画面，主窗口在后台加载并保持隐藏，之后从托盘打开
+pub fn start_hidden(app_handle: &AppHandle, config: &SplashConfig) {
+    if prepare_main_window(app_handle, config) {
+        log::info!("🌙 开机自启动，主窗口保持隐藏");
+    }
+}
+
+/// 显示启动画面，主页面就绪后关闭并显示主窗口
+pub fn start(app_handle: &AppHandle, config: SplashConfig, html: &str) {
+    if !config.enabled {
+        log::info!("📄 启动画面已禁用，直接显示主窗口");
+        show_main_window(app_handle, &config);
+        return;
+    }
+
+    let splash = match create_window(app_handle, &config, html) {
+        Ok(window) => window,
+        Err(e) => {
+            log::error!("❌ 创建启动画面窗口失败: {}，直接显示主窗口", e);
+            show_main_window(app_handle, &config);
+            return;
+        }
+    };
+    log::info!("🪟 启动画面窗口已创建");
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let started = Instant::now();
+        let min_duration = config.min_duration();
+        let max_wait = config.max_wait();
+
+        let ready = !config.wait_for_main_page || wait_ready(&app_handle, Some(max_wait)).await;
+        if !ready {
+            log::error!("❌ 主页面在{}ms内未就绪", max_wait.as_millis());
+            if let Err(e) = show_timeout_error(&splash, max_wait) {
+                log::error!("❌ 显示启动超时状态失败: {}", e);
+            }
+            // 仍然显示主窗口，页面稍后就绪时再关闭启动画面
+            show_main_window(&app_handle, &config);
+            wait_ready(&app_handle, None).await;
+            let _ = splash.close();
+            return;
+        }
+
+        if let Some(remaining) = min_duration.checked_sub(started.elapsed()) {
+            tokio::time::sleep(remaining).await;
+        }
+
+        show_main_window(&app_handle, &config);
+        if let Err(e) = splash.close() {
+            log::warn!("⚠️ 关闭启动画面失败: {}", e);
+        }
+        log::info!("🎉 启动流程完成 (总耗时: {}ms)", started.elapsed().as_millis());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_builtin_config() {
+        let config = SplashConfig::parse(DEFAULT_SPLASH_CONFIG).unwrap();
+        assert_eq!(config.min_duration(), Duration::from_millis(config.duration));
+    }
+
+    #[test]
+    fn parse_fills_missing_fields_with_defaults() {
+        let config = SplashConfig::parse(r#"{ "enabled": false, "maxWaitTime": 5000 }"#).unwrap();
+        assert!(!config.enabled);
+        assert_eq!(config.max_wait(), Duration::from_millis(5000));
+        assert_eq!(config.duration, SplashConfig::default().duration);
+    }
+
+    #[test]
+    fn parse_rejects_unknown_fields() {
+        assert!(SplashConfig::parse(r#"{ "enabeld": false }"#).is_err());
+        assert!(SplashConfig::parse(r#"{ "development": { "delay": 1 } }"#).is_err());
+    }
+
+    #[test]
+    fn parse_rejects_out_of_range_values() {
+        assert!(SplashConfig::parse(r#"{ "duration": 60000 }"#).is_err());
+        assert!(SplashConfig::parse(r#"{ "maxWaitTime": 500 }"#).is_err());
+        assert!(SplashConfig::parse(r#"{ "development": { "maxWaitTime": 200000 } }"#).is_err());
+        assert!(SplashConfig::parse(r#"{ "windowSize": { "width": 100, "height": 600 } }"#).is_err());
+        assert!(SplashConfig::parse(r#"{ "mainWindowSize": { "width": 1200, "height": 5000 } }"#).is_err());
+    }
+
+    #[test]
+    fn sanitize_html_removes_scripts_and_handlers() {
+        let html = r#"<html><head><script src="x.js"></script></head><body onload="run()"><script>alert(1)</script><iframe src="https://example.com"></iframe><a href="javascript:alert(1)">x</a><p>Loading</p></body></html>"#;
+        let sanitized = sanitize_html(html).unwrap();
+        assert!(!sanitized.to_lowercase().contains("<script"));
+        assert!(!sanitized.contains("alert(1)</script>"));
+        assert!(!sanitized.contains("onload"));
+        assert!(!sanitized.contains("<iframe"));
+        assert!(!sanitized.contains("javascript:"));
+        assert!(sanitized.contains("<p>Loading</p>"));
+    }
+
+    #[test]
+    fn sanitize_html_inserts_csp() {
+        let with_head = sanitize_html("<html><head><title>x</title></head></html>").unwrap();
+        assert!(with_head.starts_with("<html><head><meta charset=\"UTF-8\"><meta http-equiv=\"Content-Security-Policy\""));
+        assert!(with_head.contains(CUSTOM_HTML_CSP));
+
+        let fragment = sanitize_html("<p>x</p>").unwrap();
+        assert!(fragment.contains(CUSTOM_HTML_CSP));
+        assert!(fragment.ends_with("<p>x</p>"));
+    }
+
+    #[test]
+    fn sanitize_html_rejects_oversized_pages() {
+        assert!(sanitize_html(&"a".repeat(MAX_HTML_BYTES + 1)).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::i18n;
+use crate::splash::{SplashConfig, SplashSettings};
+use tauri::{AppHandle, command};
+
+/// 主页面挂载完成后调用，结束启动画面
+#[command]
+pub async fn app_ready(app_handle: AppHandle) -> Result<(), String> {
+    crate::splash::mark_ready(&app_handle);
+    Ok(())
+}
+
+/// 获取启动画面设置
+#[command]
+pub async fn get_splash_config(app_handle: AppHandle) -> Result<SplashSettings, String> {
+    let custom_html = crate::splash::load_custom_html(&app_handle)
+        .map_err(|e| i18n::tf("splash.read_html_failed", &[("error", &e)]))?;
+    Ok(SplashSettings {
+        config: SplashConfig::load(&app_handle),
+        custom_html,
+    })
+}
+
+/// 保存启动画面设置，下次启动时生效
+#[command]
+pub async fn save_splash_config(
+    app_handle: AppHandle,
+    config: SplashConfig,
+    custom_html: Option<String>,
+) -> Result<String, String> {
+    config.save(&app_handle)
+        .map_err(|e| i18n::tf("splash.save_config_failed", &[("error", &e)]))?;
+    crate::splash::save_custom_html(&app_handle, custom_html.as_deref())
+        .map_err(|e| i18n::tf("splash.save_html_failed", &[("error", &e)]))?;
+
+    log::info!("🎨 启动画面设置已保存");
+    Ok(i18n::t("splash.saved"))
+}
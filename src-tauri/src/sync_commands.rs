@@ -0,0 +1,46 @@
+use crate::i18n;
+use crate::settings_sync::{self, ConflictChoice, SyncConfig, SyncReport, SyncStatus};
+use tauri::{AppHandle, Emitter, command};
+
+/// 获取同步配置
+#[command]
+pub async fn get_sync_config(app_handle: AppHandle) -> Result<SyncConfig, String> {
+    SyncConfig::load(&app_handle)
+        .map_err(|e| i18n::tf("sync.read_config_failed", &[("error", &e)]))
+}
+
+/// 保存同步配置
+#[command]
+pub async fn save_sync_config(app_handle: AppHandle, config: SyncConfig) -> Result<String, String> {
+    config.save(&app_handle)
+        .map_err(|e| i18n::tf("sync.save_config_failed", &[("error", &e)]))?;
+    Ok(i18n::t("sync.config_saved"))
+}
+
+/// 获取同步状态（上次同步时间、是否在等待保险库解锁）
+#[command]
+pub async fn get_sync_status(app_handle: AppHandle) -> Result<SyncStatus, String> {
+    settings_sync::status(&app_handle)
+        .map_err(|e| i18n::tf("sync.read_config_failed", &[("error", &e)]))
+}
+
+/// 立即同步
+#[command]
+pub async fn sync_now(app_handle: AppHandle) -> Result<SyncReport, String> {
+    let report = settings_sync::sync_now(&app_handle).await
+        .map_err(|e| i18n::tf("sync.failed", &[("error", &e)]))?;
+    let _ = app_handle.emit("sync-completed", &report);
+    Ok(report)
+}
+
+/// 解决同步冲突，`choice` 为 `local` 或 `remote`
+#[command]
+pub async fn resolve_sync_conflict(
+    app_handle: AppHandle,
+    section: String,
+    choice: ConflictChoice,
+) -> Result<String, String> {
+    settings_sync::resolve_conflict(&app_handle, &section, choice).await
+        .map_err(|e| i18n::tf("sync.resolve_failed", &[("error", &e)]))?;
+    Ok(i18n::t("sync.resolved"))
+}
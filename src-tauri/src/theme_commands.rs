@@ -0,0 +1,8 @@
+use crate::theme_sync::{self, ThemeInfo};
+use tauri::{AppHandle, command};
+
+/// 获取主题偏好、系统外观和实际使用的外观
+#[command]
+pub async fn get_theme_state(app_handle: AppHandle) -> Result<ThemeInfo, String> {
+    Ok(theme_sync::current(&app_handle))
+}
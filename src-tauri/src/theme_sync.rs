@@ -0,0 +1,223 @@
+// 主题同步：跟随系统的亮色/暗色偏好（Linux 通过 XDG 桌面门户读取），应用到原生窗口、菜单和托盘图标，并通知前端
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Theme};
+
+/// 主题变化时发给所有窗口的事件
+pub const CHANGED_EVENT: &str = "theme-changed";
+
+/// 用户设置中的主题偏好
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreference {
+    /// 跟随系统
+    #[default]
+    Auto,
+    Light,
+    Dark,
+}
+
+/// 亮色或暗色外观
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Appearance {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl From<Theme> for Appearance {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Dark => Appearance::Dark,
+            _ => Appearance::Light,
+        }
+    }
+}
+
+impl From<Appearance> for Theme {
+    fn from(appearance: Appearance) -> Self {
+        match appearance {
+            Appearance::Light => Theme::Light,
+            Appearance::Dark => Theme::Dark,
+        }
+    }
+}
+
+/// 当前主题状态
+#[derive(Debug, Clone, Serialize)]
+pub struct ThemeInfo {
+    /// 用户设置的偏好
+    pub preference: ThemePreference,
+    /// 系统的外观
+    pub system: Appearance,
+    /// 实际使用的外观：明确设置亮色/暗色时覆盖系统外观
+    pub effective: Appearance,
+}
+
+/// 最近一次读取到的系统外观（由 Tauri 管理）
+#[derive(Default)]
+pub struct ThemeState(Mutex<Appearance>);
+
+/// 读取用户设置中的主题偏好，无效值按跟随系统处理
+pub fn preference(app_handle: &AppHandle) -> ThemePreference {
+    let profile = crate::config_profiles::active_profile(app_handle);
+    crate::config_profiles::read_effective(app_handle, profile.as_deref(), "user-settings.json")
+        .ok()
+        .flatten()
+        .and_then(|settings| settings.get("theme").cloned())
+        .and_then(|theme| serde_json::from_value(theme).ok())
+        .unwrap_or_default()
+}
+
+/// 当前主题状态
+pub fn current(app_handle: &AppHandle) -> ThemeInfo {
+    let preference = preference(app_handle);
+    let system = app_handle.try_state::<ThemeState>().map(|state| *state.0.lock().unwrap()).unwrap_or_default();
+    let effective = match preference {
+        ThemePreference::Auto => system,
+        ThemePreference::Light => Appearance::Light,
+        ThemePreference::Dark => Appearance::Dark,
+    };
+    ThemeInfo { preference, system, effective }
+}
+
+/// 把主题应用到窗口、菜单和托盘图标，并通知前端；设置变化后调用
+pub fn apply(app_handle: &AppHandle) {
+    let info = current(app_handle);
+
+    // macOS / Windows 上不指定主题时窗口和菜单自动跟随系统；Linux 的 GTK 主题不一定反映门户设置，始终明确指定
+    let native_theme = match info.preference {
+        ThemePreference::Auto if !cfg!(target_os = "linux") => None,
+        _ => Some(info.effective.into()),
+    };
+    app_handle.set_theme(native_theme);
+
+    // 托盘图标显示在系统任务栏/菜单栏中，跟随系统外观而不是应用主题
+    crate::tray::apply_theme(app_handle, info.system);
+
+    if let Err(e) = app_handle.emit(CHANGED_EVENT, &info) {
+        log::warn!("⚠️ 发送主题变化事件失败: {}", e);
+    }
+    log::info!("🌓 主题: {:?} (系统: {:?}, 实际: {:?})", info.preference, info.system, info.effective);
+}
+
+/// 记录新的系统外观，有变化时重新应用主题
+fn set_system(app_handle: &AppHandle, appearance: Appearance) {
+    let changed = {
+        let state = app_handle.state::<ThemeState>();
+        let mut system = state.0.lock().unwrap();
+        std::mem::replace(&mut *system, appearance) != appearance
+    };
+    if changed {
+        log::info!("🌓 系统外观已变化: {:?}", appearance);
+        apply(app_handle);
+    }
+}
+
+/// 窗口主题变化时调用（macOS / Windows）；只有跟随系统时窗口主题才反映系统外观
+pub fn on_window_theme_changed(app_handle: &AppHandle, theme: Theme) {
+    if cfg!(target_os = "linux") || preference(app_handle) != ThemePreference::Auto {
+        return;
+    }
+    set_system(app_handle, theme.into());
+}
+
+/// 读取系统外观并应用主题；Linux 上在后台监听门户的设置变化
+pub fn start(app_handle: &AppHandle) {
+    app_handle.manage(ThemeState::default());
+
+    #[cfg(target_os = "linux")]
+    {
+        match portal::read() {
+            Ok(Some(appearance)) => *app_handle.state::<ThemeState>().0.lock().unwrap() = appearance,
+            Ok(None) => log::info!("ℹ️ 桌面门户没有提供外观设置，使用亮色"),
+            Err(e) => log::warn!("⚠️ 读取系统外观失败: {}", e),
+        }
+
+        let watch_handle = app_handle.clone();
+        std::thread::spawn(move || {
+            let callback_handle = watch_handle.clone();
+            if let Err(e) = portal::watch(move |appearance| {
+                // 窗口和托盘需要在主线程上更新
+                let handle = callback_handle.clone();
+                let _ = callback_handle.run_on_main_thread(move || set_system(&handle, appearance));
+            }) {
+                log::warn!("⚠️ 监听系统外观变化失败: {}", e);
+            }
+        });
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let system = app_handle
+            .get_webview_window(crate::tool_windows::MAIN_LABEL)
+            .and_then(|window| window.theme().ok());
+        if let Some(theme) = system {
+            *app_handle.state::<ThemeState>().0.lock().unwrap() = theme.into();
+        }
+    }
+
+    apply(app_handle);
+}
+
+/// XDG 桌面门户的外观设置（org.freedesktop.appearance color-scheme：0 无偏好，1 暗色，2 亮色）
+#[cfg(target_os = "linux")]
+mod portal {
+    use super::Appearance;
+    use zbus::blocking::{Connection, Proxy};
+    use zbus::zvariant::{OwnedValue, Value};
+
+    const DESTINATION: &str = "org.freedesktop.portal.Desktop";
+    const PATH: &str = "/org/freedesktop/portal/desktop";
+    const INTERFACE: &str = "org.freedesktop.portal.Settings";
+    const NAMESPACE: &str = "org.freedesktop.appearance";
+    const KEY: &str = "color-scheme";
+
+    fn proxy(connection: &Connection) -> zbus::Result<Proxy<'static>> {
+        Proxy::new(connection, DESTINATION, PATH, INTERFACE)
+    }
+
+    /// 旧版门户的 Read 会多包一层变体
+    fn to_appearance(value: &Value) -> Option<Appearance> {
+        match value {
+            Value::U32(1) => Some(Appearance::Dark),
+            Value::U32(_) => Some(Appearance::Light),
+            Value::Value(inner) => to_appearance(inner),
+            _ => None,
+        }
+    }
+
+    /// 读取当前外观；门户不可用或没有该设置时返回错误或 None
+    pub fn read() -> Result<Option<Appearance>, Box<dyn std::error::Error>> {
+        let connection = Connection::session()?;
+        let proxy = proxy(&connection)?;
+        // ReadOne 从门户第 2 版开始提供，旧版本只有 Read
+        let value: OwnedValue = match proxy.call("ReadOne", &(NAMESPACE, KEY)) {
+            Ok(value) => value,
+            Err(_) => proxy.call("Read", &(NAMESPACE, KEY))?,
+        };
+        Ok(to_appearance(&value))
+    }
+
+    /// 阻塞监听外观变化，每次变化调用 callback
+    pub fn watch(callback: impl Fn(Appearance)) -> Result<(), Box<dyn std::error::Error>> {
+        let connection = Connection::session()?;
+        let proxy = proxy(&connection)?;
+        for message in proxy.receive_signal("SettingChanged")? {
+            let (namespace, key, value): (String, String, OwnedValue) = match message.body().deserialize() {
+                Ok(body) => body,
+                Err(e) => {
+                    log::warn!("⚠️ 解析门户设置变化失败: {}", e);
+                    continue;
+                }
+            };
+            if namespace == NAMESPACE && key == KEY {
+                if let Some(appearance) = to_appearance(&value) {
+                    callback(appearance);
+                }
+            }
+        }
+        Ok(())
+    }
+}
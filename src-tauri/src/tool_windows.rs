@@ -0,0 +1,146 @@
+// 工具独立窗口：按标签跟踪弹出的工具窗口，重启后恢复；位置和尺寸由 window-state 插件保存
+use crate::i18n;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+/// 主窗口标签
+pub const MAIN_LABEL: &str = "main";
+
+/// 工具窗口标签前缀，完整标签为 `tool-<工具 ID>`
+pub const TOOL_LABEL_PREFIX: &str = "tool-";
+
+/// 打开的工具窗口列表（位于配置目录）
+const CONFIG_FILE: &str = "tool-windows.json";
+
+/// 工具窗口的默认尺寸
+const DEFAULT_SIZE: (f64, f64) = (520.0, 720.0);
+
+/// 工具窗口的最小尺寸
+const MIN_SIZE: (f64, f64) = (360.0, 400.0);
+
+/// 一个弹出的工具窗口
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolWindow {
+    pub label: String,
+    pub tool: String,
+}
+
+/// 保存在配置目录中的窗口列表
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ToolWindowsFile {
+    windows: Vec<ToolWindow>,
+}
+
+/// 当前打开的工具窗口（由 Tauri 管理）
+#[derive(Default)]
+pub struct ToolWindowsState(Mutex<BTreeMap<String, ToolWindow>>);
+
+/// 工具对应的窗口标签
+pub fn label_for(tool: &str) -> String {
+    format!("{}{}", TOOL_LABEL_PREFIX, tool)
+}
+
+/// 是否为工具窗口
+pub fn is_tool_window(label: &str) -> bool {
+    label.starts_with(TOOL_LABEL_PREFIX)
+}
+
+fn load_file(app_handle: &AppHandle) -> Result<ToolWindowsFile, Box<dyn std::error::Error>> {
+    let path = crate::app_paths::config_dir(app_handle)?.join(CONFIG_FILE);
+    if !path.exists() {
+        return Ok(ToolWindowsFile::default());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// 保存当前打开的工具窗口，下次启动时恢复
+fn persist(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let file = ToolWindowsFile { windows: list(app_handle) };
+    let config_dir = crate::app_paths::config_dir(app_handle)?;
+    fs::create_dir_all(&config_dir)?;
+    fs::write(config_dir.join(CONFIG_FILE), serde_json::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+/// 当前打开的工具窗口
+pub fn list(app_handle: &AppHandle) -> Vec<ToolWindow> {
+    app_handle
+        .try_state::<ToolWindowsState>()
+        .map(|state| state.0.lock().unwrap().values().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// 在独立窗口中打开工具；已经打开时只显示并聚焦
+pub fn open(app_handle: &AppHandle, tool: &str) -> Result<ToolWindow, Box<dyn std::error::Error>> {
+    let route = crate::routes::find_tool(tool).ok_or_else(|| i18n::tf("tools.unknown", &[("tool", &tool)]))?;
+    let label = label_for(&route.id);
+    let entry = ToolWindow { label: label.clone(), tool: route.id.clone() };
+
+    if let Some(window) = app_handle.get_webview_window(&label) {
+        window.unminimize()?;
+        window.show()?;
+        window.set_focus()?;
+        return Ok(entry);
+    }
+
+    // window-state 插件会在窗口创建时恢复上次的位置和尺寸
+    let window = WebviewWindowBuilder::new(app_handle, &label, WebviewUrl::App(format!("tools/{}/", route.id).into()))
+        .title(i18n::tf("window.tool_title", &[("tool", &route.name)]))
+        .inner_size(DEFAULT_SIZE.0, DEFAULT_SIZE.1)
+        .min_inner_size(MIN_SIZE.0, MIN_SIZE.1)
+        .build()?;
+    // Windows/Linux 上应用菜单会附加到所有窗口，工具窗口不需要
+    #[cfg(not(target_os = "macos"))]
+    let _ = window.remove_menu();
+
+    app_handle.state::<ToolWindowsState>().0.lock().unwrap().insert(label.clone(), entry.clone());
+    if let Err(e) = persist(app_handle) {
+        log::warn!("⚠️ 保存工具窗口列表失败: {}", e);
+    }
+    log::info!("🪟 已打开工具窗口: {}", label);
+    Ok(entry)
+}
+
+/// 用户关闭工具窗口时调用，之后不再恢复该窗口（退出应用时不会调用，窗口会在下次启动时恢复）
+pub fn forget(app_handle: &AppHandle, label: &str) {
+    let removed = app_handle
+        .try_state::<ToolWindowsState>()
+        .and_then(|state| state.0.lock().unwrap().remove(label))
+        .is_some();
+    if removed {
+        if let Err(e) = persist(app_handle) {
+            log::warn!("⚠️ 保存工具窗口列表失败: {}", e);
+        }
+        log::info!("🪟 已关闭工具窗口: {}", label);
+    }
+}
+
+/// 恢复上次退出时打开的工具窗口
+pub fn restore(app_handle: &AppHandle) {
+    let file = match load_file(app_handle) {
+        Ok(file) => file,
+        Err(e) => {
+            log::warn!("⚠️ 读取工具窗口列表失败: {}", e);
+            return;
+        }
+    };
+
+    for window in file.windows {
+        if let Err(e) = open(app_handle, &window.tool) {
+            log::warn!("⚠️ 恢复工具窗口 {} 失败: {}", window.label, e);
+        }
+    }
+}
+
+/// 当前获得焦点的窗口，没有时返回主窗口
+pub fn focused_window(app_handle: &AppHandle) -> Option<WebviewWindow> {
+    app_handle
+        .webview_windows()
+        .into_values()
+        .find(|window| window.is_focused().unwrap_or(false))
+        .or_else(|| app_handle.get_webview_window(MAIN_LABEL))
+}
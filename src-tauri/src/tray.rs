@@ -0,0 +1,255 @@
+// 系统托盘：菜单文案来自消息目录，跟随 language 设置
+use crate::i18n;
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::image::Image;
+use tauri::{AppHandle, Manager};
+
+/// 托盘图标 ID
+pub const TRAY_ID: &str = "main";
+
+/// 托盘菜单中打开工具的菜单项 ID 前缀
+pub const OPEN_TOOL_PREFIX: &str = "open_tool:";
+
+/// 最近使用的工具最多保留数量
+const MAX_RECENT_TOOLS: usize = 10;
+
+/// 工具条目，兼容只保存 ID 的旧格式；未保存名称时使用路由表中的名称，未知工具会被忽略
+fn tool_entry(value: &serde_json::Value) -> Option<(String, String)> {
+    let (id, name) = match value {
+        serde_json::Value::String(id) => (id.as_str(), None),
+        serde_json::Value::Object(map) => (map.get("id")?.as_str()?, map.get("name").and_then(|n| n.as_str())),
+        _ => return None,
+    };
+
+    let route = crate::routes::find_tool(id)?;
+    Some((route.id.clone(), name.unwrap_or(&route.name).to_string()))
+}
+
+/// 读取当前生效的用户设置
+fn load_user_settings(app_handle: &AppHandle) -> serde_json::Value {
+    let profile = crate::config_profiles::active_profile(app_handle);
+    crate::config_profiles::read_effective(app_handle, profile.as_deref(), "user-settings.json")
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// 读取用户设置 `tools` 对象中 `<key>` 下的工具列表
+fn tool_list(tools: Option<&serde_json::Value>, key: &str) -> Vec<(String, String)> {
+    tools
+        .and_then(|tools| tools.get(key))
+        .and_then(|list| list.as_array())
+        .map(|list| list.iter().filter_map(tool_entry).collect())
+        .unwrap_or_default()
+}
+
+/// 是否开启了关闭到托盘
+pub fn minimize_to_tray_enabled(app_handle: &AppHandle) -> bool {
+    load_user_settings(app_handle)
+        .get("minimize_to_tray")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true)
+}
+
+/// 根据收藏和最近使用的工具构建托盘菜单
+fn build_menu(app_handle: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let settings = load_user_settings(app_handle);
+
+    let toggle_item = MenuItem::with_id(app_handle, "toggle_window", i18n::t("tray.toggle_window"), true, None::<&str>)?;
+    let home_item = MenuItem::with_id(app_handle, "go_home", i18n::t("menu.go_home"), true, None::<&str>)?;
+    let mini_item = MenuItem::with_id(app_handle, "toggle_mini_mode", i18n::t("menu.mini_mode"), true, None::<&str>)?;
+    let click_through_item = MenuItem::with_id(app_handle, "toggle_mini_click_through", i18n::t("menu.mini_click_through"), true, None::<&str>)?;
+
+    let favorites_menu = Submenu::new(app_handle, i18n::t("menu.favorites"), true)?;
+    let favorites = tool_list(settings.get("tools"), "favorites");
+    if favorites.is_empty() {
+        favorites_menu.append(&MenuItem::new(app_handle, i18n::t("menu.no_favorites"), false, None::<&str>)?)?;
+    }
+    for (id, name) in favorites {
+        favorites_menu.append(&MenuItem::with_id(app_handle, format!("{}{}", OPEN_TOOL_PREFIX, id), name, true, None::<&str>)?)?;
+    }
+
+    let recent_menu = Submenu::new(app_handle, i18n::t("tray.recent"), true)?;
+    let recent = tool_list(settings.get("tools"), "recent");
+    if recent.is_empty() {
+        recent_menu.append(&MenuItem::new(app_handle, i18n::t("tray.no_recent"), false, None::<&str>)?)?;
+    }
+    for (id, name) in recent {
+        recent_menu.append(&MenuItem::with_id(app_handle, format!("{}{}", OPEN_TOOL_PREFIX, id), name, true, None::<&str>)?)?;
+    }
+
+    let quit_item = MenuItem::with_id(app_handle, "quit_app", i18n::t("menu.quit"), true, None::<&str>)?;
+
+    Menu::with_items(app_handle, &[
+        &toggle_item,
+        &home_item,
+        &mini_item,
+        &click_through_item,
+        &PredefinedMenuItem::separator(app_handle)?,
+        &favorites_menu,
+        &recent_menu,
+        &PredefinedMenuItem::separator(app_handle)?,
+        &quit_item,
+    ])
+}
+
+/// 创建托盘图标；菜单点击由全局菜单事件统一交给 menu_handler 处理
+pub fn create_tray(app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_menu(app_handle)?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(i18n::t("tray.tooltip"))
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+                toggle_main_window(tray.app_handle());
+            }
+        });
+
+    if let Some(icon) = app_handle.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    builder.build(app_handle)?;
+    Ok(())
+}
+
+/// 由应用图标生成单色托盘图标：保留图标中的亮色字形，去掉彩色背景
+/// 暗色系统使用白色字形，亮色系统使用黑色字形
+fn monochrome_icon(icon: &Image<'_>, appearance: crate::theme_sync::Appearance) -> Image<'static> {
+    let shade = match appearance {
+        crate::theme_sync::Appearance::Dark => 255,
+        crate::theme_sync::Appearance::Light => 0,
+    };
+    let rgba = icon
+        .rgba()
+        .chunks_exact(4)
+        .flat_map(|pixel| {
+            // 字形为白色，背景为彩色；用最暗的通道区分，边缘的抗锯齿像素得到半透明
+            let brightness = pixel[0].min(pixel[1]).min(pixel[2]) as u32;
+            let coverage = brightness.saturating_sub(100) * 255 / 155;
+            let alpha = (coverage * pixel[3] as u32 / 255) as u8;
+            [shade, shade, shade, alpha]
+        })
+        .collect();
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
+
+/// 按系统外观切换托盘图标（macOS 使用模板图标，由系统着色）
+pub fn apply_theme(app_handle: &AppHandle, appearance: crate::theme_sync::Appearance) {
+    let (Some(tray), Some(icon)) = (app_handle.tray_by_id(TRAY_ID), app_handle.default_window_icon()) else {
+        return;
+    };
+
+    let result = if cfg!(target_os = "macos") {
+        tray.set_icon(Some(monochrome_icon(icon, crate::theme_sync::Appearance::Light)))
+            .and_then(|_| tray.set_icon_as_template(true))
+    } else {
+        tray.set_icon(Some(monochrome_icon(icon, appearance)))
+    };
+    if let Err(e) = result {
+        log::warn!("⚠️ 更新托盘图标失败: {}", e);
+    }
+}
+
+/// 收藏、最近使用或语言变化后重建托盘菜单
+pub fn refresh_menu(app_handle: &AppHandle) {
+    let Some(tray) = app_handle.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    if let Err(e) = tray.set_tooltip(Some(i18n::t("tray.tooltip"))) {
+        log::warn!("⚠️ 更新托盘提示失败: {}", e);
+    }
+
+    match build_menu(app_handle) {
+        Ok(menu) => {
+            if let Err(e) = tray.set_menu(Some(menu)) {
+                log::warn!("⚠️ 更新托盘菜单失败: {}", e);
+            }
+        }
+        Err(e) => log::warn!("⚠️ 构建托盘菜单失败: {}", e),
+    }
+}
+
+/// 显示主窗口并聚焦
+pub fn show_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// 切换主窗口的显示状态
+pub fn toggle_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        if window.is_visible().unwrap_or(false) {
+            let _ = window.hide();
+        } else {
+            show_main_window(app_handle);
+        }
+    }
+}
+
+/// 记录最近使用的工具（保存在 user-settings.json 的 `tools.recent`）
+pub fn record_recent_tool(app_handle: &AppHandle, id: &str, name: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let settings = load_user_settings(app_handle);
+    let mut recent: Vec<serde_json::Value> = tool_list(settings.get("tools"), "recent")
+        .into_iter()
+        .filter(|(existing, _)| existing != id)
+        .map(|(id, name)| serde_json::json!({ "id": id, "name": name }))
+        .collect();
+    recent.insert(0, serde_json::json!({ "id": id, "name": name.unwrap_or(id) }));
+    recent.truncate(MAX_RECENT_TOOLS);
+
+    write_tool_list(app_handle, settings, "recent", recent)?;
+    refresh_menu(app_handle);
+    Ok(())
+}
+
+/// 收藏的工具（ID 和名称）
+pub fn favorite_tools(app_handle: &AppHandle) -> Vec<(String, String)> {
+    tool_list(load_user_settings(app_handle).get("tools"), "favorites")
+}
+
+/// 保存收藏的工具并重建托盘菜单和应用菜单；未知工具会被忽略
+pub fn set_favorite_tools(app_handle: &AppHandle, tools: &[(String, Option<String>)]) -> Result<(), Box<dyn std::error::Error>> {
+    let favorites: Vec<serde_json::Value> = tools
+        .iter()
+        .filter_map(|(id, name)| {
+            let route = crate::routes::find_tool(id)?;
+            Some(serde_json::json!({ "id": route.id, "name": name.as_deref().unwrap_or(&route.name) }))
+        })
+        .collect();
+
+    write_tool_list(app_handle, load_user_settings(app_handle), "favorites", favorites)?;
+    refresh_menu(app_handle);
+    crate::app_menu::refresh(app_handle);
+    Ok(())
+}
+
+/// 把工具列表写入用户设置 `tools` 对象中的 `<key>`
+fn write_tool_list(
+    app_handle: &AppHandle,
+    mut settings: serde_json::Value,
+    key: &str,
+    list: Vec<serde_json::Value>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !settings.is_object() {
+        settings = serde_json::json!({});
+    }
+
+    let tools = settings.as_object_mut().expect("设置必须是对象")
+        .entry("tools")
+        .or_insert_with(|| serde_json::json!({}));
+    if !tools.is_object() {
+        *tools = serde_json::json!({});
+    }
+    tools.as_object_mut().expect("tools 必须是对象")
+        .insert(key.to_string(), serde_json::Value::Array(list));
+
+    crate::config_profiles::write_effective(app_handle, "user-settings.json", &settings)
+}
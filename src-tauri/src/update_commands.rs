@@ -1,6 +1,7 @@
+use crate::i18n;
 use crate::updater::{UpdateManager, UpdateStatus};
 use crate::update_config::{UpdateConfig, UpdateHistory};
-use tauri::{AppHandle, Manager, State, command};
+use tauri::{AppHandle, State, WebviewWindow, command};
 use std::sync::Mutex;
 
 /// 更新管理器状态
@@ -14,7 +15,7 @@ pub async fn check_for_updates(
 ) -> Result<crate::updater::VersionInfo, String> {
     // 加载配置
     let config = UpdateConfig::load(&app_handle)
-        .map_err(|e| format!("Failed to load update config: {}", e))?;
+        .map_err(|e| i18n::tf("update.load_config_failed", &[("error", &e)]))?;
 
     // 保存更新服务器地址
     let update_server = config.update_server.clone();
@@ -40,7 +41,7 @@ pub async fn check_for_updates(
 
             Ok(version_info)
         }
-        Err(e) => Err(format!("Failed to check for updates: {}", e)),
+        Err(e) => Err(i18n::tf("update.check_failed", &[("error", &e)])),
     }
 }
 
@@ -49,19 +50,19 @@ pub async fn check_for_updates(
 pub async fn apply_hotfix_update(
     update_files: Vec<crate::updater::UpdateFile>,
     update_manager: State<'_, UpdateManagerState>,
-    _app_handle: AppHandle,
+    window: WebviewWindow,
 ) -> Result<String, String> {
     // 先取出 UpdateManager，避免跨 await 持有锁
     let manager = {
         let manager_guard = update_manager.0.lock().unwrap();
         let manager = manager_guard.as_ref()
-            .ok_or("Update manager not initialized")?;
+            .ok_or_else(|| i18n::t("update.not_initialized"))?;
         manager.clone()
     };
 
-    match manager.apply_hotfix(update_files).await {
-        Ok(_) => Ok("Hotfix update applied successfully".to_string()),
-        Err(e) => Err(format!("Failed to apply hotfix update: {}", e)),
+    match manager.apply_hotfix(update_files, window).await {
+        Ok(_) => Ok(i18n::t("update.hotfix_applied")),
+        Err(e) => Err(i18n::tf("update.hotfix_failed", &[("error", &e)])),
     }
 }
 
@@ -69,22 +70,19 @@ pub async fn apply_hotfix_update(
 #[command]
 pub async fn download_major_update(
     download_url: String,
-    app_handle: AppHandle,
+    window: WebviewWindow,
     update_manager: State<'_, UpdateManagerState>,
 ) -> Result<String, String> {
     let manager = {
         let guard = update_manager.0.lock().unwrap();
         guard.as_ref()
-            .ok_or("Update manager not initialized")?
+            .ok_or_else(|| i18n::t("update.not_initialized"))?
             .clone()
     };
 
-    let window = app_handle.get_webview_window("main")
-        .ok_or("Main window not found")?;
-
     match manager.download_major_update(download_url, window).await {
-        Ok(update_file) => Ok(format!("Update downloaded to: {:?}", update_file)),
-        Err(e) => Err(format!("Failed to download major update: {}", e)),
+        Ok(update_file) => Ok(i18n::tf("update.downloaded", &[("path", &update_file.display())])),
+        Err(e) => Err(i18n::tf("update.download_failed", &[("error", &e)])),
     }
 }
 
@@ -92,24 +90,21 @@ pub async fn download_major_update(
 #[command]
 pub async fn install_major_update(
     update_file_path: String,
-    app_handle: AppHandle,
+    window: WebviewWindow,
     update_manager: State<'_, UpdateManagerState>,
 ) -> Result<String, String> {
     let manager = {
         let guard = update_manager.0.lock().unwrap();
         guard.as_ref()
-            .ok_or("Update manager not initialized")?
+            .ok_or_else(|| i18n::t("update.not_initialized"))?
             .clone()
     };
 
-    let window = app_handle.get_webview_window("main")
-        .ok_or("Main window not found")?;
-
     let update_path = std::path::PathBuf::from(update_file_path);
 
     match manager.install_major_update(update_path, window).await {
-        Ok(_) => Ok("Major update installation initiated".to_string()),
-        Err(e) => Err(format!("Failed to install major update: {}", e)),
+        Ok(_) => Ok(i18n::t("update.install_started")),
+        Err(e) => Err(i18n::tf("update.install_failed", &[("error", &e)])),
     }
 }
 
@@ -123,7 +118,7 @@ pub fn get_current_version() -> String {
 #[command]
 pub fn get_update_config(app_handle: AppHandle) -> Result<UpdateConfig, String> {
     UpdateConfig::load(&app_handle)
-        .map_err(|e| format!("Failed to load update config: {}", e))
+        .map_err(|e| i18n::tf("update.load_config_failed", &[("error", &e)]))
 }
 
 /// 更新配置命令
@@ -133,8 +128,8 @@ pub fn update_update_config(
     config: UpdateConfig,
 ) -> Result<String, String> {
     config.save(&app_handle)
-        .map_err(|e| format!("Failed to save update config: {}", e))?;
-    Ok("Update configuration saved successfully".to_string())
+        .map_err(|e| i18n::tf("update.save_config_failed", &[("error", &e)]))?;
+    Ok(i18n::t("update.config_saved"))
 }
 
 /// 忽略版本命令
@@ -144,13 +139,13 @@ pub fn ignore_version(
     version: String,
 ) -> Result<String, String> {
     let mut config = UpdateConfig::load(&app_handle)
-        .map_err(|e| format!("Failed to load update config: {}", e))?;
+        .map_err(|e| i18n::tf("update.load_config_failed", &[("error", &e)]))?;
 
     config.ignore_version(version.clone());
     config.save(&app_handle)
-        .map_err(|e| format!("Failed to save update config: {}", e))?;
+        .map_err(|e| i18n::tf("update.save_config_failed", &[("error", &e)]))?;
 
-    Ok(format!("Version {} ignored", version))
+    Ok(i18n::tf("update.version_ignored", &[("version", &version)]))
 }
 
 /// 获取更新历史命令
@@ -162,7 +157,7 @@ pub fn get_update_history(
     let history = UpdateHistory::new(app_handle);
     let limit = limit.unwrap_or(10);
     history.get_recent_histories(limit)
-        .map_err(|e| format!("Failed to load update history: {}", e))
+        .map_err(|e| i18n::tf("update.load_history_failed", &[("error", &e)]))
 }
 
 /// 清理更新历史命令
@@ -170,8 +165,8 @@ pub fn get_update_history(
 pub fn clear_update_history(app_handle: AppHandle) -> Result<String, String> {
     let history = UpdateHistory::new(app_handle);
     history.clear_histories()
-        .map_err(|e| format!("Failed to clear update history: {}", e))?;
-    Ok("Update history cleared successfully".to_string())
+        .map_err(|e| i18n::tf("update.clear_history_failed", &[("error", &e)]))?;
+    Ok(i18n::t("update.history_cleared"))
 }
 
 /// 重启应用命令
@@ -212,7 +207,7 @@ pub fn request_admin_restart() -> Result<String, String> {
     {
         use std::process::Command;
         let app_path = std::env::current_exe()
-            .map_err(|e| format!("Failed to get app path: {}", e))?;
+            .map_err(|e| i18n::tf("update.app_path_failed", &[("error", &e)]))?;
 
         Command::new("osascript")
             .arg("-e")
@@ -221,13 +216,13 @@ pub fn request_admin_restart() -> Result<String, String> {
                 app_path.display()
             ))
             .spawn()
-            .map_err(|e| format!("Failed to request admin restart: {}", e))?;
+            .map_err(|e| i18n::tf("update.admin_restart_failed", &[("error", &e)]))?;
 
-        Ok("Admin restart requested".to_string())
+        Ok(i18n::t("update.admin_restart_requested"))
     }
     #[cfg(not(target_os = "macos"))]
     {
-        Ok("Admin restart not supported on this platform".to_string())
+        Ok(i18n::t("update.admin_restart_unsupported"))
     }
 }
 
@@ -248,5 +243,5 @@ pub async fn cancel_update(
 ) -> Result<String, String> {
     // 这里可以实现取消逻辑
     // 比如设置一个取消标志，停止下载等
-    Ok("Update cancelled".to_string())
+    Ok(i18n::t("update.cancelled"))
 }
\ No newline at end of file
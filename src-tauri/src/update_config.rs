@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 
 /// 更新配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,12 +45,10 @@ impl Default for UpdateConfig {
 impl UpdateConfig {
     /// 从文件加载配置
     pub fn load(app_handle: &AppHandle) -> Result<Self, Box<dyn std::error::Error>> {
-        let config_dir = app_handle.path().app_config_dir()?;
-        let config_path = config_dir.join("update-config.json");
+        let profile = crate::config_profiles::active_profile(app_handle);
 
-        if config_path.exists() {
-            let content = fs::read_to_string(&config_path)?;
-            let config: UpdateConfig = serde_json::from_str(&content)?;
+        if let Some(value) = crate::config_profiles::read_effective(app_handle, profile.as_deref(), "update-config.json")? {
+            let config: UpdateConfig = serde_json::from_value(value)?;
             Ok(config)
         } else {
             // 如果配置文件不存在，创建默认配置
@@ -62,14 +60,7 @@ impl UpdateConfig {
 
     /// 保存配置到文件
     pub fn save(&self, app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
-        let config_dir = app_handle.path().app_config_dir()?;
-        fs::create_dir_all(&config_dir)?;
-
-        let config_path = config_dir.join("update-config.json");
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(&config_path, content)?;
-
-        Ok(())
+        crate::config_profiles::write_effective(app_handle, "update-config.json", &serde_json::to_value(self)?)
     }
 
     /// 更新最后检查时间
@@ -144,7 +135,7 @@ impl UpdateHistory {
 
     /// 加载更新历史
     pub fn load_histories(&self) -> Result<Vec<VersionHistory>, Box<dyn std::error::Error>> {
-        let config_dir = self.app_handle.path().app_config_dir()?;
+        let config_dir = crate::app_paths::config_dir(&self.app_handle)?;
         let history_path = config_dir.join("update-history.json");
 
         if history_path.exists() {
@@ -158,7 +149,7 @@ impl UpdateHistory {
 
     /// 保存更新历史
     fn save_histories(&self, histories: &[VersionHistory]) -> Result<(), Box<dyn std::error::Error>> {
-        let config_dir = self.app_handle.path().app_config_dir()?;
+        let config_dir = crate::app_paths::config_dir(&self.app_handle)?;
         let history_path = config_dir.join("update-history.json");
         let content = serde_json::to_string_pretty(histories)?;
         fs::write(&history_path, content)?;
@@ -174,7 +165,7 @@ impl UpdateHistory {
 
     /// 清理更新历史
     pub fn clear_histories(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let config_dir = self.app_handle.path().app_config_dir()?;
+        let config_dir = crate::app_paths::config_dir(&self.app_handle)?;
         let history_path = config_dir.join("update-history.json");
         if history_path.exists() {
             fs::remove_file(&history_path)?;
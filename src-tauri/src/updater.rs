@@ -1,3 +1,4 @@
+use crate::i18n;
 use tauri::{Manager, AppHandle, WebviewWindow, Emitter};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -79,15 +80,12 @@ impl UpdateManager {
             let version_info: VersionInfo = response.json().await?;
             Ok(version_info)
         } else {
-            Err("Failed to check for updates".into())
+            Err(i18n::tf("update.server_error", &[("status", &response.status())]).into())
         }
     }
 
-    /// 执行热更新
-    pub async fn apply_hotfix(&self, update_files: Vec<UpdateFile>) -> Result<(), Box<dyn std::error::Error>> {
-        let window = self.app_handle.get_webview_window("main")
-            .ok_or("Main window not found")?;
-
+    /// 执行热更新，进度发送给发起更新的窗口
+    pub async fn apply_hotfix(&self, update_files: Vec<UpdateFile>, window: WebviewWindow) -> Result<(), Box<dyn std::error::Error>> {
         // 发送更新状态
         self.emit_update_status(&window, UpdateStatus::Downloading { progress: 0.0 }).await?;
 
@@ -99,7 +97,7 @@ impl UpdateManager {
             let response = client.get(&file.url).send().await?;
 
             if !response.status().is_success() {
-                return Err(format!("Failed to download file: {}", file.path).into());
+                return Err(i18n::tf("update.file_download_failed", &[("path", &file.path)]).into());
             }
 
             let bytes = response.bytes().await?;
@@ -107,11 +105,11 @@ impl UpdateManager {
             // 验证文件哈希
             let hash = format!("{:x}", sha2::Sha256::digest(&bytes));
             if hash != file.hash {
-                return Err(format!("Hash mismatch for file: {}", file.path).into());
+                return Err(i18n::tf("update.file_hash_mismatch", &[("path", &file.path)]).into());
             }
 
             // 计算更新路径
-            let app_dir = self.app_handle.path().app_config_dir()?;
+            let app_dir = crate::app_paths::config_dir(&self.app_handle)?;
             let file_path = app_dir.join(&file.path);
 
             // 创建目录（如果不存在）
@@ -131,7 +129,7 @@ impl UpdateManager {
         self.emit_update_status(&window, UpdateStatus::Installing).await?;
 
         // 应用热更新
-        self.apply_hotfix_changes().await?;
+        self.apply_hotfix_changes(&window).await?;
 
         // 完成更新
         self.emit_update_status(&window, UpdateStatus::Completed).await?;
@@ -140,18 +138,19 @@ impl UpdateManager {
     }
 
     /// 应用热更新变更
-    async fn apply_hotfix_changes(&self) -> Result<(), Box<dyn std::error::Error>> {
-        // 重启应用以应用更新
-        let window = self.app_handle.get_webview_window("main")
-            .ok_or("Main window not found")?;
-
+    async fn apply_hotfix_changes(&self, window: &WebviewWindow) -> Result<(), Box<dyn std::error::Error>> {
         // 显示重启提示
         window.eval("window.showRestartPrompt = true")?;
 
-        // 延迟重启
+        // 延迟刷新所有应用窗口（包括弹出的工具窗口）
+        let app_handle = self.app_handle.clone();
         tokio::spawn(async move {
             sleep(Duration::from_secs(3)).await;
-            let _ = window.eval("window.location.reload()");
+            for (label, window) in app_handle.webview_windows() {
+                if label != crate::splash::SPLASH_LABEL {
+                    let _ = window.reload();
+                }
+            }
         });
 
         Ok(())
@@ -165,15 +164,21 @@ impl UpdateManager {
         let response = client.get(&download_url).send().await?;
 
         if !response.status().is_success() {
-            return Err("Failed to download update".into());
+            return Err(i18n::tf("update.download_rejected", &[("status", &response.status())]).into());
         }
 
         let total_size = response.content_length().unwrap_or(0);
         let mut downloaded = 0u64;
         let mut stream = response.bytes_stream();
 
-        // 创建临时文件
-        let temp_dir = std::env::temp_dir();
+        // 创建临时文件（便携模式下保存到便携目录，方便手动替换）
+        let temp_dir = if crate::app_paths::is_portable() {
+            let updates_dir = crate::app_paths::updates_dir(&self.app_handle)?;
+            fs::create_dir_all(&updates_dir)?;
+            updates_dir
+        } else {
+            std::env::temp_dir()
+        };
         let update_file = temp_dir.join(format!("util-update-{}.dmg", chrono::Utc::now().timestamp()));
         let mut file = fs::File::create(&update_file)?;
 
@@ -194,6 +199,11 @@ impl UpdateManager {
 
     /// 安装大版本更新
     pub async fn install_major_update(&self, update_file: PathBuf, window: WebviewWindow) -> Result<(), Box<dyn std::error::Error>> {
+        // 便携模式不安装到系统目录，由用户手动替换可执行文件
+        if crate::app_paths::is_portable() {
+            return Err(i18n::tf("update.portable_manual_install", &[("path", &update_file.display())]).into());
+        }
+
         self.emit_update_status(&window, UpdateStatus::Installing).await?;
 
         // 在 macOS 上，打开 DMG 文件
@@ -206,10 +216,9 @@ impl UpdateManager {
                 .arg(&update_file)
                 .spawn()?;
 
-            // 显示安装说明
-            window.eval(r#"
-                alert('更新已下载完成！\n\n请按照以下步骤安装新版本：\n1. 在弹出的 Finder 窗口中，将应用拖拽到 Applications 文件夹\n2. 等待安装完成\n3. 重新启动应用');
-            "#)?;
+            // 显示安装说明（转成 JSON 字符串，避免文案中的引号破坏脚本）
+            let instructions = serde_json::to_string(&i18n::t("update.install_instructions"))?;
+            window.eval(format!("alert({});", instructions))?;
         }
 
         Ok(())
@@ -217,7 +226,8 @@ impl UpdateManager {
 
     /// 发送更新状态到前端
     async fn emit_update_status(&self, window: &WebviewWindow, status: UpdateStatus) -> Result<(), Box<dyn std::error::Error>> {
-        window.emit("update-status", &status)?;
+        // 只发给发起更新的窗口
+        window.emit_to(window.label(), "update-status", &status)?;
         Ok(())
     }
 
@@ -270,6 +280,6 @@ async fn check_for_updates_impl(update_server: &str, current_version: &str) -> R
         let version_info: VersionInfo = response.json().await?;
         Ok(version_info)
     } else {
-        Err("Failed to check for updates".into())
+        Err(i18n::tf("update.server_error", &[("status", &response.status())]).into())
     }
 }
\ No newline at end of file
@@ -0,0 +1,254 @@
+use crate::i18n;
+use crate::secrets_vault::{self, SecretsVaultState, VaultEntryInfo, VaultStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter, State, command};
+use zeroize::Zeroizing;
+
+/// 通过保险库发起的 HTTP 请求
+///
+/// `url`、`headers` 和 `body` 中的 `{{vault:名称}}` 会在后端替换为对应密钥
+#[derive(Debug, Clone, Deserialize)]
+pub struct VaultHttpRequest {
+    pub method: Option<String>,
+    pub url: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    pub body: Option<String>,
+}
+
+/// 通过保险库发起的 HTTP 请求的响应（已去除密钥明文）
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultHttpResponse {
+    pub ok: bool,
+    pub status: u16,
+    pub body: String,
+}
+
+/// 获取保险库状态
+#[command]
+pub async fn vault_status(
+    app_handle: AppHandle,
+    vault: State<'_, SecretsVaultState>,
+) -> Result<VaultStatus, String> {
+    secrets_vault::status(&app_handle, &vault)
+        .map_err(|e| i18n::tf("vault.status_failed", &[("error", &e)]))
+}
+
+/// 创建保险库
+#[command]
+pub async fn vault_initialize(
+    app_handle: AppHandle,
+    vault: State<'_, SecretsVaultState>,
+    master_password: String,
+) -> Result<String, String> {
+    let master_password = Zeroizing::new(master_password);
+    secrets_vault::initialize(&app_handle, &vault, &master_password)
+        .map_err(|e| i18n::tf("vault.initialize_failed", &[("error", &e)]))?;
+    Ok(i18n::t("vault.initialized"))
+}
+
+/// 解锁保险库
+#[command]
+pub async fn vault_unlock(
+    app_handle: AppHandle,
+    vault: State<'_, SecretsVaultState>,
+    master_password: String,
+) -> Result<String, String> {
+    let master_password = Zeroizing::new(master_password);
+    secrets_vault::unlock(&app_handle, &vault, &master_password)
+        .map_err(|e| i18n::tf("vault.unlock_failed", &[("error", &e)]))?;
+    let _ = app_handle.emit("vault-unlocked", ());
+    Ok(i18n::t("vault.unlocked"))
+}
+
+/// 锁定保险库
+#[command]
+pub async fn vault_lock(
+    app_handle: AppHandle,
+    vault: State<'_, SecretsVaultState>,
+) -> Result<String, String> {
+    vault.0.lock().unwrap().lock();
+    let _ = app_handle.emit("vault-locked", "manual");
+    Ok(i18n::t("vault.locked"))
+}
+
+/// 修改主密码
+#[command]
+pub async fn vault_change_password(
+    app_handle: AppHandle,
+    vault: State<'_, SecretsVaultState>,
+    old_password: String,
+    new_password: String,
+) -> Result<String, String> {
+    let old_password = Zeroizing::new(old_password);
+    let new_password = Zeroizing::new(new_password);
+    secrets_vault::change_password(&app_handle, &vault, &old_password, &new_password)
+        .map_err(|e| i18n::tf("vault.change_password_failed", &[("error", &e)]))?;
+    Ok(i18n::t("vault.password_changed"))
+}
+
+/// 设置空闲自动锁定时间
+#[command]
+pub async fn vault_set_auto_lock(
+    app_handle: AppHandle,
+    vault: State<'_, SecretsVaultState>,
+    minutes: u32,
+) -> Result<String, String> {
+    secrets_vault::set_auto_lock(&app_handle, &vault, minutes)
+        .map_err(|e| i18n::tf("vault.auto_lock_failed", &[("error", &e)]))?;
+    Ok(i18n::t("vault.auto_lock_updated"))
+}
+
+/// 列出保险库中的密钥（不含明文）
+#[command]
+pub async fn vault_list_secrets(app_handle: AppHandle) -> Result<Vec<VaultEntryInfo>, String> {
+    secrets_vault::list_secrets(&app_handle)
+        .map_err(|e| i18n::tf("vault.list_failed", &[("error", &e)]))
+}
+
+/// 保存密钥（只写，不会返回明文）
+#[command]
+pub async fn vault_set_secret(
+    app_handle: AppHandle,
+    vault: State<'_, SecretsVaultState>,
+    name: String,
+    value: String,
+    description: Option<String>,
+    allowed_hosts: Option<Vec<String>>,
+) -> Result<String, String> {
+    let value = Zeroizing::new(value);
+    secrets_vault::set_secret(&app_handle, &vault, &name, &value, description, allowed_hosts.unwrap_or_default())
+        .map_err(|e| i18n::tf("vault.save_secret_failed", &[("error", &e)]))?;
+    Ok(i18n::tf("vault.secret_saved", &[("name", &name)]))
+}
+
+/// 删除密钥
+#[command]
+pub async fn vault_delete_secret(
+    app_handle: AppHandle,
+    vault: State<'_, SecretsVaultState>,
+    name: String,
+) -> Result<String, String> {
+    secrets_vault::delete_secret(&app_handle, &vault, &name)
+        .map_err(|e| i18n::tf("vault.delete_secret_failed", &[("error", &e)]))?;
+    Ok(i18n::tf("vault.secret_deleted", &[("name", &name)]))
+}
+
+/// 把密钥复制到剪贴板（明文不返回给前端）
+#[command]
+pub async fn vault_copy_secret(
+    app_handle: AppHandle,
+    vault: State<'_, SecretsVaultState>,
+    name: String,
+) -> Result<String, String> {
+    secrets_vault::copy_secret(&app_handle, &vault, &name)
+        .map_err(|e| i18n::tf("vault.copy_secret_failed", &[("error", &e)]))?;
+    Ok(i18n::tf("vault.secret_copied", &[("name", &name)]))
+}
+
+/// 使用保险库中的密钥发起 HTTPS 请求，密钥明文不会返回给前端；只能发送到密钥允许的主机
+#[command]
+pub async fn vault_http_request(
+    app_handle: AppHandle,
+    vault: State<'_, SecretsVaultState>,
+    request: VaultHttpRequest,
+) -> Result<VaultHttpResponse, String> {
+    let parsed_url = url::Url::parse(&request.url)
+        .map_err(|e| i18n::tf("vault.invalid_url", &[("error", &e)]))?;
+    // 密钥只通过 https 发送
+    if parsed_url.scheme() != "https" {
+        return Err(i18n::tf("vault.unsupported_scheme", &[("scheme", &parsed_url.scheme())]));
+    }
+    let host = parsed_url.host_str().unwrap_or_default().to_string();
+
+    // 收集请求中引用的密钥
+    let mut referenced = Vec::new();
+    collect_placeholders(&request.url, &mut referenced);
+    for (name, value) in &request.headers {
+        collect_placeholders(name, &mut referenced);
+        collect_placeholders(value, &mut referenced);
+    }
+    if let Some(body) = &request.body {
+        collect_placeholders(body, &mut referenced);
+    }
+
+    let mut secrets: Vec<(String, Zeroizing<String>)> = Vec::new();
+    for name in referenced {
+        let value = secrets_vault::with_secret_for_host(&app_handle, &vault, &name, &host, |v| Zeroizing::new(v.to_string()))
+            .map_err(|e| i18n::tf("vault.read_secret_failed", &[("name", &name), ("error", &e)]))?;
+        secrets.push((name, value));
+    }
+
+    let substitute = |text: &str| -> Zeroizing<String> {
+        let mut result = Zeroizing::new(text.to_string());
+        for (name, value) in &secrets {
+            *result = result.replace(&format!("{{{{vault:{}}}}}", name), value);
+        }
+        result
+    };
+
+    let method = reqwest::Method::from_bytes(request.method.as_deref().unwrap_or("GET").to_uppercase().as_bytes())
+        .map_err(|e| i18n::tf("vault.invalid_method", &[("error", &e)]))?;
+
+    // 替换后的地址必须仍指向校验过的主机
+    let final_url = url::Url::parse(substitute(&request.url).as_str())
+        .map_err(|e| i18n::tf("vault.invalid_url", &[("error", &redact(&e.to_string(), &secrets))]))?;
+    if final_url.scheme() != "https" || final_url.host_str().unwrap_or_default() != host {
+        return Err(i18n::tf("vault.host_changed", &[("host", &host)]));
+    }
+
+    // 不跟随重定向，避免密钥被转发到其他主机
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|e| i18n::tf("vault.request_failed", &[("error", &e)]))?;
+    let mut builder = client.request(method, final_url.as_str());
+    for (name, value) in &request.headers {
+        builder = builder.header(substitute(name).as_str(), substitute(value).as_str());
+    }
+    if let Some(body) = &request.body {
+        builder = builder.body(substitute(body).to_string());
+    }
+
+    let response = builder.send().await
+        .map_err(|e| i18n::tf("vault.request_failed", &[("error", &redact(&e.to_string(), &secrets))]))?;
+    let status = response.status();
+    let body = response.text().await
+        .map_err(|e| i18n::tf("vault.read_response_failed", &[("error", &e)]))?;
+
+    Ok(VaultHttpResponse {
+        ok: status.is_success(),
+        status: status.as_u16(),
+        body: redact(&body, &secrets),
+    })
+}
+
+/// 收集文本中的 `{{vault:名称}}` 占位符
+fn collect_placeholders(text: &str, names: &mut Vec<String>) {
+    let mut rest = text;
+    while let Some(start) = rest.find("{{vault:") {
+        let after = &rest[start + "{{vault:".len()..];
+        match after.find("}}") {
+            Some(end) => {
+                let name = after[..end].to_string();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+                rest = &after[end + 2..];
+            }
+            None => break,
+        }
+    }
+}
+
+/// 将响应中出现的密钥明文替换为占位符
+fn redact(text: &str, secrets: &[(String, Zeroizing<String>)]) -> String {
+    let mut result = text.to_string();
+    for (name, value) in secrets {
+        if !value.is_empty() {
+            result = result.replace(value.as_str(), &format!("{{{{vault:{}}}}}", name));
+        }
+    }
+    result
+}
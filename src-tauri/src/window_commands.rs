@@ -0,0 +1,16 @@
+use crate::i18n;
+use crate::tool_windows::{self, ToolWindow};
+use tauri::{AppHandle, command};
+
+/// 在独立窗口中打开工具
+#[command]
+pub async fn open_tool_window(app_handle: AppHandle, tool: String) -> Result<ToolWindow, String> {
+    tool_windows::open(&app_handle, &tool)
+        .map_err(|e| i18n::tf("window.open_tool_failed", &[("error", &e)]))
+}
+
+/// 获取当前打开的工具窗口
+#[command]
+pub async fn list_tool_windows(app_handle: AppHandle) -> Result<Vec<ToolWindow>, String> {
+    Ok(tool_windows::list(&app_handle))
+}
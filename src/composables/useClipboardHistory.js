@@ -0,0 +1,43 @@
+// 原生剪贴板历史 composable：桌面端由后台记录系统剪贴板，页面通过命令读取和操作
+export const useClipboardHistory = () => {
+  const tauri = typeof window !== 'undefined' ? window.__TAURI__ : null
+  const isNative = !!tauri
+
+  const invoke = (command, args) => {
+    const call = tauri.core?.invoke ?? tauri.invoke
+    return call(command, args)
+  }
+
+  // 转换为页面使用的记录格式
+  const toItem = (entry) => ({
+    id: entry.id,
+    nativeId: entry.id,
+    kind: entry.kind,
+    content: entry.kind === 'image' ? `[图片 ${entry.image.width}×${entry.image.height}]` : entry.text,
+    title: '',
+    category: entry.kind === 'image' ? 'image' : '',
+    tags: '',
+    favorite: entry.pinned,
+    timestamp: Date.parse(entry.last_used_at),
+    usageCount: entry.copy_count
+  })
+
+  const list = async (limit = 1000) => (await invoke('get_clipboard_history', { limit })).map(toItem)
+  const search = async (query, kind = null) => (await invoke('search_clipboard_history', { query, kind })).map(toItem)
+  const copy = (id) => invoke('copy_clipboard_entry', { id })
+  const pin = (id, pinned) => invoke('pin_clipboard_entry', { id, pinned })
+  const remove = (id) => invoke('delete_clipboard_entry', { id })
+  const clear = (keepPinned = true) => invoke('clear_clipboard_history', { keepPinned })
+  const getImage = (id) => invoke('get_clipboard_image', { id })
+  const getConfig = () => invoke('get_clipboard_history_config')
+  const saveConfig = (config) => invoke('save_clipboard_history_config', { config })
+  const setPaused = (paused) => invoke('set_clipboard_history_paused', { paused })
+
+  // 后台记录到新内容时回调
+  const onChanged = async (callback) => {
+    if (!isNative) return () => {}
+    return tauri.event.listen('clipboard-history-changed', (event) => callback(toItem(event.payload)))
+  }
+
+  return { isNative, list, search, copy, pin, remove, clear, getImage, getConfig, saveConfig, setPaused, onChanged }
+}
@@ -0,0 +1,27 @@
+// 深层链接 composable：工具页面读取 utilx:// 链接中预填的输入或文件
+import { computed } from 'vue'
+
+export const useDeepLink = (toolId) => {
+  const pending = useState('deepLink', () => null)
+
+  // 只返回属于当前工具的链接内容
+  const payload = computed(() => (pending.value?.tool === toolId ? pending.value : null))
+
+  // 页面使用后清除，避免重复预填
+  const consume = async () => {
+    const link = payload.value
+    pending.value = null
+    const tauri = typeof window !== 'undefined' ? window.__TAURI__ : null
+    if (tauri) {
+      const invoke = tauri.core?.invoke ?? tauri.invoke
+      try {
+        await invoke('take_deep_link', { tool: toolId })
+      } catch (error) {
+        console.warn('清除深层链接失败:', error)
+      }
+    }
+    return link
+  }
+
+  return { payload, consume }
+}
@@ -10,6 +10,23 @@ export const useFavorites = () => {
 
   const saveFavorites = () => {
     localStorage.setItem('favoriteTools', JSON.stringify(favorites.value))
+    syncNativeFavorites()
+  }
+
+  // 桌面端同步到用户设置，用于托盘菜单和应用菜单
+  const syncNativeFavorites = async () => {
+    const tauri = typeof window !== 'undefined' ? window.__TAURI__ : null
+    if (!tauri) return
+
+    const tools = favorites.value
+      .map(fav => ({ id: fav.url?.match(/\/tools\/([^/?#]+)/)?.[1], name: fav.name }))
+      .filter(tool => tool.id)
+    try {
+      const invoke = tauri.core?.invoke ?? tauri.invoke
+      await invoke('save_favorite_tools', { tools })
+    } catch (error) {
+      console.warn('同步收藏失败:', error)
+    }
   }
 
   const toggleFavorite = (tool) => {
@@ -0,0 +1,77 @@
+// 密钥保险库 composable：桌面端把密钥加密保存在后端，页面只能写入、复制到剪贴板或在请求中以 {{vault:名称}} 引用
+import { ref, onMounted, onUnmounted } from 'vue'
+
+export const useSecretsVault = () => {
+  const tauri = typeof window !== 'undefined' ? window.__TAURI__ : null
+  const isNative = !!tauri
+
+  const invoke = (command, args) => {
+    const call = tauri.core?.invoke ?? tauri.invoke
+    return call(command, args)
+  }
+
+  const status = ref({ initialized: false, unlocked: false, auto_lock_minutes: 15, entry_count: 0 })
+
+  const refresh = async () => {
+    if (!isNative) return status.value
+    status.value = await invoke('vault_status')
+    return status.value
+  }
+
+  const initialize = async (masterPassword) => {
+    await invoke('vault_initialize', { masterPassword })
+    return refresh()
+  }
+  const unlock = async (masterPassword) => {
+    await invoke('vault_unlock', { masterPassword })
+    return refresh()
+  }
+  const lock = async () => {
+    await invoke('vault_lock')
+    return refresh()
+  }
+
+  const list = () => invoke('vault_list_secrets')
+  const setSecret = (name, value, { description = null, allowedHosts = [] } = {}) =>
+    invoke('vault_set_secret', { name, value, description, allowedHosts })
+  const deleteSecret = (name) => invoke('vault_delete_secret', { name })
+  const copySecret = (name) => invoke('vault_copy_secret', { name })
+  const httpRequest = (request) => invoke('vault_http_request', { request })
+
+  // 密钥名称只允许字母、数字和 -_.，用前缀加随机后缀生成
+  const newSecretName = (prefix) => {
+    const bytes = crypto.getRandomValues(new Uint8Array(6))
+    return `${prefix}.${Array.from(bytes, b => b.toString(16).padStart(2, '0')).join('')}`
+  }
+
+  const placeholder = (name) => `{{vault:${name}}}`
+
+  // 保险库在其他页面或空闲超时后锁定/解锁时刷新状态
+  const unlisteners = []
+  onMounted(async () => {
+    if (!isNative) return
+    await refresh().catch(err => console.warn('读取保险库状态失败:', err))
+    for (const event of ['vault-locked', 'vault-unlocked']) {
+      unlisteners.push(await tauri.event.listen(event, () => refresh().catch(() => {})))
+    }
+  })
+  onUnmounted(() => {
+    unlisteners.forEach((unlisten) => unlisten())
+  })
+
+  return {
+    isNative,
+    status,
+    refresh,
+    initialize,
+    unlock,
+    lock,
+    list,
+    setSecret,
+    deleteSecret,
+    copySecret,
+    httpRequest,
+    newSecretName,
+    placeholder
+  }
+}
@@ -5,22 +5,19 @@ export const useTauri = () => {
   const isTauri = typeof window !== 'undefined' && window.__TAURI__
   const { showSuccess, showError, showInfo } = useNotification()
 
-  // 打开外部链接
+  // 打开外部链接（桌面端由 Rust 校验协议和主机，未知主机会先请求确认）
   const openExternalLink = async (url) => {
     if (isTauri) {
       try {
-        await window.__TAURI__.invoke('open_external_link', { url })
-
-        // 显示成功通知
-        showSuccess(`正在打开: ${url}`)
+        const invoke = window.__TAURI__.core?.invoke ?? window.__TAURI__.invoke
+        const result = await invoke('open_external_link', { url })
 
+        if (result?.status === 'opened') {
+          showSuccess(`正在打开: ${result.url}`)
+        }
       } catch (error) {
-
-        // 显示错误通知
+        // 被拒绝的链接不再降级到 window.open
         showError(`打开链接失败: ${error.message || error}`)
-
-        // 降级到普通方式打开
-        window.open(url, '_blank')
       }
     } else {
       // Web 环境
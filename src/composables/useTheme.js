@@ -1,6 +1,6 @@
 // 主题管理 composable
 export const useTheme = () => {
-  // 获取当前主题
+  // 获取当前主题偏好
   const getTheme = () => {
     if (process.client) {
       return localStorage.getItem('theme') || 'dark'
@@ -8,8 +8,21 @@ export const useTheme = () => {
     return 'dark'
   }
 
+  // 桌面端把偏好保存到用户设置，由 Rust 端应用到原生窗口并广播 theme-changed
+  const saveDesktopPreference = async (theme) => {
+    const tauri = window.__TAURI__
+    if (!tauri) return
+    const invoke = tauri.core?.invoke ?? tauri.invoke
+    try {
+      const settings = await invoke('get_user_settings')
+      await invoke('save_user_settings', { settings: { ...settings, theme } })
+    } catch (error) {
+      console.warn('保存主题设置失败:', error)
+    }
+  }
+
   // 设置主题
-  const setTheme = (theme) => {
+  const setTheme = async (theme) => {
     if (process.client) {
       const html = document.documentElement
       if (theme === 'dark') {
@@ -18,13 +31,14 @@ export const useTheme = () => {
         html.classList.remove('dark')
       }
       localStorage.setItem('theme', theme)
+      await saveDesktopPreference(theme)
     }
   }
 
-  // 切换主题
+  // 切换主题（以当前显示的主题为准，跟随系统时也能切换）
   const toggleTheme = () => {
-    const currentTheme = getTheme()
-    const newTheme = currentTheme === 'dark' ? 'light' : 'dark'
+    const isDark = process.client && document.documentElement.classList.contains('dark')
+    const newTheme = isDark ? 'light' : 'dark'
     setTheme(newTheme)
     return newTheme
   }
@@ -43,4 +57,4 @@ export const useTheme = () => {
     toggleTheme,
     initTheme
   }
-}
\ No newline at end of file
+}
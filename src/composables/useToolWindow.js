@@ -0,0 +1,36 @@
+// 工具独立窗口 composable：桌面端可以把工具弹出到单独的窗口
+export const useToolWindow = () => {
+  const tauri = typeof window !== 'undefined' ? window.__TAURI__ : null
+  const isNative = !!tauri
+
+  // 当前窗口标签（主窗口为 main，工具窗口为 tool-<工具 ID>，迷你模式为 mini）
+  const label = isNative ? tauri.window.getCurrentWindow().label : null
+  const isMiniWindow = label === 'mini'
+  const isToolWindow = !!label && (label.startsWith('tool-') || isMiniWindow)
+
+  const invoke = (command, args) => {
+    const call = tauri.core?.invoke ?? tauri.invoke
+    return call(command, args)
+  }
+
+  // 在独立窗口中打开工具
+  const popOut = (tool) => invoke('open_tool_window', { tool })
+
+  // 当前打开的工具窗口
+  const list = () => invoke('list_tool_windows')
+
+  // 在置顶的迷你窗口中打开工具
+  const openMini = (tool) => invoke('open_mini_mode', { tool })
+  const getMiniConfig = () => invoke('get_mini_mode_config')
+
+  // 记录最近使用的工具（托盘菜单的“最近使用”）
+  const recordRecent = (tool, name) => invoke('record_recent_tool', { toolId: tool, name: name || null })
+
+  // 迷你模式配置变化（透明度、鼠标穿透）时回调
+  const onMiniChanged = async (callback) => {
+    if (!isNative) return () => {}
+    return tauri.event.listen('mini-mode-changed', (event) => callback(event.payload))
+  }
+
+  return { isNative, isToolWindow, isMiniWindow, label, popOut, list, openMini, getMiniConfig, onMiniChanged, recordRecent }
+}
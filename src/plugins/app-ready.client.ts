@@ -0,0 +1,13 @@
+// 启动握手插件 - 客户端
+// 应用挂载后通知 Rust 端主页面已就绪，启动画面据此结束
+export default defineNuxtPlugin((nuxtApp) => {
+  const tauri = (window as any).__TAURI__
+  if (!tauri) return
+
+  const invoke = tauri.core?.invoke ?? tauri.invoke
+  nuxtApp.hook('app:mounted', () => {
+    invoke('app_ready').catch((error: unknown) => {
+      console.warn('通知应用就绪失败:', error)
+    })
+  })
+})
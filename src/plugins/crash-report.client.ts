@@ -0,0 +1,11 @@
+// 崩溃报告插件 - 客户端
+// 主页面就绪后 Rust 端发送 crash-report-pending 事件，这里在应用挂载前注册监听，由 CrashReportPrompt 组件提示用户
+export default defineNuxtPlugin(async () => {
+  const tauri = (window as any).__TAURI__
+  if (!tauri?.event) return
+
+  const pending = useState<any[]>('crashReports', () => [])
+  await tauri.event.listen('crash-report-pending', (event: any) => {
+    pending.value = event.payload ?? []
+  })
+})
@@ -0,0 +1,42 @@
+// 深层链接插件 - 客户端
+// Rust 端解析 utilx://tool/<id>?input=...（或 file=...，文件内容由 Rust 端读取）后发送 deep-link 事件，这里负责跳转到对应工具
+// 带 file 参数的链接先征得用户同意，同意后 Rust 端才读取文件
+export default defineNuxtPlugin(async () => {
+  const tauri = (window as any).__TAURI__
+  if (!tauri) return
+
+  const invoke = tauri.core?.invoke ?? tauri.invoke
+  const pending = useState<any>('deepLink', () => null)
+
+  // 需要读取本地文件的链接：询问用户，同意后换成带文件内容的链接，拒绝时丢弃文件只保留其他参数
+  const confirmFile = async (link: any) => {
+    if (!link?.confirm_id) return link
+    const allow = window.confirm(`外部链接请求读取本地文件：\n\n${link.file}\n\n确定要在 ${link.tool} 中打开吗？`)
+    try {
+      const loaded = await invoke('confirm_deep_link_file', { id: link.confirm_id, allow })
+      if (loaded) return loaded
+    } catch (error) {
+      console.warn('读取深层链接文件失败:', error)
+    }
+    return { ...link, file: null, confirm_id: null }
+  }
+
+  const openLink = async (link: any) => {
+    if (!link?.tool) return
+    pending.value = await confirmFile(link)
+    await navigateTo(`/tools/${link.tool}/`)
+  }
+
+  // 通过深层链接启动时，链接内容在页面加载前已保存在 Rust 端
+  try {
+    const link = await invoke('take_deep_link', { tool: null })
+    if (link) await openLink(link)
+  } catch (error) {
+    console.warn('读取深层链接失败:', error)
+  }
+
+  // 程序运行中收到的深层链接：跳转由 Rust 端的 navigate 事件完成，这里只保存预填内容
+  tauri.event?.listen('deep-link', async (event: any) => {
+    pending.value = await confirmFile(event.payload)
+  })
+})
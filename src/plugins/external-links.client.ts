@@ -0,0 +1,18 @@
+// 外部链接确认插件 - 客户端
+// Rust 端遇到未被信任的主机时发出确认事件，由用户决定是否打开
+export default defineNuxtPlugin(() => {
+  const tauri = (window as any).__TAURI__
+  if (!tauri) return
+
+  const invoke = tauri.core?.invoke ?? tauri.invoke
+  tauri.event.listen('external-link-confirm', async (event: { payload: { id: string, url: string, host: string } }) => {
+    const { id, url, host } = event.payload
+    const allow = window.confirm(`即将使用浏览器打开外部链接：\n\n${url}\n\n确定要打开 ${host} 吗？`)
+    const remember = allow && window.confirm(`以后打开 ${host} 的链接时不再询问？`)
+    try {
+      await invoke('confirm_external_link', { id, allow, remember })
+    } catch (error) {
+      console.warn('打开外部链接失败:', error)
+    }
+  })
+})
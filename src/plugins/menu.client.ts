@@ -28,20 +28,13 @@ export default defineNuxtPlugin(() => {
           // 降级到客户端路由
           switch (page) {
             case 'home':
-              // 强制返回首页，处理外部链接情况
-              console.log('🏠 [CLIENT] 强制返回首页')
-              if (window.location.hostname !== 'localhost' && window.location.hostname !== '127.0.0.1') {
-                console.log('检测到外部链接，强制返回应用首页')
-                window.location.replace('/')
-              } else {
-                window.location.href = '/'
-              }
+              navigateTo('/')
               break
             case 'updates':
-              window.location.href = '/feedback/'
+              navigateTo('/feedback/')
               break
             case 'about':
-              window.location.href = '/about/'
+              navigateTo('/about/')
               break
           }
         }
@@ -0,0 +1,15 @@
+// 导航插件 - 客户端
+// 菜单、托盘、深层链接等由 Rust 端路由表解析后发送 navigate 事件，这里统一交给前端路由处理
+export default defineNuxtPlugin(() => {
+  const tauri = (window as any).__TAURI__
+  if (!tauri?.event) return
+
+  tauri.event.listen('navigate', (event: any) => {
+    const { path } = event.payload || {}
+    if (typeof path !== 'string' || !path.startsWith('/')) {
+      console.warn('忽略无效的导航事件:', event.payload)
+      return
+    }
+    navigateTo(path)
+  })
+})
@@ -2,6 +2,21 @@
 export default defineNuxtPlugin(() => {
   // 只在客户端执行
   if (process.client) {
+    // 桌面端由 Rust 端根据主题设置和系统外观决定实际主题；
+    // 这里只应用实际主题，不写入 localStorage 中的主题偏好
+    const tauri = window.__TAURI__
+    if (tauri) {
+      const applyTheme = ({ effective }) => {
+        document.documentElement.classList.toggle('dark', effective === 'dark')
+      }
+      const invoke = tauri.core?.invoke ?? tauri.invoke
+      invoke('get_theme_state').then(applyTheme).catch((error) => {
+        console.warn('获取主题失败:', error)
+      })
+      tauri.event.listen('theme-changed', (event) => applyTheme(event.payload))
+      return
+    }
+
     // 确保DOM已加载
     onMounted(() => {
       // 检查并应用保存的主题
@@ -16,4 +31,4 @@ export default defineNuxtPlugin(() => {
       }
     })
   }
-})
\ No newline at end of file
+})